@@ -0,0 +1,261 @@
+//! Build script: bakes the label font into a signed-distance-field atlas.
+//!
+//! The source is a classic 5×7 bitmap font (printable ASCII, column-major,
+//! least-significant bit at the top), followed by the accented lower-case
+//! letters the French catalogue needs and the symbols of measurement labels.
+//! Each glyph is upscaled and converted into a distance field so labels stay
+//! crisp at any size on screen.
+//!
+//! Outputs in `OUT_DIR`:
+//! - `font_atlas.bin` — single-channel atlas, one byte per texel
+//!   (128 = glyph edge, larger = inside)
+//! - `font_atlas.rs` — atlas dimensions and glyph-grid constants, included
+//!   by `src/renderer/font.rs`
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// First character in the font table.
+const FIRST_CHAR: u8 = b' ';
+/// Source glyph size in font pixels.
+const GLYPH_W: usize = 5;
+const GLYPH_H: usize = 7;
+/// Atlas texels per font pixel.
+const SCALE: usize = 4;
+/// Empty border around each glyph, in atlas texels (room for the field to fall off).
+const PADDING: usize = 6;
+/// Distance (in atlas texels) mapped to the full 0–255 range on each side of the edge.
+const SPREAD: f32 = 6.0;
+/// Glyphs per atlas row.
+const COLUMNS: usize = 16;
+
+const CELL_W: usize = GLYPH_W * SCALE + 2 * PADDING;
+const CELL_H: usize = GLYPH_H * SCALE + 2 * PADDING;
+
+#[rustfmt::skip]
+const FONT_5X7: [[u8; GLYPH_W]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+/// Accented letters placed after the ASCII glyphs. Lower-case letters leave
+/// the top two rows free for the accent; `ç` is raised a row to make room
+/// for the cedilla.
+#[rustfmt::skip]
+const ACCENTED: [(char, [u8; GLYPH_W]); 13] = [
+    ('à', [0x20, 0x55, 0x56, 0x54, 0x78]),
+    ('â', [0x20, 0x56, 0x55, 0x56, 0x78]),
+    ('ç', [0x1C, 0x22, 0x62, 0x22, 0x10]),
+    ('é', [0x38, 0x54, 0x56, 0x55, 0x18]),
+    ('è', [0x38, 0x55, 0x56, 0x54, 0x18]),
+    ('ê', [0x38, 0x56, 0x55, 0x56, 0x18]),
+    ('ë', [0x38, 0x56, 0x54, 0x56, 0x18]),
+    ('î', [0x00, 0x46, 0x7D, 0x42, 0x00]),
+    ('ï', [0x00, 0x46, 0x7C, 0x42, 0x00]),
+    ('ô', [0x38, 0x46, 0x45, 0x46, 0x38]),
+    ('ù', [0x3C, 0x41, 0x42, 0x20, 0x7C]),
+    ('û', [0x3C, 0x42, 0x41, 0x22, 0x7C]),
+    ('ü', [0x3C, 0x42, 0x40, 0x22, 0x7C]),
+];
+
+/// Symbols placed after the accented letters.
+#[rustfmt::skip]
+const SYMBOLS: [(char, [u8; GLYPH_W]); 2] = [
+    ('·', [0x00, 0x0C, 0x0C, 0x00, 0x00]),
+    ('°', [0x00, 0x02, 0x05, 0x02, 0x00]),
+];
+
+/// Whether font pixel `(x, y)` of `glyph` is set (out of range = empty).
+fn pixel(glyph: &[u8; GLYPH_W], x: i32, y: i32) -> bool {
+    if x < 0 || y < 0 || x >= GLYPH_W as i32 || y >= GLYPH_H as i32 {
+        return false;
+    }
+    glyph[x as usize] & (1 << y) != 0
+}
+
+/// Distance from point `(px, py)` to the axis-aligned square covering font pixel `(x, y)`.
+fn distance_to_pixel(px: f32, py: f32, x: i32, y: i32) -> f32 {
+    let (x0, y0) = ((x * SCALE as i32) as f32, (y * SCALE as i32) as f32);
+    let (x1, y1) = (x0 + SCALE as f32, y0 + SCALE as f32);
+    let dx = (x0 - px).max(0.0).max(px - x1);
+    let dy = (y0 - py).max(0.0).max(py - y1);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Signed distance (positive inside) from a texel centre to the glyph outline.
+fn signed_distance(glyph: &[u8; GLYPH_W], px: f32, py: f32) -> f32 {
+    let fx = (px / SCALE as f32).floor() as i32;
+    let fy = (py / SCALE as f32).floor() as i32;
+    let inside = pixel(glyph, fx, fy);
+
+    // Nearest font pixel of the opposite state; the search window covers SPREAD.
+    let reach = (SPREAD / SCALE as f32).ceil() as i32 + 1;
+    let mut best = SPREAD;
+    for y in fy - reach..=fy + reach {
+        for x in fx - reach..=fx + reach {
+            if pixel(glyph, x, y) != inside {
+                best = best.min(distance_to_pixel(px, py, x, y));
+            }
+        }
+    }
+    if inside { best } else { -best }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let ascii_count = FONT_5X7.len();
+    let glyphs: Vec<&[u8; GLYPH_W]> = FONT_5X7
+        .iter()
+        .chain(ACCENTED.iter().chain(&SYMBOLS).map(|(_, glyph)| glyph))
+        .collect();
+    let glyph_count = glyphs.len();
+    let rows = glyph_count.div_ceil(COLUMNS);
+    let width = COLUMNS * CELL_W;
+    let height = rows * CELL_H;
+    let mut atlas = vec![0u8; width * height];
+
+    for (index, glyph) in glyphs.into_iter().enumerate() {
+        let cell_x = (index % COLUMNS) * CELL_W;
+        let cell_y = (index / COLUMNS) * CELL_H;
+        for ty in 0..CELL_H {
+            for tx in 0..CELL_W {
+                let px = tx as f32 + 0.5 - PADDING as f32;
+                let py = ty as f32 + 0.5 - PADDING as f32;
+                let d = signed_distance(glyph, px, py);
+                let value = (0.5 + 0.5 * d / SPREAD).clamp(0.0, 1.0);
+                atlas[(cell_y + ty) * width + cell_x + tx] = (value * 255.0).round() as u8;
+            }
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out = Path::new(&out_dir);
+    fs::write(out.join("font_atlas.bin"), &atlas).expect("write font atlas");
+    fs::write(
+        out.join("font_atlas.rs"),
+        format!(
+            "/// Atlas width in texels.\n\
+             pub const ATLAS_WIDTH: usize = {width};\n\
+             /// Atlas height in texels.\n\
+             pub const ATLAS_HEIGHT: usize = {height};\n\
+             /// Glyph cell size in texels (glyph plus padding).\n\
+             pub const CELL_WIDTH: usize = {CELL_W};\n\
+             pub const CELL_HEIGHT: usize = {CELL_H};\n\
+             /// Empty border inside each cell, in texels.\n\
+             pub const CELL_PADDING: usize = {PADDING};\n\
+             /// Atlas texels per font pixel.\n\
+             pub const TEXELS_PER_PIXEL: usize = {SCALE};\n\
+             /// Glyph cells per atlas row.\n\
+             pub const COLUMNS: usize = {COLUMNS};\n\
+             /// Code point of the first glyph.\n\
+             pub const FIRST_CHAR: u32 = {first};\n\
+             /// Glyphs in the code-point range starting at `FIRST_CHAR`.\n\
+             pub const ASCII_COUNT: usize = {ascii_count};\n\
+             /// Characters of the glyphs after the ASCII range, in atlas order.\n\
+             pub const EXTRA_CHARS: &[char] = &{extra:?};\n\
+             /// Source glyph size in font pixels.\n\
+             pub const GLYPH_WIDTH: usize = {GLYPH_W};\n\
+             pub const GLYPH_HEIGHT: usize = {GLYPH_H};\n\
+             /// Single-channel distance field (128 = edge).\n\
+             pub static ATLAS: &[u8] = include_bytes!(concat!(env!(\"OUT_DIR\"), \"/font_atlas.bin\"));\n",
+            first = FIRST_CHAR,
+            extra = ACCENTED.iter().chain(&SYMBOLS).map(|(c, _)| *c).collect::<Vec<_>>(),
+        ),
+    )
+    .expect("write font atlas metadata");
+}
@@ -0,0 +1,9 @@
+// Frame-constant uniform block (std140), filled once per frame by
+// `FrameUniforms::update`. Keep in sync with `frame_uniforms::pack`.
+layout(std140) uniform Frame {
+    mat4 u_view;
+    mat4 u_projection;
+    vec4 u_light_pos;   // xyz = light position
+    vec4 u_view_pos;    // xyz = eye position
+    vec4 u_params;      // x = render time (s)
+};
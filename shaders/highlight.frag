@@ -0,0 +1,24 @@
+#version 300 es
+precision highp float;
+
+in vec2 v_offset;
+
+uniform vec3 u_color;
+uniform float u_extent;
+
+out vec4 frag_color;
+
+void main() {
+    float r = length(v_offset);
+    if (r > u_extent) discard;
+
+    // Crisp ring at r = 1, antialiased over about a pixel
+    float width = fwidth(r);
+    float ring = 1.0 - smoothstep(width, 2.5 * width, abs(r - 1.0));
+    // Soft glow fading outwards from the ring
+    float glow = r > 1.0 ? 0.45 * (1.0 - smoothstep(1.0, u_extent, r)) : 0.0;
+
+    float alpha = max(ring, glow);
+    if (alpha < 0.01) discard;
+    frag_color = vec4(u_color, alpha);
+}
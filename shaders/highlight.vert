@@ -0,0 +1,24 @@
+#version 300 es
+precision highp float;
+
+// Camera-facing quad around the hovered body, expanded from gl_VertexID.
+#include "frame.glsl"
+uniform vec3 u_center;
+uniform float u_radius;
+uniform float u_extent;
+
+out vec2 v_offset;
+
+const vec2 CORNERS[4] = vec2[4](
+    vec2(-1.0, -1.0),
+    vec2( 1.0, -1.0),
+    vec2(-1.0,  1.0),
+    vec2( 1.0,  1.0)
+);
+
+void main() {
+    // Offset from the body's centre, in units of the ring radius
+    v_offset = CORNERS[gl_VertexID] * u_extent;
+    vec4 center_view = u_view * vec4(u_center, 1.0);
+    gl_Position = u_projection * (center_view + vec4(v_offset * u_radius, 0.0, 0.0));
+}
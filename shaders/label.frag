@@ -0,0 +1,28 @@
+#version 300 es
+precision mediump float;
+
+uniform sampler2D u_atlas;
+uniform vec3 u_color;
+// Opacity of the halo (higher in the high-contrast palette).
+uniform float u_halo;
+
+in vec2 v_uv;
+in float v_alpha;
+out vec4 frag_color;
+
+// Distance-field offset of the dark halo that keeps text readable over bright bodies.
+const float HALO = 0.2;
+
+void main() {
+    float d = texture(u_atlas, v_uv).r;
+    float w = max(fwidth(d), 1e-4);
+
+    float fill = smoothstep(0.5 - w, 0.5 + w, d);
+    float halo = smoothstep(0.5 - HALO - w, 0.5 - HALO + w, d);
+
+    float alpha = max(fill, halo * u_halo) * v_alpha;
+    if (alpha < 0.004) {
+        discard;
+    }
+    frag_color = vec4(u_color * fill, alpha);
+}
@@ -0,0 +1,19 @@
+#version 300 es
+precision highp float;
+
+// Screen-space glyph quads, positioned in device pixels (origin top-left).
+layout(location = 0) in vec2 a_screen;
+layout(location = 1) in vec2 a_uv;
+layout(location = 2) in float a_alpha;
+
+uniform vec2 u_viewport;
+
+out vec2 v_uv;
+out float v_alpha;
+
+void main() {
+    vec2 ndc = a_screen / u_viewport * 2.0 - 1.0;
+    v_uv = a_uv;
+    v_alpha = a_alpha;
+    gl_Position = vec4(ndc.x, -ndc.y, 0.0, 1.0);
+}
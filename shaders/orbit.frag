@@ -2,9 +2,10 @@
 precision highp float;
 
 uniform vec3 u_color;
+uniform float u_alpha;
 
 out vec4 frag_color;
 
 void main() {
-    frag_color = vec4(u_color, 0.2);
+    frag_color = vec4(u_color, u_alpha);
 }
@@ -4,8 +4,7 @@ precision highp float;
 layout(location = 0) in vec3 a_position;
 
 uniform mat4 u_model;
-uniform mat4 u_view;
-uniform mat4 u_projection;
+#include "frame.glsl"
 
 void main() {
     gl_Position = u_projection * u_view * u_model * vec4(a_position, 1.0);
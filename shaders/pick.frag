@@ -0,0 +1,11 @@
+#version 300 es
+precision highp float;
+
+// Flat object ID for GPU picking (see renderer/picking.rs).
+uniform vec4 u_id;
+
+out vec4 frag_color;
+
+void main() {
+    frag_color = u_id;
+}
@@ -1,31 +1,30 @@
 #version 300 es
 precision highp float;
+precision mediump sampler2DArray;
 
 in vec3 v_normal;
 in vec3 v_frag_pos;
 in vec2 v_uv;
+flat in vec3 v_color;
+flat in int v_flags;
+flat in float v_layer;
 
-uniform vec3 u_color;
-uniform vec3 u_light_pos;
-uniform vec3 u_view_pos;
-uniform bool u_is_star;
-uniform bool u_has_texture;
-uniform sampler2D u_texture;
+#include "frame.glsl"
+
+uniform sampler2DArray u_textures;
+
+// Instance flag bits — keep in sync with `render_pass::INSTANCE_FLAG_*`.
+const int FLAG_TEXTURED = 1;
 
 out vec4 frag_color;
 
 void main() {
-    // Base color: texture if available, otherwise uniform color
-    vec3 base_color = u_has_texture ? texture(u_texture, v_uv).rgb : u_color;
-
-    if (u_is_star) {
-        // Sun: self-illuminated with subtle surface detail
-        frag_color = vec4(base_color, 1.0);
-        return;
-    }
+    // Base color: texture layer if loaded, otherwise the instance colour
+    bool has_texture = (v_flags & FLAG_TEXTURED) != 0;
+    vec3 base_color = has_texture ? texture(u_textures, vec3(v_uv, v_layer)).rgb : v_color;
 
     vec3 norm = normalize(v_normal);
-    vec3 light_dir = normalize(u_light_pos - v_frag_pos);
+    vec3 light_dir = normalize(u_light_pos.xyz - v_frag_pos);
 
     // Ambient
     float ambient_strength = 0.08;
@@ -36,7 +35,7 @@ void main() {
     vec3 diffuse = diff * base_color;
 
     // Specular (Blinn-Phong)
-    vec3 view_dir = normalize(u_view_pos - v_frag_pos);
+    vec3 view_dir = normalize(u_view_pos.xyz - v_frag_pos);
     vec3 halfway = normalize(light_dir + view_dir);
     float spec = pow(max(dot(norm, halfway), 0.0), 32.0);
     vec3 specular = vec3(0.15) * spec;
@@ -5,19 +5,28 @@ layout(location = 0) in vec3 a_position;
 layout(location = 1) in vec3 a_normal;
 layout(location = 2) in vec2 a_uv;
 
-uniform mat4 u_model;
-uniform mat4 u_view;
-uniform mat4 u_projection;
-uniform mat4 u_normal_matrix;
+// Per-instance attributes (divisor 1)
+layout(location = 3) in mat4 a_model;        // locations 3–6
+layout(location = 7) in vec4 a_color_flags;  // rgb = colour, a = flag bits
+layout(location = 8) in float a_layer;       // texture array layer
+
+#include "frame.glsl"
 
 out vec3 v_normal;
 out vec3 v_frag_pos;
 out vec2 v_uv;
+flat out vec3 v_color;
+flat out int v_flags;
+flat out float v_layer;
 
 void main() {
-    vec4 world_pos = u_model * vec4(a_position, 1.0);
+    vec4 world_pos = a_model * vec4(a_position, 1.0);
     v_frag_pos = world_pos.xyz;
-    v_normal = mat3(u_normal_matrix) * a_normal;
+    // Uniform scale + translation only, so the model matrix preserves normals.
+    v_normal = mat3(a_model) * a_normal;
     v_uv = a_uv;
+    v_color = a_color_flags.rgb;
+    v_flags = int(a_color_flags.a + 0.5);
+    v_layer = a_layer;
     gl_Position = u_projection * u_view * world_pos;
 }
@@ -0,0 +1,35 @@
+#version 300 es
+precision highp float;
+
+in vec2 v_offset;
+
+uniform vec3 u_color;
+uniform float u_extent;
+
+out vec4 frag_color;
+
+#include "frame.glsl"
+#include "noise.glsl"
+
+void main() {
+    float r = length(v_offset);
+    if (r < 1.0 || r > u_extent) discard;
+
+    // Height above the photosphere, in solar radii
+    float h = r - 1.0;
+    float angle = atan(v_offset.y, v_offset.x);
+    // Sample on the unit circle so there is no seam where atan wraps
+    vec2 dir = vec2(cos(angle), sin(angle));
+
+    // Faint corona glow
+    float corona = exp(-h * 6.0) * 0.35 * (1.0 - smoothstep(u_extent - 0.3, u_extent, r));
+
+    // Prominences: noisy loops rising from the limb, with filament detail
+    float arcs = smoothstep(0.15, 0.45, fbm(vec3(dir * 3.0, u_params.x * 0.05)));
+    float height = 0.12 + 0.25 * arcs;
+    float filament = 1.0 - abs(snoise(vec3(dir * 12.0, h * 6.0 - u_params.x * 0.1)));
+    float prominence = arcs * (1.0 - smoothstep(0.0, height, h)) * pow(filament, 3.0);
+
+    vec3 color = u_color * corona + vec3(1.0, 0.35, 0.15) * prominence;
+    frag_color = vec4(color, clamp(corona + prominence, 0.0, 1.0));
+}
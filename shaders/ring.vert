@@ -4,8 +4,7 @@ precision highp float;
 layout(location = 0) in vec3 a_position;
 
 uniform mat4 u_model;
-uniform mat4 u_view;
-uniform mat4 u_projection;
+#include "frame.glsl"
 
 out vec3 v_pos;
 
@@ -0,0 +1,67 @@
+#version 300 es
+precision highp float;
+precision mediump sampler2DArray;
+
+in vec3 v_normal;
+in vec3 v_frag_pos;
+in vec3 v_local;
+in vec2 v_uv;
+
+uniform vec3 u_color;
+uniform float u_rotation_days;
+uniform vec3 u_diff_rotation;
+uniform bool u_has_texture;
+uniform sampler2DArray u_textures;
+uniform float u_layer;
+
+out vec4 frag_color;
+
+#include "frame.glsl"
+#include "noise.glsl"
+
+// Rotate `p` around the Sun's spin axis (+Y) by `angle` radians.
+vec3 rotate_y(vec3 p, float angle) {
+    float c = cos(angle);
+    float s = sin(angle);
+    return vec3(c * p.x + s * p.z, p.y, -s * p.x + c * p.z);
+}
+
+void main() {
+    vec3 p = normalize(v_local);
+    float lat = asin(clamp(p.y, -1.0, 1.0));
+
+    // Differential rotation: ω(φ) = A + B·sin²φ + C·sin⁴φ  (degrees / day)
+    float s2 = sin(lat) * sin(lat);
+    float omega = u_diff_rotation.x + u_diff_rotation.y * s2 + u_diff_rotation.z * s2 * s2;
+    vec3 rotated = rotate_y(p, -radians(omega * u_rotation_days));
+
+    // Granulation: two noise layers drifting in opposite directions
+    vec3 flow = vec3(0.0, 0.0, u_params.x * 0.04);
+    float g1 = 1.0 - abs(snoise(rotated * 22.0 + flow));
+    float g2 = 1.0 - abs(snoise(rotated * 41.0 - flow * 1.7));
+    float granulation = 0.65 * g1 + 0.35 * g2;
+
+    // Large-scale mottling (supergranulation / faculae)
+    float mottling = fbm(rotated * 4.0 + vec3(u_params.x * 0.01));
+
+    // Sunspots: sparse dark patches confined to the active latitudes
+    float spot_noise = fbm(rotated * 3.0 + vec3(17.0));
+    float band = smoothstep(0.05, 0.12, abs(lat)) * (1.0 - smoothstep(0.55, 0.65, abs(lat)));
+    float penumbra = smoothstep(0.32, 0.40, spot_noise) * band;
+    float umbra = smoothstep(0.42, 0.48, spot_noise) * band;
+
+    vec3 base = u_has_texture ? texture(u_textures, vec3(v_uv, u_layer)).rgb : u_color;
+    vec3 hot = mix(base, vec3(1.0, 0.95, 0.82), 0.35);
+    vec3 color = hot * (0.78 + 0.30 * granulation + 0.10 * mottling);
+
+    color = mix(color, color * 0.45, penumbra);
+    color = mix(color, vec3(0.12, 0.05, 0.02), umbra);
+
+    // Limb darkening (linear law) with a slight reddening towards the edge
+    vec3 view_dir = normalize(u_view_pos.xyz - v_frag_pos);
+    float mu = max(dot(normalize(v_normal), view_dir), 0.0);
+    float limb = 1.0 - 0.6 * (1.0 - mu);
+    color *= limb * mix(vec3(1.0, 0.55, 0.25), vec3(1.0), sqrt(mu));
+
+    frag_color = vec4(color, 1.0);
+}
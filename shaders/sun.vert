@@ -0,0 +1,24 @@
+#version 300 es
+precision highp float;
+
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+layout(location = 2) in vec2 a_uv;
+
+uniform mat4 u_model;
+#include "frame.glsl"
+
+out vec3 v_normal;
+out vec3 v_frag_pos;
+out vec3 v_local;
+out vec2 v_uv;
+
+void main() {
+    vec4 world_pos = u_model * vec4(a_position, 1.0);
+    v_frag_pos = world_pos.xyz;
+    // Uniform scale + translation only, so the model matrix preserves normals.
+    v_normal = mat3(u_model) * a_normal;
+    v_local = a_position;
+    v_uv = a_uv;
+    gl_Position = u_projection * u_view * world_pos;
+}
@@ -4,8 +4,7 @@ precision highp float;
 layout(location = 0) in vec3 a_position;
 layout(location = 1) in float a_alpha;
 
-uniform mat4 u_view;
-uniform mat4 u_projection;
+#include "frame.glsl"
 
 out float v_alpha;
 
@@ -0,0 +1,267 @@
+//! Accessibility: screen-reader announcements, keyboard focus on the canvas,
+//! and the user's motion and contrast preferences.
+//!
+//! The canvas itself is opaque to assistive technology, so each viewer gives
+//! it a label and a keyboard focus stop, and keeps a visually hidden ARIA
+//! live region next to it. Every frame the viewer takes a [`Status`] of what
+//! a listener cares about (selection, camera lock, clock, layers) and
+//! [`announcement`] turns the differences from the previous one into a
+//! sentence for the region. While the canvas has focus, Tab and Shift-Tab
+//! step through the bodies ([`cycle_body`]) and let focus move on past
+//! either end, so the canvas is never a keyboard trap.
+//!
+//! [`Preferences`] follow `prefers-reduced-motion` and `prefers-contrast`
+//! unless the viewer options force them.
+
+use wasm_bindgen::prelude::*;
+
+use crate::hud::speed_label;
+use crate::i18n::I18n;
+use crate::renderer::labels::LabelMode;
+use crate::renderer::render_pass::Layers;
+
+/// Media query for the reduced-motion preference.
+pub const REDUCED_MOTION_QUERY: &str = "(prefers-reduced-motion: reduce)";
+
+/// Media query for the high-contrast preference (including forced colours).
+pub const HIGH_CONTRAST_QUERY: &str = "(prefers-contrast: more), (forced-colors: active)";
+
+/// How the viewer adapts to the user's accessibility settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Preferences {
+    /// Stars hold still and the camera jumps instead of gliding.
+    pub reduced_motion: bool,
+    /// Opaque, saturated orbits and labels on a solid halo.
+    pub high_contrast: bool,
+}
+
+// ── Announcements ──
+
+/// The selected body, as far as announcements are concerned.
+///
+/// Two statuses of the same body are equal whatever their distances, so a
+/// body moving along its orbit does not count as a change.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectedStatus {
+    /// Stable body id.
+    pub id: &'static str,
+    pub is_star: bool,
+    /// Distance from the Sun in AU this frame; only announced on the frame
+    /// the body is selected.
+    pub sun_distance_au: f64,
+}
+
+impl PartialEq for SelectedStatus {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.is_star == other.is_star
+    }
+}
+
+/// What the live region describes, taken once per frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Status {
+    pub selected: Option<SelectedStatus>,
+    pub locked: bool,
+    pub paused: bool,
+    pub days_per_second: f64,
+    pub fly_mode: bool,
+    /// Whether a tour is playing.
+    pub touring: bool,
+    pub layers: Layers,
+    pub labels: LabelMode,
+}
+
+/// Sentence describing what changed from `previous` to `current`, or `None`
+/// when nothing worth announcing did.
+pub fn announcement(previous: &Status, current: &Status, i18n: &I18n) -> Option<String> {
+    let mut sentences = Vec::new();
+    let body_name = |selected: &SelectedStatus| i18n.body_name(selected.id);
+
+    let selection_changed = previous.selected.map(|s| s.id) != current.selected.map(|s| s.id);
+    if selection_changed {
+        sentences.push(match &current.selected {
+            Some(selected) if selected.is_star => {
+                i18n.format("a11y.selected_star", &[("body", body_name(selected))])
+            }
+            Some(selected) => i18n.format(
+                "a11y.selected",
+                &[
+                    ("body", body_name(selected)),
+                    ("distance", &i18n.number(selected.sun_distance_au, 2)),
+                ],
+            ),
+            None => i18n.text("a11y.overview").to_string(),
+        });
+    }
+    if current.locked != previous.locked {
+        match (&current.selected, current.locked) {
+            (Some(selected), true) => {
+                sentences.push(i18n.format("a11y.locked", &[("body", body_name(selected))]))
+            }
+            // Changing the selection drops the lock without a word.
+            (_, false) if !selection_changed => sentences.push(i18n.text("a11y.unlocked").into()),
+            _ => {}
+        }
+    }
+
+    let speed = || speed_label(current.days_per_second, false, i18n);
+    if current.paused != previous.paused {
+        sentences.push(if current.paused {
+            i18n.text("a11y.paused").to_string()
+        } else {
+            i18n.format("a11y.resumed", &[("speed", &speed())])
+        });
+    } else if !current.paused && current.days_per_second != previous.days_per_second {
+        sentences.push(i18n.format("a11y.speed", &[("speed", &speed())]));
+    }
+
+    let toggles = [
+        (
+            previous.fly_mode,
+            current.fly_mode,
+            "a11y.fly_on",
+            "a11y.fly_off",
+        ),
+        (
+            previous.touring,
+            current.touring,
+            "a11y.tour_started",
+            "a11y.tour_finished",
+        ),
+        (
+            previous.layers.orbits,
+            current.layers.orbits,
+            "a11y.orbits_on",
+            "a11y.orbits_off",
+        ),
+        (
+            previous.layers.trails,
+            current.layers.trails,
+            "a11y.trails_on",
+            "a11y.trails_off",
+        ),
+    ];
+    for (before, now, on, off) in toggles {
+        if before != now {
+            sentences.push(i18n.text(if now { on } else { off }).to_string());
+        }
+    }
+    if current.labels != previous.labels {
+        sentences.push(
+            i18n.text(&format!("a11y.labels_{}", current.labels.id()))
+                .to_string(),
+        );
+    }
+
+    (!sentences.is_empty()).then(|| sentences.join(" "))
+}
+
+/// Turns per-frame [`Status`]es into announcements.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    last: Option<Status>,
+}
+
+impl Announcer {
+    /// Announcement for `status`, given the previous call's. The first call
+    /// only records the starting point.
+    pub fn update(&mut self, status: Status, i18n: &I18n) -> Option<String> {
+        let previous = self.last.replace(status)?;
+        if previous == status {
+            return None;
+        }
+        announcement(&previous, &status, i18n)
+    }
+}
+
+// ── Keyboard focus ──
+
+/// Body Tab selects among `count` bodies when `current` is selected
+/// (Shift-Tab with `backwards`). `None` once the cycle steps past either
+/// end, when the selection is cleared and focus leaves the canvas.
+pub fn cycle_body(current: Option<usize>, count: usize, backwards: bool) -> Option<usize> {
+    match (current, backwards) {
+        _ if count == 0 => None,
+        (None, false) => Some(0),
+        (None, true) => Some(count - 1),
+        (Some(i), false) => (i + 1 < count).then_some(i + 1),
+        (Some(i), true) => i.checked_sub(1).map(|i| i.min(count - 1)),
+    }
+}
+
+// ── DOM ──
+
+/// Whether the browser matches the media `query` right now.
+pub fn media_matches(query: &str) -> bool {
+    web_sys::window()
+        .and_then(|w| w.match_media(query).ok().flatten())
+        .is_some_and(|list| list.matches())
+}
+
+/// The browser's accessibility preferences.
+pub fn browser_preferences() -> Preferences {
+    Preferences {
+        reduced_motion: media_matches(REDUCED_MOTION_QUERY),
+        high_contrast: media_matches(HIGH_CONTRAST_QUERY),
+    }
+}
+
+/// The canvas's accessible name and focus stop, and its live region.
+pub struct LiveRegion {
+    canvas: web_sys::HtmlCanvasElement,
+    region: web_sys::Element,
+}
+
+impl LiveRegion {
+    /// Make `canvas` focusable and labelled, and add a polite, visually
+    /// hidden live region right after it.
+    pub fn attach(canvas: &web_sys::HtmlCanvasElement, i18n: &I18n) -> Result<Self, JsValue> {
+        let document = canvas.owner_document().ok_or("No document")?;
+        let region = document.create_element("div")?;
+        region.set_attribute("role", "status")?;
+        region.set_attribute("aria-live", "polite")?;
+        region.set_attribute("aria-atomic", "true")?;
+        region.set_attribute(
+            "style",
+            "position:absolute;width:1px;height:1px;margin:-1px;padding:0;\
+             overflow:hidden;clip:rect(0 0 0 0);white-space:nowrap;border:0",
+        )?;
+        canvas.after_with_node_1(&region)?;
+
+        if !canvas.has_attribute("tabindex") {
+            canvas.set_attribute("tabindex", "0")?;
+        }
+        canvas.set_attribute("role", "application")?;
+        let live = Self {
+            canvas: canvas.clone(),
+            region,
+        };
+        live.set_label(i18n);
+        Ok(live)
+    }
+
+    /// Label the canvas in the current locale.
+    pub fn set_label(&self, i18n: &I18n) {
+        let _ = self
+            .canvas
+            .set_attribute("aria-label", i18n.text("a11y.canvas"));
+    }
+
+    /// Have screen readers read `text`.
+    pub fn announce(&self, text: &str) {
+        // Identical text is not re-read, so alternate a trailing space.
+        let text = match self.region.text_content() {
+            Some(current) if current == text => format!("{text}\u{a0}"),
+            _ => text.to_string(),
+        };
+        self.region.set_text_content(Some(&text));
+    }
+
+    /// Remove the region and the canvas's role and label.
+    pub fn remove(&self) {
+        self.region.remove();
+        for name in ["role", "aria-label"] {
+            let _ = self.canvas.remove_attribute(name);
+        }
+    }
+}
@@ -0,0 +1,629 @@
+//! Public JavaScript API.
+//!
+//! Embedding pages create viewers and drive them through a [`SolaraHandle`].
+//! Each handle owns its own canvas and state, so several viewers can run on
+//! one page (the bundled bootstrap exposes its viewer as `window.solara`):
+//!
+//! ```js
+//! const solara = new SolaraHandle(canvas, { bodies: ['Earth', 'Mars'], view: 'date=1969-07-20' });
+//! solara.setDate('1969-07-20');
+//! solara.on('select', (name) => console.log('selected', name));
+//! solara.flyTo('Earth', { distance: 4, duration: 3 });
+//! ```
+//!
+//! Argument and return types are declared in the TypeScript section below,
+//! so wasm-bindgen emits a fully typed `solara.d.ts` alongside the module.
+//!
+//! Event callbacks never run while the app state is borrowed: events are
+//! queued on [`AppState`] and delivered by [`flush_events`], after each
+//! frame and after each handle call, so a callback may call back into the
+//! handle.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glam::DVec3;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::app::AppState;
+use crate::constants::{AU_TO_DISPLAY, CAMERA_TRANSITION_SECONDS};
+use crate::hud::Hud;
+use crate::i18n::{I18n, Locale};
+use crate::input::actions::ActionTarget;
+use crate::renderer::camera::{CameraPose, Easing};
+use crate::simulation::body::CelestialBody;
+use crate::url_state;
+use crate::viewer::{self, Viewer, ViewerOptions};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Options for `SolaraHandle.flyTo`; anything omitted keeps the current view. */
+export interface FlyToOptions {
+    /** Horizontal orbit angle in radians. */
+    theta?: number;
+    /** Elevation in radians, clamped to just short of the poles. */
+    phi?: number;
+    /** Orbit distance in display units. */
+    distance?: number;
+    /** Flight time in seconds (default 1.2). */
+    duration?: number;
+    easing?: "linear" | "ease_in" | "ease_out" | "ease_in_out";
+}
+
+/** Snapshot of one body, from `SolaraHandle.getBodyState`. */
+export interface BodyState {
+    /** Stable body id, e.g. "Earth". */
+    name: string;
+    /** Name in the viewer's locale, e.g. "Terre". */
+    displayName: string;
+    /** Heliocentric position in AU. */
+    position: [number, number, number];
+    /** Distance from the Sun in AU. */
+    distanceAu: number;
+    radiusKm: number;
+    orbitalPeriodDays: number;
+    selected: boolean;
+    /** Whether the camera is following this body. */
+    locked: boolean;
+}
+
+/** Something that happened since the previous HUD snapshot. */
+export type HudEvent =
+    | { type: "select"; body: string }
+    | { type: "deselect" }
+    | { type: "bookmarkSaved"; name: string }
+    | { type: "tourStarted"; name: string }
+    | { type: "tourFinished" };
+
+/** Catalogue facts about the selected body. */
+export interface SelectedBody {
+    /** Stable body id, e.g. "Earth". */
+    name: string;
+    /** Name in the viewer's locale. */
+    displayName: string;
+    isStar: boolean;
+    radiusKm: number;
+    semiMajorAxisAu: number;
+    orbitalPeriodDays: number;
+    inclinationDeg: number;
+    eccentricity: number;
+    massKg: number;
+    /** Surface gravity in m/s². */
+    gravity: number;
+    escapeVelocityKmS: number;
+    orbitalSpeedKmS: number;
+    /** Light travel time from the Sun, in seconds. */
+    sunLightSeconds: number;
+    dayLengthHours: number;
+    moons: number;
+    /** Whether the camera follows the body. */
+    locked: boolean;
+}
+
+/** Values of the selected body that change as the planets move. */
+export interface LiveBody {
+    /** Distance from Earth in AU; null for Earth itself or without Earth. */
+    earthDistanceAu: number | null;
+    earthLightSeconds: number | null;
+    /** Position around the orbit in radians. */
+    orbitAngle: number;
+}
+
+/** Everything a HUD shows, passed to `HudCallbacks.update`. */
+export interface HudSnapshot {
+    /** Language of the strings in the snapshot, e.g. "fr". */
+    locale: string;
+    /** Simulation date as ISO-8601 UTC. */
+    date: string;
+    /** Simulation date formatted for the locale. */
+    dateLabel: string;
+    /** Simulation date in days since J2000. */
+    days: number;
+    speed: { daysPerSecond: number; paused: boolean; label: string };
+    fps: { current: number; average: number; frameMs: number };
+    gpu: { drawCalls: number; stateChanges: number };
+    flyMode: boolean;
+    selected: SelectedBody | null;
+    /** Null for the Sun or without a selection. */
+    live: LiveBody | null;
+    /** Live distances in AU. */
+    distances: { cameraToSun: number; cameraToSelected: number | null; selectedToSun: number | null };
+    events: HudEvent[];
+}
+
+/** HUD callbacks; each is optional. */
+export interface HudCallbacks {
+    /** Called when the snapshot changes, at most ten times a second for telemetry alone. */
+    update?(snapshot: HudSnapshot): void;
+    /** Rows of `[section, keys, description, actionId]`. */
+    bindings?(rows: [string, string, string, string][]): void;
+    toggle?(): void;
+    toggleHelp?(): void;
+}
+
+/** Options for `new SolaraHandle(canvas, options)`; everything is optional. */
+export interface ViewerOptions {
+    hud?: HudCallbacks;
+    /** Bodies to show (the Sun is always included). */
+    bodies?: string[];
+    /** Initial view in URL-hash syntax, e.g. "date=1969-07-20&body=Earth". */
+    view?: string;
+    /** Listen to document keys, gamepads and `solara-action` / `solara-tour` events. */
+    pageControls?: boolean;
+    /** Mirror the view in `location.hash`. */
+    urlHash?: boolean;
+    /** localStorage key to save the session under. */
+    storageKey?: string;
+    /** Report loading progress to the page's splash screen. */
+    splash?: boolean;
+    /** Directory body textures are fetched from (default "textures/"). */
+    texturePath?: string;
+    /** UI language, e.g. "fr"; the browser's languages decide when absent or unsupported. */
+    locale?: string;
+    /** Still the starfield and make camera moves jump; follows `prefers-reduced-motion` when absent. */
+    reducedMotion?: boolean;
+    /** High-contrast orbits and labels; follows `prefers-contrast` when absent. */
+    highContrast?: boolean;
+    /** Tessellation of the body spheres (default "cubeSphere"). */
+    sphereKind?: "uv" | "cubeSphere";
+}
+
+/** Events delivered to `SolaraHandle.on`. */
+export type SolaraEvent = "select" | "deselect";
+
+/** `select` passes the body name; `deselect` passes nothing. */
+export type SolaraListener = (name?: string) => void;
+
+declare global {
+    interface Window {
+        /** Set by the bundled bootstrap once the app is running. */
+        solara?: SolaraHandle;
+    }
+}
+"#;
+
+/// Options accepted by [`SolaraHandle::fly_to`].
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlyToOptions {
+    pub theta: Option<f32>,
+    pub phi: Option<f32>,
+    pub distance: Option<f32>,
+    pub duration: Option<f32>,
+    pub easing: Option<Easing>,
+}
+
+impl FlyToOptions {
+    /// Reject values JS can pass but a camera pose cannot hold: `NaN`,
+    /// infinities, a non-positive distance or a negative duration.
+    pub fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("theta", self.theta),
+            ("phi", self.phi),
+            ("distance", self.distance),
+            ("duration", self.duration),
+        ];
+        for (name, value) in fields {
+            if value.is_some_and(|v| !v.is_finite()) {
+                return Err(format!("flyTo: {name} must be a finite number"));
+            }
+        }
+        if self.distance.is_some_and(|d| d <= 0.0) {
+            return Err("flyTo: distance must be positive".into());
+        }
+        if self.duration.is_some_and(|d| d < 0.0) {
+            return Err("flyTo: duration must not be negative".into());
+        }
+        Ok(())
+    }
+}
+
+/// Snapshot returned by [`SolaraHandle::get_body_state`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyState {
+    pub name: String,
+    pub display_name: String,
+    /// Heliocentric position in AU.
+    pub position: [f64; 3],
+    pub distance_au: f64,
+    pub radius_km: f64,
+    pub orbital_period_days: f64,
+    pub selected: bool,
+    pub locked: bool,
+}
+
+impl BodyState {
+    /// Describe `body` relative to the Sun at `sun`.
+    pub fn new(
+        body: &CelestialBody,
+        sun: DVec3,
+        selected: bool,
+        locked: bool,
+        i18n: &I18n,
+    ) -> Self {
+        let au = (body.position - sun) / AU_TO_DISPLAY as f64;
+        Self {
+            name: body.id.to_string(),
+            display_name: i18n.body_name(body.id).to_string(),
+            position: au.to_array(),
+            distance_au: au.length(),
+            radius_km: body.real_radius_km,
+            orbital_period_days: body.orbital_period_days,
+            selected,
+            locked: selected && locked,
+        }
+    }
+}
+
+/// Something a page can subscribe to with [`SolaraHandle::on`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Select,
+    Deselect,
+}
+
+impl EventKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "select" => Some(EventKind::Select),
+            "deselect" => Some(EventKind::Deselect),
+            _ => None,
+        }
+    }
+}
+
+/// An event waiting to be delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiEvent {
+    /// A body was selected (its name).
+    Select(String),
+    Deselect,
+}
+
+impl ApiEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            ApiEvent::Select(_) => EventKind::Select,
+            ApiEvent::Deselect => EventKind::Deselect,
+        }
+    }
+}
+
+/// Page callbacks registered with [`SolaraHandle::on`].
+#[derive(Default)]
+pub struct Listeners {
+    entries: Vec<(EventKind, js_sys::Function)>,
+}
+
+impl Listeners {
+    fn add(&mut self, kind: EventKind, callback: js_sys::Function) {
+        self.entries.push((kind, callback));
+    }
+
+    fn remove(&mut self, kind: EventKind, callback: &js_sys::Function) {
+        self.entries.retain(|(k, f)| !(*k == kind && f == callback));
+    }
+
+    /// Callbacks for `kind`, in registration order.
+    fn for_kind(&self, kind: EventKind) -> Vec<js_sys::Function> {
+        self.entries
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, f)| f.clone())
+            .collect()
+    }
+}
+
+thread_local! {
+    /// The viewer [`get_handle`] returns.
+    static APP: RefCell<Option<Rc<Viewer>>> = const { RefCell::new(None) };
+}
+
+/// Make `viewer` reachable from [`get_handle`], unless a viewer already is.
+pub fn register(viewer: &Rc<Viewer>) {
+    APP.with(|app| {
+        app.borrow_mut().get_or_insert_with(|| Rc::clone(viewer));
+    });
+}
+
+/// Forget `viewer` if it is the one [`get_handle`] returns.
+pub fn unregister(viewer: &Viewer) {
+    APP.with(|app| {
+        let mut app = app.borrow_mut();
+        if app.as_ref().is_some_and(|v| std::ptr::eq(Rc::as_ptr(v), viewer)) {
+            *app = None;
+        }
+    });
+}
+
+/// Deliver queued events to page callbacks.
+///
+/// Must be called with `state` unborrowed; callbacks may use the handle.
+pub fn flush_events(state: &Rc<RefCell<AppState>>) {
+    loop {
+        let (events, callbacks) = {
+            let mut s = state.borrow_mut();
+            if s.pending_events.is_empty() {
+                return;
+            }
+            let events = std::mem::take(&mut s.pending_events);
+            let callbacks: Vec<_> = events
+                .iter()
+                .map(|e| s.listeners.for_kind(e.kind()))
+                .collect();
+            (events, callbacks)
+        };
+        for (event, callbacks) in events.iter().zip(callbacks) {
+            let detail = match event {
+                ApiEvent::Select(name) => JsValue::from_str(name),
+                ApiEvent::Deselect => JsValue::UNDEFINED,
+            };
+            for callback in callbacks {
+                if let Err(err) = callback.call1(&JsValue::NULL, &detail) {
+                    log::warn!("⚠️ Event listener threw: {err:?}");
+                }
+            }
+        }
+    }
+}
+
+/// Handle to the running app, for scripting it from JavaScript.
+#[wasm_bindgen]
+pub struct SolaraHandle {
+    viewer: Rc<Viewer>,
+}
+
+/// The first viewer created on the page. Throws if there is none yet, or
+/// once it has been destroyed.
+#[wasm_bindgen(js_name = getHandle)]
+pub fn get_handle() -> Result<SolaraHandle, JsValue> {
+    APP.with(|app| app.borrow().clone())
+        .map(|viewer| SolaraHandle { viewer })
+        .ok_or_else(|| JsValue::from_str("SOLARA is not running yet"))
+}
+
+impl SolaraHandle {
+    /// Run `f` on the app state, then deliver any events it queued.
+    fn with<R>(&self, f: impl FnOnce(&mut AppState) -> R) -> R {
+        let result = f(&mut self.viewer.state.borrow_mut());
+        flush_events(&self.viewer.state);
+        result
+    }
+}
+
+/// Index of the body called `name` (case-insensitive).
+fn body_index(state: &AppState, name: &str) -> Result<usize, JsValue> {
+    state
+        .simulation
+        .bodies
+        .iter()
+        .position(|b| b.id.eq_ignore_ascii_case(name))
+        .ok_or_else(|| JsValue::from_str(&format!("No body named {name}")))
+}
+
+#[wasm_bindgen]
+impl SolaraHandle {
+    /// Create a viewer on `canvas` and start rendering.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        canvas: web_sys::HtmlCanvasElement,
+        #[wasm_bindgen(unchecked_param_type = "ViewerOptions | undefined")] options: JsValue,
+    ) -> Result<SolaraHandle, JsValue> {
+        let (options, hud) = if options.is_undefined() || options.is_null() {
+            (ViewerOptions::default(), None)
+        } else {
+            // The HUD callbacks are functions, which serde cannot read.
+            let data = js_sys::Object::assign(&js_sys::Object::new(), options.unchecked_ref());
+            js_sys::Reflect::delete_property(&data, &"hud".into())?;
+            let hud = js_sys::Reflect::get(&options, &"hud".into())?
+                .dyn_into::<js_sys::Object>()
+                .ok();
+            (serde_wasm_bindgen::from_value(data.into())?, hud)
+        };
+        let viewer = viewer::create(canvas, options, Hud::new(hud))?;
+        Ok(SolaraHandle { viewer })
+    }
+
+    /// Stop rendering and release everything the viewer holds: its DOM
+    /// listeners, animation frame and GL resources. The handle must not be
+    /// used afterwards; calling `destroy` again does nothing.
+    pub fn destroy(&self) {
+        self.viewer.destroy();
+    }
+
+    /// Jump the clock to `date`: an ISO-8601 string (`2030-05-01`,
+    /// `2030-05-01T12:00Z`) or a `Date`.
+    #[wasm_bindgen(js_name = setDate)]
+    pub fn set_date(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "string | Date")] date: JsValue,
+    ) -> Result<(), JsValue> {
+        let days = match date.dyn_ref::<js_sys::Date>() {
+            Some(date) => url_state::unix_millis_to_days(date.get_time()),
+            None => date
+                .as_string()
+                .and_then(|text| url_state::iso_to_days(&text))
+                .ok_or_else(|| JsValue::from_str("Expected an ISO date or a Date"))?,
+        };
+        if !days.is_finite() {
+            return Err(JsValue::from_str("Invalid date"));
+        }
+        self.with(|s| {
+            s.jump_to_date(days);
+            s.time_changed();
+        });
+        Ok(())
+    }
+
+    /// Current simulation date as an ISO-8601 UTC string.
+    #[wasm_bindgen(js_name = getDate)]
+    pub fn get_date(&self) -> String {
+        url_state::days_to_iso(self.viewer.state.borrow().simulation.time.current_days)
+    }
+
+    /// Set the speed in simulated days per real second (clamped to 0.1–100).
+    /// Throws for `NaN` and infinities.
+    #[wasm_bindgen(js_name = setSpeed)]
+    pub fn set_speed(&self, days_per_second: f64) -> Result<(), JsValue> {
+        if !days_per_second.is_finite() {
+            return Err(JsValue::from_str("Speed must be a finite number"));
+        }
+        self.with(|s| {
+            s.simulation.time.set_speed(days_per_second);
+            s.time_changed();
+        });
+        Ok(())
+    }
+
+    /// Speed in simulated days per real second.
+    #[wasm_bindgen(js_name = getSpeed)]
+    pub fn get_speed(&self) -> f64 {
+        self.viewer.state.borrow().simulation.time.days_per_second
+    }
+
+    /// Stop the clock.
+    pub fn pause(&self) {
+        self.with(|s| {
+            s.simulation.time.paused = true;
+            s.time_changed();
+        });
+    }
+
+    /// Restart the clock.
+    pub fn resume(&self) {
+        self.with(|s| {
+            s.simulation.time.paused = false;
+            s.time_changed();
+        });
+    }
+
+    #[wasm_bindgen(js_name = isPaused)]
+    pub fn is_paused(&self) -> bool {
+        self.viewer.state.borrow().simulation.time.paused
+    }
+
+    /// Names of every body, Sun first.
+    #[wasm_bindgen(js_name = bodyNames)]
+    pub fn body_names(&self) -> Vec<String> {
+        let s = self.viewer.state.borrow();
+        s.simulation
+            .bodies
+            .iter()
+            .map(|b| b.id.to_string())
+            .collect()
+    }
+
+    /// Select a body by name (case-insensitive) and zoom to it, as a click would.
+    #[wasm_bindgen(js_name = selectBody)]
+    pub fn select_body(&self, name: &str) -> Result<(), JsValue> {
+        self.with(|s| {
+            let idx = body_index(s, name)?;
+            crate::input::select_planet(s, idx);
+            Ok(())
+        })
+    }
+
+    /// Clear the selection and return to the overview.
+    pub fn deselect(&self) {
+        self.with(|s| s.deselect());
+    }
+
+    /// Fly to a body and follow it. Options override the orbit angles,
+    /// distance, flight time and easing; non-finite numbers throw.
+    #[wasm_bindgen(js_name = flyTo)]
+    pub fn fly_to(
+        &self,
+        name: &str,
+        #[wasm_bindgen(unchecked_param_type = "FlyToOptions | undefined")] options: JsValue,
+    ) -> Result<(), JsValue> {
+        let options: FlyToOptions = if options.is_undefined() || options.is_null() {
+            FlyToOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options)?
+        };
+        options.validate().map_err(|e| JsValue::from_str(&e))?;
+        self.with(|s| {
+            let idx = body_index(s, name)?;
+            s.stop_tour();
+            crate::input::focus_body(s, idx);
+            let camera = &s.renderer.camera;
+            let base = camera.transition.as_ref().map_or(camera.pose(), |t| t.to);
+            let pose = CameraPose {
+                target: s.simulation.bodies[idx].position,
+                theta: options.theta.unwrap_or(base.theta),
+                phi: options.phi.unwrap_or(base.phi),
+                distance: options.distance.unwrap_or(base.distance),
+            };
+            s.renderer.camera.start_transition(
+                pose,
+                options.duration.unwrap_or(CAMERA_TRANSITION_SECONDS),
+                options.easing.unwrap_or_default(),
+            );
+            Ok(())
+        })
+    }
+
+    /// Position and facts for a body. Throws for unknown names.
+    #[wasm_bindgen(js_name = getBodyState, unchecked_return_type = "BodyState")]
+    pub fn get_body_state(&self, name: &str) -> Result<JsValue, JsValue> {
+        let s = self.viewer.state.borrow();
+        let idx = body_index(&s, name)?;
+        let state = BodyState::new(
+            &s.simulation.bodies[idx],
+            s.sun_position(),
+            s.selected_planet == Some(idx),
+            s.camera_locked,
+            &s.i18n,
+        );
+        Ok(serde_wasm_bindgen::to_value(&state)?)
+    }
+
+    /// Language of the viewer's messages, e.g. `fr`.
+    pub fn locale(&self) -> String {
+        self.viewer.state.borrow().i18n.locale().id().into()
+    }
+
+    /// Switch the viewer's language. Throws for unsupported tags.
+    #[wasm_bindgen(js_name = setLocale)]
+    pub fn set_locale(&self, tag: &str) -> Result<(), JsValue> {
+        let locale = Locale::parse(tag)
+            .ok_or_else(|| JsValue::from_str(&format!("Unsupported locale {tag}")))?;
+        self.with(|s| s.set_locale(locale));
+        Ok(())
+    }
+
+    /// Every message of the viewer's locale, by key (English where the
+    /// translation has none), for the page's own text.
+    #[wasm_bindgen(unchecked_return_type = "Record<string, string>")]
+    pub fn messages(&self) -> Result<JsValue, JsValue> {
+        let s = self.viewer.state.borrow();
+        let messages: std::collections::BTreeMap<&str, &str> = s.i18n.messages().iter().collect();
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        Ok(messages.serialize(&serializer)?)
+    }
+
+    /// Call `callback` whenever `event` happens.
+    pub fn on(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SolaraEvent")] event: &str,
+        #[wasm_bindgen(unchecked_param_type = "SolaraListener")] callback: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        let kind = EventKind::parse(event)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown event {event}")))?;
+        self.viewer.state.borrow_mut().listeners.add(kind, callback);
+        Ok(())
+    }
+
+    /// Remove a callback added with [`on`](Self::on).
+    pub fn off(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SolaraEvent")] event: &str,
+        #[wasm_bindgen(unchecked_param_type = "SolaraListener")] callback: js_sys::Function,
+    ) {
+        if let Some(kind) = EventKind::parse(event) {
+            self.viewer.state.borrow_mut().listeners.remove(kind, &callback);
+        }
+    }
+}
@@ -4,22 +4,67 @@
 //! Wrapped in `Rc<RefCell<…>>` so event closures and the render loop
 //! can all mutate it safely.
 
+use glam::{DVec3, Vec2, Vec3};
+
+use crate::accessibility::{Announcer, LiveRegion, SelectedStatus, Status};
+use crate::api::{ApiEvent, Listeners};
+use crate::constants::{
+    AU_TO_DISPLAY, BOOKMARK_TRAVEL_SECONDS, CAMERA_DISTANCE, CAMERA_PHI, CAMERA_THETA,
+    SESSION_SAVE_INTERVAL_SECONDS, URL_STATE_INTERVAL_SECONDS,
+};
+use crate::hud::{
+    Distances, FpsStats, Hud, HudEvent, HudSnapshot, LiveBody, SelectedBody, SpeedState,
+};
+use crate::i18n::{I18n, Locale};
+use crate::input::actions::KeyBindings;
+use crate::input::gamepad;
+use crate::input::gestures::GestureRecognizer;
+use crate::input::hover::Hover;
+use crate::input::inertia::CameraMomentum;
+use crate::persistence::{self, Session};
 use crate::renderer::Renderer;
+use crate::renderer::camera::{CameraPose, Easing};
+use crate::renderer::fly_camera::{FlyCamera, FlyInput, cruise_speed};
+use crate::renderer::labels::LabelMode;
+use crate::renderer::render_pass::Layers;
 use crate::simulation::Simulation;
+use crate::simulation::time::SimulationTime;
+use crate::tour::{Bookmark, Tour, TourPlayer, TourStep};
+use crate::url_state::{self, ViewState};
+use crate::viewer::ViewerOptions;
 
 /// Everything the app needs at runtime, bundled together.
 pub struct AppState {
     pub renderer: Renderer,
     pub simulation: Simulation,
+    /// What this viewer may touch outside its canvas.
+    pub options: ViewerOptions,
+    /// This viewer's HUD callbacks.
+    pub hud: Hud,
+    /// Messages and formatting for the viewer's locale.
+    pub i18n: I18n,
+
+    // ── Accessibility ──
+    /// The canvas's label and live region; absent if they could not be added.
+    pub live_region: Option<LiveRegion>,
+    /// Turns state changes into live-region announcements.
+    pub announcer: Announcer,
 
     // ── Input tracking ──
     pub mouse_down: bool,
     pub last_mouse_x: f32,
     pub last_mouse_y: f32,
     pub mouse_drag_distance: f32,
-    pub last_touch_x: f32,
-    pub last_touch_y: f32,
-    pub touch_distance: Option<f32>,
+    /// The held mouse button pans (right or middle) rather than rotates.
+    pub mouse_panning: bool,
+    /// Turns touches into taps, drags, pinches and twists.
+    pub gestures: GestureRecognizer,
+    /// Rotation and zoom the camera keeps after a fling or wheel notch.
+    pub momentum: CameraMomentum,
+    /// Body under the mouse pointer, picked a few times a second.
+    pub hover: Hover,
+    /// Camera-relative hit spheres for picking, refilled in place.
+    pub hit_spheres: Vec<(Vec3, f32)>,
 
     // ── Planet selection ──
     /// Index into `simulation.bodies` of the currently selected body, if any.
@@ -27,26 +72,502 @@ pub struct AppState {
     /// When `true`, the camera target is updated every frame to follow the
     /// selected planet as it orbits.
     pub camera_locked: bool,
+    /// How far the user panned the orbit target away from the point the
+    /// camera follows (the Sun or the locked body).
+    pub pan_offset: DVec3,
+
+    // ── Keyboard ──
+    /// Key → action table (defaults plus any stored user overrides).
+    pub bindings: KeyBindings,
+
+    // ── Fly mode ──
+    /// Free-fly camera, while fly mode is active. It drives
+    /// `renderer.camera` every frame.
+    pub fly: Option<FlyCamera>,
+    /// Gamepad fly-toggle button state last frame, for edge detection.
+    pub gamepad_toggle_down: bool,
+
+    // ── Bookmarks & tours ──
+    /// Views saved this session, in the order they were saved.
+    pub bookmarks: Vec<Bookmark>,
+    /// Index of the bookmark the next "next bookmark" action flies to.
+    pub bookmark_cursor: usize,
+    /// Tour being played, if any.
+    pub tour: Option<TourPlayer>,
+
+    // ── Deep links ──
+    /// Hash last written to the URL.
+    pub url_hash: String,
+    /// Seconds since the URL hash was last refreshed.
+    pub url_timer: f32,
+
+    // ── Session ──
+    /// Session JSON last written to localStorage.
+    pub session_json: String,
+    /// Seconds since the session was last saved.
+    pub session_timer: f32,
+
+    // ── JavaScript API ──
+    /// Page callbacks registered through `SolaraHandle.on`.
+    pub listeners: Listeners,
+    /// Events waiting for `api::flush_events`.
+    pub pending_events: Vec<ApiEvent>,
 }
 
 impl AppState {
     /// Build a new `AppState` from an already-initialized renderer and simulation.
-    pub fn new(renderer: Renderer, simulation: Simulation) -> Self {
+    pub fn new(
+        renderer: Renderer,
+        simulation: Simulation,
+        options: ViewerOptions,
+        hud: Hud,
+        i18n: I18n,
+    ) -> Self {
         Self {
             renderer,
             simulation,
+            options,
+            hud,
+            i18n,
+            live_region: None,
+            announcer: Announcer::default(),
             mouse_down: false,
             last_mouse_x: 0.0,
             last_mouse_y: 0.0,
             mouse_drag_distance: 0.0,
-            last_touch_x: 0.0,
-            last_touch_y: 0.0,
-            touch_distance: None,
+            mouse_panning: false,
+            gestures: GestureRecognizer::default(),
+            momentum: CameraMomentum::default(),
+            hover: Hover::default(),
+            hit_spheres: Vec::new(),
             selected_planet: None,
             camera_locked: false,
+            pan_offset: DVec3::ZERO,
+            bindings: KeyBindings::default(),
+            fly: None,
+            gamepad_toggle_down: false,
+            bookmarks: Vec::new(),
+            bookmark_cursor: 0,
+            tour: None,
+            url_hash: String::new(),
+            url_timer: 0.0,
+            session_json: String::new(),
+            session_timer: 0.0,
+            listeners: Listeners::default(),
+            pending_events: Vec::new(),
         }
     }
 
+    /// Switch the UI language: help rows are resent and the HUD refreshed.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.i18n = I18n::new(locale);
+        self.hud.set_bindings(&self.bindings.help_rows(&self.i18n));
+        self.hud.invalidate();
+        if let Some(live_region) = &self.live_region {
+            live_region.set_label(&self.i18n);
+        }
+    }
+
+    /// What the live region describes this frame.
+    pub fn status(&self) -> Status {
+        let time = &self.simulation.time;
+        let sun = self.sun_position();
+        Status {
+            selected: self
+                .selected_planet
+                .and_then(|idx| self.simulation.bodies.get(idx))
+                .map(|b| SelectedStatus {
+                    id: b.id,
+                    is_star: b.is_star,
+                    sun_distance_au: b.position.distance(sun) / AU_TO_DISPLAY as f64,
+                }),
+            locked: self.camera_locked,
+            paused: time.paused,
+            days_per_second: time.days_per_second,
+            fly_mode: self.fly.is_some(),
+            touring: self.tour.is_some(),
+            layers: self.renderer.layers,
+            labels: self.renderer.label_mode,
+        }
+    }
+
+    /// Announce what changed since the last frame in the live region.
+    fn update_announcements(&mut self) {
+        let Some(live_region) = &self.live_region else {
+            return;
+        };
+        if let Some(text) = self.announcer.update(self.status(), &self.i18n) {
+            live_region.announce(&text);
+        }
+    }
+
+    /// Snapshot of the shareable view state.
+    pub fn view_state(&self) -> ViewState {
+        let time = &self.simulation.time;
+        let camera = &self.renderer.camera;
+        let body = self
+            .selected_planet
+            .and_then(|idx| self.simulation.bodies.get(idx))
+            .map(|b| b.id.to_string());
+        ViewState {
+            days: Some(time.current_days),
+            speed: Some(time.days_per_second),
+            paused: Some(time.paused),
+            locked: body.as_ref().map(|_| self.camera_locked),
+            body,
+            theta: Some(camera.theta),
+            phi: Some(camera.phi),
+            distance: Some(camera.distance),
+            layers: Some(self.renderer.layers),
+            labels: Some(self.renderer.label_mode),
+        }
+    }
+
+    /// Apply a (possibly partial) view state, e.g. from a shared link.
+    ///
+    /// With `animate` the camera flies to the new view; otherwise it jumps.
+    pub fn apply_view_state(&mut self, view: &ViewState, animate: bool) {
+        self.set_fly_mode(false);
+        self.stop_tour();
+        self.pan_offset = DVec3::ZERO;
+
+        let time = &mut self.simulation.time;
+        if let Some(days) = view.days {
+            time.current_days = days;
+        }
+        if let Some(speed) = view.speed {
+            time.set_speed(speed);
+        }
+        if let Some(paused) = view.paused {
+            time.paused = paused;
+        }
+        self.simulation.update(0.0);
+        if let Some(layers) = view.layers {
+            self.renderer.layers = layers;
+        }
+        if let Some(labels) = view.labels {
+            self.renderer.label_mode = labels;
+        }
+
+        if let Some(name) = &view.body {
+            match self
+                .simulation
+                .bodies
+                .iter()
+                .position(|b| b.id.eq_ignore_ascii_case(name))
+            {
+                Some(idx) => {
+                    crate::input::select_planet(self, idx);
+                    if view.locked.is_some_and(|locked| locked != self.camera_locked) {
+                        crate::input::toggle_camera_lock(self);
+                    }
+                }
+                None => log::warn!("⚠️ URL state: no body named {name}"),
+            }
+        }
+
+        // Start from wherever a selection was heading, then override with
+        // the link's angles and distance.
+        let camera = &self.renderer.camera;
+        let base = camera.transition.as_ref().map_or(camera.pose(), |t| t.to);
+        let target = match self.selected_planet {
+            Some(idx) if self.camera_locked => self.simulation.bodies[idx].position,
+            _ => self.sun_position(),
+        };
+        let pose = CameraPose {
+            target,
+            theta: view.theta.unwrap_or(base.theta),
+            phi: view.phi.unwrap_or(base.phi),
+            distance: view.distance.unwrap_or(base.distance),
+        };
+        let camera = &mut self.renderer.camera;
+        if animate {
+            camera.animate_to(pose);
+        } else {
+            camera.transition = None;
+            camera.lerp_target = None;
+            camera.set_pose(pose);
+        }
+        self.url_hash = self.view_state().to_hash();
+    }
+
+    /// Refresh the URL hash with the current view, at most once per
+    /// [`URL_STATE_INTERVAL_SECONDS`].
+    fn update_url(&mut self, dt: f32) {
+        if !self.options.url_hash {
+            return;
+        }
+        self.url_timer += dt;
+        if self.url_timer < URL_STATE_INTERVAL_SECONDS {
+            return;
+        }
+        self.url_timer = 0.0;
+        let hash = self.view_state().to_hash();
+        if hash != self.url_hash {
+            url_state::write_hash(&hash);
+            self.url_hash = hash;
+        }
+    }
+
+    /// Snapshot of everything saved across reloads.
+    pub fn session(&self) -> Session {
+        Session::capture(&self.view_state(), &self.bookmarks)
+    }
+
+    /// Restore a saved session, jumping straight to its view.
+    pub fn restore_session(&mut self, session: &Session) {
+        self.apply_view_state(&session.view(), false);
+        self.bookmarks = session.bookmarks.clone();
+        self.bookmark_cursor = 0;
+        self.session_json = session.to_json();
+    }
+
+    /// Write the session to localStorage if it changed since the last save.
+    pub fn save_session(&mut self) {
+        self.session_timer = 0.0;
+        let Some(key) = &self.options.storage_key else {
+            return;
+        };
+        let json = self.session().to_json();
+        if json != self.session_json {
+            persistence::save(key, &json);
+            self.session_json = json;
+        }
+    }
+
+    /// Save the session at most once per [`SESSION_SAVE_INTERVAL_SECONDS`].
+    fn update_session(&mut self, dt: f32) {
+        self.session_timer += dt;
+        if self.session_timer >= SESSION_SAVE_INTERVAL_SECONDS {
+            self.save_session();
+        }
+    }
+
+    /// Return the clock, camera, selection and display toggles to their
+    /// start-up defaults, drop the bookmarks and forget the saved session.
+    pub fn reset_to_defaults(&mut self) {
+        crate::input::deselect_all(self);
+        self.simulation.time = SimulationTime::default();
+        self.simulation.update(0.0);
+        self.renderer.layers = Layers::default();
+        self.renderer.label_mode = LabelMode::default();
+        self.bookmarks.clear();
+        self.bookmark_cursor = 0;
+        let pose = CameraPose {
+            target: self.sun_position(),
+            theta: CAMERA_THETA,
+            phi: CAMERA_PHI,
+            distance: CAMERA_DISTANCE,
+        };
+        self.renderer.camera.animate_to(pose);
+        if let Some(key) = &self.options.storage_key {
+            persistence::clear(key);
+        }
+        self.session_json.clear();
+        log::info!("↩️ Reset to defaults");
+    }
+
+    /// Current position of the Sun (the anchor that follows galactic drift).
+    pub fn sun_position(&self) -> DVec3 {
+        self.simulation
+            .bodies
+            .iter()
+            .find(|b| b.is_star)
+            .map_or(DVec3::ZERO, |b| b.position)
+    }
+
+    /// Enter or leave fly mode.
+    ///
+    /// Entering starts from the current orbital view. Leaving hands the
+    /// camera back in the same pose; the orbit target then glides back to
+    /// the Sun as usual.
+    pub fn set_fly_mode(&mut self, on: bool) {
+        if on == self.fly.is_some() {
+            return;
+        }
+        if on {
+            self.stop_tour();
+            self.camera_locked = false;
+            self.pan_offset = DVec3::ZERO;
+            self.fly = Some(FlyCamera::from_orbit(&self.renderer.camera, self.sun_position()));
+        } else {
+            self.fly = None;
+        }
+    }
+
+    /// Start playing `tour` from its first stop.
+    pub fn play_tour(&mut self, tour: Tour) {
+        self.set_fly_mode(false);
+        log::info!("🎬 Playing tour \"{}\" ({} stops)", tour.name, tour.stops.len());
+        self.hud.push_event(HudEvent::TourStarted {
+            name: tour.name.clone(),
+        });
+        self.tour = Some(TourPlayer::new(tour));
+    }
+
+    /// Stop the current tour, leaving the camera where it is.
+    pub fn stop_tour(&mut self) {
+        if self.tour.take().is_some() {
+            self.renderer.camera.transition = None;
+        }
+    }
+
+    /// Fly to `bookmark` over `travel` seconds and keep following its body.
+    ///
+    /// If the bookmark records a date the clock jumps to it first.
+    pub fn go_to_bookmark(&mut self, bookmark: &Bookmark, travel: f32, easing: Easing) {
+        let Some(idx) = self
+            .simulation
+            .bodies
+            .iter()
+            .position(|b| b.id.eq_ignore_ascii_case(&bookmark.body))
+        else {
+            log::warn!("⚠️ Bookmark \"{}\": no body named {}", bookmark.name, bookmark.body);
+            return;
+        };
+
+        if let Some(days) = bookmark.time {
+            self.jump_to_date(days);
+        }
+        if let Some(speed) = bookmark.speed {
+            self.simulation.time.set_speed(speed);
+        }
+
+        crate::input::focus_body(self, idx);
+        let pose = bookmark.pose(self.simulation.bodies[idx].position);
+        self.renderer.camera.start_transition(pose, travel, easing);
+    }
+
+    /// Set the clock to `days` since J2000. The camera is shifted along with
+    /// the galactic drift so the jump itself does not move the view.
+    pub fn jump_to_date(&mut self, days: f64) {
+        let jump = days - self.simulation.time.current_days;
+        self.simulation.time.current_days = days;
+        self.simulation.update(0.0);
+        let shift = self.simulation.galactic_velocity * jump;
+        self.renderer.camera.shift(shift);
+    }
+
+    /// Queue an event for page callbacks (see [`crate::api`]).
+    /// The HUD hears about it too.
+    pub fn emit(&mut self, event: ApiEvent) {
+        self.hud.push_event(match &event {
+            ApiEvent::Select(name) => HudEvent::Select { body: name.clone() },
+            ApiEvent::Deselect => HudEvent::Deselect,
+        });
+        self.pending_events.push(event);
+    }
+
+    /// Save the current view as a bookmark around the selected body (or the
+    /// Sun), and log it as JSON for pasting into a tour file.
+    pub fn save_bookmark(&mut self) {
+        let body = self
+            .selected_planet
+            .and_then(|idx| self.simulation.bodies.get(idx))
+            .or_else(|| self.simulation.bodies.iter().find(|b| b.is_star))
+            .map_or("Sun", |b| b.id);
+        let name = format!("{} {}", body, self.bookmarks.len() + 1);
+        let bookmark = Bookmark::capture(&name, body, &self.renderer.camera, &self.simulation.time);
+        if let Ok(json) = serde_json::to_string(&bookmark) {
+            log::info!("🔖 {json}");
+        }
+        self.hud.push_event(HudEvent::BookmarkSaved { name });
+        self.bookmarks.push(bookmark);
+    }
+
+    /// Fly to the next saved bookmark, wrapping around.
+    pub fn next_bookmark(&mut self) {
+        if self.bookmarks.is_empty() {
+            return;
+        }
+        self.stop_tour();
+        let index = self.bookmark_cursor % self.bookmarks.len();
+        self.bookmark_cursor = index + 1;
+        let bookmark = self.bookmarks[index].clone();
+        self.go_to_bookmark(&bookmark, BOOKMARK_TRAVEL_SECONDS, Easing::EaseInOut);
+    }
+
+    /// Advance the tour clock and begin the next stop when it is due.
+    fn update_tour(&mut self, dt: f32) {
+        let Some(player) = self.tour.as_mut() else {
+            return;
+        };
+        match player.advance(dt) {
+            TourStep::Continue => {}
+            TourStep::Begin(index) => {
+                if let Some((stop, bookmark)) = player.stop(index) {
+                    let (travel, easing, bookmark) = (stop.travel, stop.easing, bookmark.clone());
+                    self.go_to_bookmark(&bookmark, travel, easing);
+                }
+            }
+            TourStep::Finished => {
+                log::info!("🎬 Tour finished");
+                self.tour = None;
+                self.hud.push_event(HudEvent::TourFinished);
+            }
+        }
+    }
+
+    /// Drag deltas from mouse or touch: mouse-look in fly mode, orbit otherwise.
+    ///
+    /// Dragging takes the camera back from a running tour.
+    pub fn drag_view(&mut self, dx: f32, dy: f32) {
+        self.stop_tour();
+        match self.fly.as_mut() {
+            Some(fly) => fly.look(dx, dy),
+            None => self.renderer.camera.rotate(dx, dy),
+        }
+    }
+
+    /// Pan the orbit target across the view by a drag of (`dx`, `dy`)
+    /// pixels; ignored in fly mode. The offset stays relative to what the
+    /// camera follows, so the view keeps drifting with the Sun.
+    pub fn pan_view(&mut self, dx: f32, dy: f32) {
+        if self.fly.is_some() {
+            return;
+        }
+        self.stop_tour();
+        self.shift_target(dx, dy);
+    }
+
+    fn shift_target(&mut self, dx: f32, dy: f32) {
+        let offset = self.renderer.camera.pan_offset(dx, dy);
+        self.pan_offset += offset;
+        self.renderer.camera.shift(offset);
+    }
+
+    /// Let the orbit camera coast on the momentum of released drags and
+    /// wheel zooms. Camera animations, fly mode and reduced motion cancel it.
+    fn update_momentum(&mut self, dt: f32) {
+        let rotate = self.momentum.rotate.update(dt);
+        let zoom = self.momentum.zoom.update(dt);
+        let pan = self.momentum.pan.update(dt);
+        let camera = &mut self.renderer.camera;
+        if self.fly.is_some() || camera.transition.is_some() || camera.reduced_motion {
+            self.momentum.stop();
+            return;
+        }
+        if rotate != Vec2::ZERO {
+            camera.rotate(rotate.x, rotate.y);
+        }
+        if zoom != 0.0 {
+            camera.zoom(zoom);
+        }
+        if pan != Vec2::ZERO {
+            self.shift_target(pan.x, pan.y);
+        }
+    }
+
+    /// Turn the view around its target by `radians` (two-finger twist);
+    /// ignored in fly mode.
+    pub fn twist_view(&mut self, radians: f32) {
+        if self.fly.is_some() {
+            return;
+        }
+        self.stop_tour();
+        self.renderer.camera.spin(radians);
+    }
+
     /// Advance the simulation and render one frame.
     ///
     /// This method exists so Rust can see that `self.simulation.bodies` and
@@ -55,34 +576,105 @@ impl AppState {
     pub fn tick(&mut self, dt: f64) {
         self.simulation.update(dt);
 
-        let fps = if dt > 0.0 {
-            (1.0 / dt).min(1000.0) as f32
+        self.update_tour(dt as f32);
+
+        let pad = if self.options.page_controls {
+            gamepad::poll()
         } else {
-            0.0
+            None
         };
-        crate::hud::update(
-            self.simulation.time.current_days,
-            self.simulation.time.days_per_second,
-            self.simulation.time.paused,
-            fps,
-        );
+        let toggle_down = pad.is_some_and(|p| p.toggle_fly);
+        if toggle_down && !self.gamepad_toggle_down {
+            self.set_fly_mode(self.fly.is_none());
+        }
+        self.gamepad_toggle_down = toggle_down;
 
-        // If locked, keep the lerp target on the moving planet so the camera
-        // continuously follows it.
-        if self.camera_locked {
+        let anchor = self.sun_position();
+        if let Some(fly) = self.fly.as_mut() {
+            let mut input = FlyInput {
+                thrust: fly.keyboard_thrust(),
+                ..FlyInput::default()
+            };
+            if let Some(pad) = pad {
+                input.thrust += pad.input.thrust;
+                input.look_rate += pad.input.look_rate;
+            }
+            let speed = cruise_speed(anchor + fly.offset, &self.simulation.bodies);
+            fly.update(dt as f32, input, speed);
+            fly.apply_to(&mut self.renderer.camera, anchor);
+        } else if self.camera_locked {
+            // If locked, keep the lerp target on the moving planet so the
+            // camera continuously follows it.
             if let Some(idx) = self.selected_planet
                 && idx < self.simulation.bodies.len()
             {
-                self.renderer.camera.lerp_target = Some(self.simulation.bodies[idx].position);
+                let target = self.simulation.bodies[idx].position + self.pan_offset;
+                self.renderer.camera.follow(target);
             }
         } else {
-            // Default: keep camera centred on the Sun so it follows galactic drift.
+            // Default: keep camera centred on the Sun so it follows galactic
+            // drift, wherever the user panned relative to it.
             if let Some(sun) = self.simulation.bodies.iter().find(|b| b.is_star) {
-                self.renderer.camera.lerp_target = Some(sun.position);
+                self.renderer.camera.follow(sun.position + self.pan_offset);
             }
         }
 
+        self.update_momentum(dt as f32);
         self.renderer.camera.update_transition(dt as f32);
-        self.renderer.render(&self.simulation.bodies, dt as f32);
+        crate::input::update_gestures(self, dt as f32);
+        crate::input::update_hover(self, dt as f32);
+        self.renderer.render(
+            &self.simulation.bodies,
+            dt as f32,
+            self.simulation.time.current_days,
+            &self.i18n,
+        );
+
+        self.update_url(dt as f32);
+        self.update_session(dt as f32);
+        self.update_announcements();
+
+        if self.hud.is_connected() {
+            let fps = self.hud.measure_frame(dt);
+            let snapshot = self.hud_snapshot(fps);
+            self.hud.publish(&snapshot, dt as f32);
+        }
+    }
+
+    /// What the HUD shows this frame; takes the queued HUD events.
+    pub fn hud_snapshot(&mut self, fps: FpsStats) -> HudSnapshot {
+        let time = &self.simulation.time;
+        let sun = self.sun_position();
+        let eye = self.renderer.camera.eye_position();
+        let au = |a: DVec3, b: DVec3| a.distance(b) / AU_TO_DISPLAY as f64;
+        let selected = self
+            .selected_planet
+            .and_then(|idx| self.simulation.bodies.get(idx));
+        HudSnapshot {
+            locale: self.i18n.locale().id(),
+            date: url_state::days_to_iso(time.current_days),
+            date_label: self.i18n.date(time.current_days),
+            days: time.current_days,
+            speed: SpeedState::new(time.days_per_second, time.paused, &self.i18n),
+            fps,
+            gpu: self.renderer.frame_stats(),
+            fly_mode: self.fly.is_some(),
+            selected: selected.map(|b| SelectedBody::new(b, self.camera_locked, &self.i18n)),
+            live: selected.filter(|b| !b.is_star).map(|b| {
+                let earth = self
+                    .simulation
+                    .bodies
+                    .iter()
+                    .find(|e| e.id == "Earth" && e.id != b.id)
+                    .map(|e| e.position - sun);
+                LiveBody::new(b, b.position - sun, earth)
+            }),
+            distances: Distances {
+                camera_to_sun: au(eye, sun),
+                camera_to_selected: selected.map(|b| au(eye, b.position)),
+                selected_to_sun: selected.map(|b| au(b.position, sun)),
+            },
+            events: self.hud.take_events(),
+        }
     }
 }
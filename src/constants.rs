@@ -16,11 +16,14 @@ pub const ORBIT_SEGMENTS: usize = 128;
 
 // ─── Sphere mesh ─────────────────────────────────────────────────────────
 
-/// Longitude subdivisions for the planet sphere mesh.
-pub const SPHERE_SEGMENTS: u32 = 32;
+/// UV-sphere `(segments, rings)` per level of detail, finest first.
+pub const SPHERE_LOD_RESOLUTIONS: [(u32, u32); 4] = [(64, 48), (32, 24), (16, 12), (8, 6)];
 
-/// Latitude subdivisions for the planet sphere mesh.
-pub const SPHERE_RINGS: u32 = 24;
+/// Cube-sphere subdivisions per face per level of detail, finest first.
+pub const CUBE_SPHERE_LOD_SUBDIVISIONS: [u32; 4] = [24, 12, 6, 3];
+
+/// Minimum projected radius (device pixels) for each LOD but the coarsest.
+pub const SPHERE_LOD_THRESHOLDS_PX: [f32; 3] = [150.0, 40.0, 10.0];
 
 // ─── Saturn ring ─────────────────────────────────────────────────────────
 
@@ -33,6 +36,43 @@ pub const RING_OUTER_RADIUS: f32 = 2.3;
 /// Number of segments for the ring annulus mesh.
 pub const RING_SEGMENTS: u32 = 64;
 
+// ─── Textures ────────────────────────────────────────────────────────────
+
+/// Width of each layer of the body texture array (source images are rescaled).
+pub const TEXTURE_ARRAY_WIDTH: i32 = 1024;
+
+/// Height of each layer of the body texture array.
+pub const TEXTURE_ARRAY_HEIGHT: i32 = 512;
+
+// ─── Sun surface ─────────────────────────────────────────────────────────
+
+/// Solar differential rotation coefficients `(A, B, C)` in degrees per day:
+/// ω(φ) = A + B·sin²φ + C·sin⁴φ (Snodgrass & Ulrich, 1990).
+pub const SUN_DIFFERENTIAL_ROTATION: [f32; 3] = [14.713, -2.396, -1.787];
+
+/// Whether to draw animated prominences and corona glow around the Sun's limb.
+pub const SUN_PROMINENCES: bool = true;
+
+/// Outer edge of the prominence billboard, in solar radii.
+pub const SUN_PROMINENCE_EXTENT: f32 = 1.6;
+
+// ─── Body labels ─────────────────────────────────────────────────────────
+
+/// Label cap height in CSS pixels (multiplied by `devicePixelRatio`).
+pub const LABEL_SIZE_PX: f32 = 11.0;
+
+/// Gap between a body's projected edge and its label, in CSS pixels.
+pub const LABEL_OFFSET_PX: f32 = 6.0;
+
+/// Extra space kept around each label when culling overlaps, in CSS pixels.
+pub const LABEL_MARGIN_PX: f32 = 3.0;
+
+/// Camera distance (display units) at which labels start to fade out.
+pub const LABEL_FADE_START: f32 = 900.0;
+
+/// Camera distance (display units) beyond which labels are hidden.
+pub const LABEL_FADE_END: f32 = 2200.0;
+
 // ─── Starfield ───────────────────────────────────────────────────────────
 
 /// Number of background stars in the skybox.
@@ -81,9 +121,93 @@ pub const PHI_CLAMP: f32 = 1.4;
 /// Touch pinch zoom multiplier.
 pub const TOUCH_ZOOM_MULTIPLIER: f32 = 2.0;
 
+/// Panning speed, as a fraction of the camera distance per dragged pixel.
+pub const PAN_SENSITIVITY: f32 = 0.001;
+
 /// Maximum mouse movement, in CSS pixels, still considered a click.
 pub const CLICK_DRAG_THRESHOLD: f32 = 4.0;
 
+// ─── Touch gestures ─────────────────────────────────────────────────────
+
+/// How far a finger may travel, in CSS pixels, and still tap.
+pub const TAP_SLOP_PX: f32 = 10.0;
+
+/// Longest gap between the taps of a double-tap (seconds).
+pub const DOUBLE_TAP_SECONDS: f32 = 0.3;
+
+/// Furthest apart the taps of a double-tap may land, in CSS pixels.
+pub const DOUBLE_TAP_SLOP_PX: f32 = 30.0;
+
+/// How long a finger must rest to long-press (seconds).
+pub const LONG_PRESS_SECONDS: f32 = 0.5;
+
+// ─── Camera momentum ────────────────────────────────────────────────────
+
+/// Time constant of a flung rotation's decay (seconds).
+pub const ROTATE_INERTIA_SECONDS: f32 = 0.3;
+
+/// Time constant of the wheel and pinch zoom glide (seconds).
+pub const ZOOM_INERTIA_SECONDS: f32 = 0.12;
+
+/// Time constant of a flung pan's decay (seconds).
+pub const PAN_INERTIA_SECONDS: f32 = 0.3;
+
+/// Time window the velocity of a held drag is averaged over (seconds).
+pub const INERTIA_VELOCITY_WINDOW_SECONDS: f32 = 0.05;
+
+/// Coasting stops once the velocity drops to this fraction of where it started.
+pub const INERTIA_STOP_FRACTION: f32 = 0.02;
+
+/// Pixels per line of a line-mode wheel delta.
+pub const WHEEL_LINE_HEIGHT_PX: f32 = 16.0;
+
+/// Pixels per page of a page-mode wheel delta.
+pub const WHEEL_PAGE_HEIGHT_PX: f32 = 800.0;
+
+/// Smallest pixel-mode step taken for a wheel notch; finer ones come from trackpads.
+pub const WHEEL_NOTCH_MIN_PX: f32 = 50.0;
+
+/// Zoom gain of trackpad pinches, whose deltas are much finer than scrolling.
+pub const TRACKPAD_PINCH_ZOOM_GAIN: f32 = 8.0;
+
+// ─── Fly camera ─────────────────────────────────────────────────────────
+
+/// Mouse-look sensitivity in fly mode (radians per CSS pixel).
+pub const FLY_LOOK_SENSITIVITY: f32 = 0.003;
+
+/// How quickly fly velocity eases towards the requested velocity (1/s).
+pub const FLY_ACCELERATION: f32 = 5.0;
+
+/// Cruise speed per display unit of clearance to the nearest body surface
+/// (so the camera covers half the remaining gap each second).
+pub const FLY_SPEED_PER_DISTANCE: f32 = 0.5;
+
+/// Slowest cruise speed, in display units per second (close to a surface).
+pub const FLY_MIN_SPEED: f32 = 0.5;
+
+/// Fastest cruise speed, in display units per second (deep space).
+pub const FLY_MAX_SPEED: f32 = 400.0;
+
+/// Gamepad right-stick look rate at full deflection (radians per second).
+pub const FLY_GAMEPAD_LOOK_RATE: f32 = 2.0;
+
+/// Radial stick deadzone, as a fraction of full deflection.
+pub const GAMEPAD_DEADZONE: f32 = 0.15;
+
+// ─── Physical constants ─────────────────────────────────────────────────
+
+/// Newtonian constant of gravitation (m³ kg⁻¹ s⁻²).
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11;
+
+/// Standard gravitational parameter of the Sun, GM☉ (km³/s²).
+pub const SUN_GM_KM3_S2: f64 = 1.327_124_400_18e11;
+
+/// One astronomical unit in km.
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// Speed of light in vacuum (km/s).
+pub const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+
 // ─── Galactic motion ─────────────────────────────────────────────────────────
 
 /// Real orbital speed of the Sun around the galactic centre (km/s).
@@ -99,19 +223,99 @@ pub const GALACTIC_PERIOD_YEARS: f64 = 230_000_000.0;
 /// Derived from the real speed (220 km/s → ~0.127 AU/day → ~5.1 display-units/day
 /// with `AU_TO_DISPLAY = 40`).  Reduce this value to slow the visible drift,
 /// or increase it to exaggerate the galactic motion for demonstration purposes.
-pub const GALACTIC_SPEED_DISPLAY: f32 = 5.086;
+pub const GALACTIC_SPEED_DISPLAY: f64 = 5.086;
 
 // ─── Planet selection / camera focus ────────────────────────────────────
 
-/// Speed factor for smooth camera-target transition (higher = faster convergence).
+/// Speed factor for the camera target following a moving body (higher = faster convergence).
 pub const CAMERA_LERP_SPEED: f32 = 4.0;
 
+/// Duration of camera moves such as selecting a body or the top-down view (seconds).
+pub const CAMERA_TRANSITION_SECONDS: f32 = 1.2;
+
 /// Multiplier applied to `display_radius` to compute ray-click hit radius.
 pub const PLANET_CLICK_RADIUS_FACTOR: f32 = 2.5;
 
+/// How far from the pointer a click still picks a body, orbit or trail, in
+/// CSS pixels.
+pub const PICK_RADIUS_PX: f32 = 6.0;
+
 /// Multiplier applied to `display_radius` to compute auto-zoom distance.
 pub const PLANET_ZOOM_FACTOR: f32 = 12.0;
 
+// ─── Bookmarks & tours ──────────────────────────────────────────────────
+
+/// Seconds to fly to a saved bookmark when cycling through them.
+pub const BOOKMARK_TRAVEL_SECONDS: f32 = 2.5;
+
+/// Travel time of a tour stop that does not specify one.
+pub const TOUR_DEFAULT_TRAVEL_SECONDS: f32 = 4.0;
+
+// ─── Deep links ─────────────────────────────────────────────────────────
+
+/// How often the URL hash is refreshed with the current view (seconds).
+pub const URL_STATE_INTERVAL_SECONDS: f32 = 1.0;
+
+// ─── Sessions ───────────────────────────────────────────────────────────
+
+/// How often the session is saved to localStorage (seconds).
+pub const SESSION_SAVE_INTERVAL_SECONDS: f32 = 5.0;
+
+// ─── HUD ────────────────────────────────────────────────────────────────
+
+/// Minimum time between HUD snapshots that only move the running telemetry
+/// (date, FPS, distances), in seconds.
+pub const HUD_UPDATE_INTERVAL_SECONDS: f32 = 0.1;
+
+/// Weight of the newest frame in the HUD's average FPS.
+pub const HUD_FPS_SMOOTHING: f32 = 0.1;
+
+/// Simulation speed from which the HUD counts years per second.
+pub const HUD_YEARS_SPEED_THRESHOLD: f64 = 365.25;
+
+// ─── Measuring tool ─────────────────────────────────────────────────────
+
+/// Colour of measurement lines and their labels.
+pub const MEASURE_COLOR: [f32; 3] = [1.0, 0.82, 0.3];
+
+/// Opacity of measurement lines.
+pub const MEASURE_ALPHA: f32 = 0.9;
+
+// ─── Hover ──────────────────────────────────────────────────────────────
+
+/// Minimum time between hover hit tests (seconds).
+pub const HOVER_PICK_INTERVAL_SECONDS: f32 = 0.05;
+
+/// Colour of the glow ring around the hovered body and of its tooltip.
+pub const HOVER_COLOR: [f32; 3] = [0.55, 0.8, 1.0];
+
+/// Radius of the glow ring as a multiple of the body's on-screen radius.
+pub const HOVER_RING_SCALE: f32 = 1.35;
+
+/// Smallest glow ring radius, in CSS pixels, so distant dots still get one.
+pub const HOVER_RING_MIN_PX: f32 = 10.0;
+
+/// How far the glow fades out beyond the ring, as a multiple of its radius.
+pub const HOVER_GLOW_EXTENT: f32 = 1.4;
+
+// ─── Accessibility ──────────────────────────────────────────────────────
+
+/// Opacity of orbit lines.
+pub const ORBIT_ALPHA: f32 = 0.2;
+
+/// Opacity of orbit lines in the high-contrast palette.
+pub const HIGH_CONTRAST_ORBIT_ALPHA: f32 = 0.9;
+
+/// How far high-contrast orbit colours are lifted towards white, after
+/// being brought to full brightness.
+pub const HIGH_CONTRAST_WHITE_MIX: f32 = 0.25;
+
+/// Opacity of the dark halo behind label text.
+pub const LABEL_HALO_ALPHA: f32 = 0.7;
+
+/// Opacity of the label halo in the high-contrast palette.
+pub const HIGH_CONTRAST_LABEL_HALO_ALPHA: f32 = 1.0;
+
 // ─── Planet trails ──────────────────────────────────────────────────────
 
 /// Maximum number of past positions stored per planet trail.
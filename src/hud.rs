@@ -1,34 +1,384 @@
-//! HUD (Head-Up Display) DOM updates.
+//! HUD (Head-Up Display) updates.
 //!
-//! Follows the same wasm-bindgen inline-JS pattern as `splash.rs` for
-//! CSP-compatible, type-safe DOM manipulation without `eval`.
+//! A viewer's HUD is an object of JavaScript callbacks supplied by the page
+//! (the `hud` viewer option), so each viewer can drive its own telemetry
+//! panel. Every frame the viewer builds a [`HudSnapshot`] (date, speed, frame
+//! stats, camera mode, the selected body and where it is on its orbit,
+//! distances and what happened since the last snapshot) and the page's
+//! `update` callback receives it as one plain object. The page renders
+//! everything from that data; no markup is built here.
+//!
+//! Snapshots whose only changes are running telemetry are throttled to
+//! [`HUD_UPDATE_INTERVAL_SECONDS`]; anything else (a new selection, a pause,
+//! an event) is sent on the frame it happens. `toggle`, `toggleHelp` and
+//! `bindings` remain separate callbacks, as they are commands rather than
+//! per-frame state. A viewer without a HUD skips all of it.
 
+use glam::DVec3;
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen(inline_js = "
-    export function hud_update(days, speed, paused, fps) {
-        if (window.solaraUpdateHud) window.solaraUpdateHud(days, speed, paused, fps);
+use crate::constants::{
+    AU_KM, AU_TO_DISPLAY, HUD_FPS_SMOOTHING, HUD_UPDATE_INTERVAL_SECONDS,
+    HUD_YEARS_SPEED_THRESHOLD, SPEED_OF_LIGHT_KM_S,
+};
+use crate::i18n::I18n;
+use crate::input::actions::HelpRow;
+use crate::renderer::render_pass::FrameStats;
+use crate::simulation::body::CelestialBody;
+
+/// Everything the page's HUD shows, for one frame.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HudSnapshot {
+    /// [`Locale::id`](crate::i18n::Locale::id) of the strings below; the page
+    /// formats its own numbers with it.
+    pub locale: &'static str,
+    /// Simulation date as ISO-8601 UTC.
+    pub date: String,
+    /// Simulation date formatted for the locale.
+    pub date_label: String,
+    /// Simulation date in days since J2000.
+    pub days: f64,
+    pub speed: SpeedState,
+    pub fps: FpsStats,
+    /// GPU work of the last rendered frame.
+    pub gpu: FrameStats,
+    /// Whether the camera is in free-fly mode rather than orbiting.
+    pub fly_mode: bool,
+    pub selected: Option<SelectedBody>,
+    /// Values of the selected body that change as the planets move.
+    pub live: Option<LiveBody>,
+    pub distances: Distances,
+    /// What happened since the previous snapshot, oldest first.
+    pub events: Vec<HudEvent>,
+}
+
+impl HudSnapshot {
+    /// Whether the two differ only in running telemetry (date, FPS, GPU
+    /// counters, live values, distances).
+    pub fn same_state(&self, other: &HudSnapshot) -> bool {
+        self.locale == other.locale
+            && self.speed == other.speed
+            && self.fly_mode == other.fly_mode
+            && self.selected == other.selected
+            && self.events == other.events
+    }
+}
+
+/// Clock speed, with a ready-made label.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedState {
+    pub days_per_second: f64,
+    pub paused: bool,
+    /// `Paused`, `×2.0 d/s` or `×1.5 yr/s`, localised.
+    pub label: String,
+}
+
+impl SpeedState {
+    pub fn new(days_per_second: f64, paused: bool, i18n: &I18n) -> Self {
+        Self {
+            days_per_second,
+            paused,
+            label: speed_label(days_per_second, paused, i18n),
+        }
+    }
+}
+
+/// The HUD's speed label.
+pub fn speed_label(days_per_second: f64, paused: bool, i18n: &I18n) -> String {
+    if paused {
+        i18n.text("speed.paused").to_string()
+    } else if days_per_second >= HUD_YEARS_SPEED_THRESHOLD {
+        let years = i18n.number(days_per_second / HUD_YEARS_SPEED_THRESHOLD, 1);
+        i18n.format("speed.years", &[("value", &years)])
+    } else {
+        i18n.format("speed.days", &[("value", &i18n.number(days_per_second, 1))])
+    }
+}
+
+/// Frame-rate figures.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FpsStats {
+    /// Frames per second of the last frame alone.
+    pub current: f32,
+    /// Exponential moving average, steadier for display.
+    pub average: f32,
+    /// Duration of the last frame in milliseconds.
+    pub frame_ms: f32,
+}
+
+/// Smooths per-frame FPS into [`FpsStats`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FpsMeter {
+    average: Option<f32>,
+}
+
+impl FpsMeter {
+    /// Record a frame of `dt` seconds.
+    pub fn update(&mut self, dt: f64) -> FpsStats {
+        let current = if dt > 0.0 {
+            (1.0 / dt).min(1000.0) as f32
+        } else {
+            0.0
+        };
+        let average = match self.average {
+            Some(average) => average + (current - average) * HUD_FPS_SMOOTHING,
+            None => current,
+        };
+        self.average = Some(average);
+        FpsStats {
+            current,
+            average,
+            frame_ms: (dt * 1000.0) as f32,
+        }
+    }
+}
+
+/// Catalogue facts about the selected body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedBody {
+    /// Stable body id, e.g. `Earth`.
+    pub name: String,
+    /// Localised name, e.g. `Terre`.
+    pub display_name: String,
+    pub is_star: bool,
+    pub radius_km: f64,
+    pub semi_major_axis_au: f64,
+    pub orbital_period_days: f64,
+    pub inclination_deg: f64,
+    pub eccentricity: f64,
+    pub mass_kg: f64,
+    /// Surface gravity in m/s².
+    pub gravity: f64,
+    pub escape_velocity_km_s: f64,
+    /// Orbital speed around the Sun in km/s.
+    pub orbital_speed_km_s: f64,
+    /// Light travel time from the Sun in seconds.
+    pub sun_light_seconds: f64,
+    pub day_length_hours: f64,
+    pub moons: u32,
+    /// Whether the camera follows the body.
+    pub locked: bool,
+}
+
+impl SelectedBody {
+    pub fn new(body: &CelestialBody, locked: bool, i18n: &I18n) -> Self {
+        Self {
+            name: body.id.to_string(),
+            display_name: i18n.body_name(body.id).to_string(),
+            is_star: body.is_star,
+            radius_km: body.real_radius_km,
+            semi_major_axis_au: body.semi_major_axis_au,
+            orbital_period_days: body.orbital_period_days,
+            inclination_deg: body.inclination_rad.to_degrees(),
+            eccentricity: body.eccentricity,
+            mass_kg: body.mass_kg,
+            gravity: body.surface_gravity(),
+            escape_velocity_km_s: body.escape_velocity_km_s(),
+            orbital_speed_km_s: body.orbital_speed_km_s(),
+            sun_light_seconds: light_seconds(body.semi_major_axis_au),
+            day_length_hours: body.day_length_hours,
+            moons: body.moons,
+            locked,
+        }
+    }
+}
+
+/// Values of the selected body that change as the planets move, recomputed
+/// every frame. Orbits are circular, so its distance from the Sun and its
+/// speed are catalogue values on [`SelectedBody`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveBody {
+    /// Distance from Earth in AU; absent for Earth itself or when Earth is
+    /// not simulated.
+    pub earth_distance_au: Option<f64>,
+    /// Light travel time from Earth in seconds.
+    pub earth_light_seconds: Option<f64>,
+    /// Position around the orbit in radians, for the orbit diagram.
+    pub orbit_angle: f64,
+}
+
+impl LiveBody {
+    /// Live values of `body` given its and Earth's offsets from the Sun in
+    /// display units.
+    pub fn new(body: &CelestialBody, heliocentric: DVec3, earth: Option<DVec3>) -> Self {
+        let earth_distance_au =
+            earth.map(|earth| (heliocentric - earth).length() / AU_TO_DISPLAY as f64);
+        Self {
+            earth_distance_au,
+            earth_light_seconds: earth_distance_au.map(light_seconds),
+            orbit_angle: body.orbit_angle(heliocentric),
+        }
+    }
+}
+
+/// Time light takes to cross `distance_au`, in seconds.
+fn light_seconds(distance_au: f64) -> f64 {
+    distance_au * AU_KM / SPEED_OF_LIGHT_KM_S
+}
+
+/// Live distances in AU.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distances {
+    pub camera_to_sun: f64,
+    /// From the camera to the selected body, when there is one.
+    pub camera_to_selected: Option<f64>,
+    /// From the Sun to the selected body, when there is one.
+    pub selected_to_sun: Option<f64>,
+}
+
+/// Something that happened, for the HUD to announce.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HudEvent {
+    Select { body: String },
+    Deselect,
+    BookmarkSaved { name: String },
+    TourStarted { name: String },
+    TourFinished,
+}
+
+/// Decides which snapshots are worth sending.
+#[derive(Debug, Default)]
+pub struct HudThrottle {
+    /// The snapshot last sent.
+    last: Option<HudSnapshot>,
+    /// Seconds since it was sent.
+    elapsed: f32,
+    /// Send the next snapshot whatever it holds.
+    forced: bool,
+}
+
+impl HudThrottle {
+    /// Let the next snapshot through, e.g. after the date jumped.
+    pub fn invalidate(&mut self) {
+        self.forced = true;
     }
-    export function hud_toggle() {
-        if (window.solaraToggleHud) window.solaraToggleHud();
+
+    /// Whether to send `snapshot`, `dt` seconds after the previous call.
+    pub fn ready(&mut self, snapshot: &HudSnapshot, dt: f32) -> bool {
+        self.elapsed += dt;
+        let urgent = self.forced
+            || !snapshot.events.is_empty()
+            || self
+                .last
+                .as_ref()
+                .is_none_or(|last| !last.same_state(snapshot));
+        let due =
+            self.elapsed >= HUD_UPDATE_INTERVAL_SECONDS && self.last.as_ref() != Some(snapshot);
+        if !urgent && !due {
+            return false;
+        }
+        self.forced = false;
+        self.elapsed = 0.0;
+        self.last = Some(snapshot.clone());
+        true
     }
-")]
-extern "C" {
-    fn hud_update(days: f64, speed: f64, paused: bool, fps: f32);
-    fn hud_toggle();
 }
 
-/// Push current simulation telemetry to the HUD DOM elements.
-///
-/// - `current_days`   — simulation time in Earth-days since J2000
-/// - `days_per_second`— simulation speed multiplier
-/// - `paused`         — whether the simulation is paused
-/// - `fps`            — raw frames-per-second for this frame
-pub fn update(current_days: f64, days_per_second: f64, paused: bool, fps: f32) {
-    hud_update(current_days, days_per_second, paused, fps);
+/// Page callbacks for one viewer's HUD.
+#[derive(Default)]
+pub struct Hud {
+    callbacks: Option<js_sys::Object>,
+    throttle: HudThrottle,
+    fps: FpsMeter,
+    /// Events for the next snapshot.
+    events: Vec<HudEvent>,
 }
 
-/// Toggle HUD visibility (bound to the `H` key).
-pub fn toggle() {
-    hud_toggle();
+impl Hud {
+    pub fn new(callbacks: Option<js_sys::Object>) -> Self {
+        Self {
+            callbacks,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the page supplied any callbacks.
+    pub fn is_connected(&self) -> bool {
+        self.callbacks.is_some()
+    }
+
+    /// Call `callbacks[name](...args)`, if it is a function.
+    fn call(&self, name: &str, args: &[JsValue]) {
+        let Some(callbacks) = &self.callbacks else {
+            return;
+        };
+        let Ok(callback) = js_sys::Reflect::get(callbacks, &name.into())
+            .and_then(|f| f.dyn_into::<js_sys::Function>())
+        else {
+            return;
+        };
+        let args: js_sys::Array = args.iter().collect();
+        if let Err(err) = callback.apply(callbacks, &args) {
+            log::warn!("⚠️ HUD callback {name} threw: {err:?}");
+        }
+    }
+
+    /// Record a frame of `dt` seconds for the FPS figures.
+    pub fn measure_frame(&mut self, dt: f64) -> FpsStats {
+        self.fps.update(dt)
+    }
+
+    /// Queue `event` for the next snapshot.
+    pub fn push_event(&mut self, event: HudEvent) {
+        if self.is_connected() {
+            self.events.push(event);
+        }
+    }
+
+    /// Events queued since the last snapshot.
+    pub fn take_events(&mut self) -> Vec<HudEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Send the next snapshot even if the throttle would hold it back.
+    pub fn invalidate(&mut self) {
+        self.throttle.invalidate();
+    }
+
+    /// Pass `snapshot` to `update`, unless throttled.
+    pub fn publish(&mut self, snapshot: &HudSnapshot, dt: f32) {
+        if !self.is_connected() || !self.throttle.ready(snapshot, dt) {
+            return;
+        }
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        match snapshot.serialize(&serializer) {
+            Ok(value) => self.call("update", &[value]),
+            Err(err) => log::warn!("⚠️ HUD snapshot: {err}"),
+        }
+    }
+
+    /// Toggle HUD visibility (`toggle`).
+    pub fn toggle(&self) {
+        self.call("toggle", &[]);
+    }
+
+    /// Toggle the keyboard-shortcut help panel (`toggleHelp`).
+    pub fn toggle_help(&self) {
+        self.call("toggleHelp", &[]);
+    }
+
+    /// List the current key bindings in the help panel (`bindings`).
+    ///
+    /// Each row is sent as a `[section, keys, description, action]` array.
+    pub fn set_bindings(&self, rows: &[HelpRow]) {
+        let array = js_sys::Array::new();
+        for row in rows {
+            let entry = js_sys::Array::of4(
+                &row.section.as_str().into(),
+                &row.keys.as_str().into(),
+                &row.description.as_str().into(),
+                &row.action.as_str().into(),
+            );
+            array.push(&entry);
+        }
+        self.call("bindings", &[array.into()]);
+    }
 }
@@ -0,0 +1,268 @@
+//! Localisation: message catalogues, locale selection and number/date
+//! formatting.
+//!
+//! Messages live in `config/locales/<locale>.conf` as `key = message` lines,
+//! with `{name}` placeholders. English is the reference catalogue: any key a
+//! translation lacks falls back to it. The engine formats its own strings
+//! (speed label, body labels, help rows, the HUD date) and hands the page the
+//! whole catalogue through `SolaraHandle.messages()` for the rest.
+//!
+//! Bodies keep their English [`id`](crate::simulation::body::CelestialBody::id)
+//! in links, sessions and the API; only what is displayed is translated.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::url_state;
+
+const EN: &str = include_str!("../config/locales/en.conf");
+const FR: &str = include_str!("../config/locales/fr.conf");
+
+/// A supported UI language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Fr];
+
+    /// Language subtag, e.g. `fr`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+
+    /// Locale for a BCP 47 language tag (`fr`, `fr-CA`, `en_GB`); only the
+    /// language subtag is considered.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.trim();
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.id().eq_ignore_ascii_case(language))
+    }
+
+    /// First supported locale among `preferred` (most preferred first);
+    /// English when none is.
+    pub fn negotiate<'a>(preferred: impl IntoIterator<Item = &'a str>) -> Self {
+        preferred
+            .into_iter()
+            .find_map(Self::parse)
+            .unwrap_or_default()
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => EN,
+            Locale::Fr => FR,
+        }
+    }
+
+    fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::Fr => ',',
+        }
+    }
+
+    fn group_separator(self) -> char {
+        match self {
+            Locale::En => ',',
+            // Narrow no-break space, as recommended for French.
+            Locale::Fr => '\u{202f}',
+        }
+    }
+}
+
+/// A malformed line in a message catalogue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogueError {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CatalogueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "messages, line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CatalogueError {}
+
+/// Key → message table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalogue {
+    messages: HashMap<String, String>,
+}
+
+impl Catalogue {
+    /// Parse `key = message` lines. Blank lines and `#` comments are
+    /// ignored; a key may appear only once.
+    pub fn parse(text: &str) -> Result<Self, CatalogueError> {
+        let mut messages = HashMap::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| CatalogueError {
+                line: i + 1,
+                message,
+            };
+            let (key, message) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected `key = message`, got `{line}`")))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(error("missing key".into()));
+            }
+            if messages
+                .insert(key.to_string(), message.trim().to_string())
+                .is_some()
+            {
+                return Err(error(format!("duplicate key `{key}`")));
+            }
+        }
+        Ok(Self { messages })
+    }
+
+    /// The bundled catalogue of `locale`, without fallbacks.
+    pub fn bundled(locale: Locale) -> Self {
+        Self::parse(locale.source()).expect("config/locales catalogues are valid")
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.messages.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// Messages and formatting conventions of one locale.
+#[derive(Debug, Clone)]
+pub struct I18n {
+    locale: Locale,
+    /// The locale's messages, with English filling any gaps.
+    messages: Catalogue,
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        Self::new(Locale::default())
+    }
+}
+
+impl I18n {
+    pub fn new(locale: Locale) -> Self {
+        let mut messages = Catalogue::bundled(Locale::En);
+        if locale != Locale::En {
+            messages
+                .messages
+                .extend(Catalogue::bundled(locale).messages);
+        }
+        Self { locale, messages }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// All messages, for the page.
+    pub fn messages(&self) -> &Catalogue {
+        &self.messages
+    }
+
+    /// Message for `key`; the key itself if no catalogue has it, so a
+    /// missing message shows up rather than disappearing.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).unwrap_or(key)
+    }
+
+    /// Message for `key` with its `{name}` placeholders replaced by `args`.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let mut text = self.text(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        text
+    }
+
+    /// Displayed name of the body with stable id `id`.
+    pub fn body_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.messages
+            .get(&format!("body.{}", id.to_ascii_lowercase()))
+            .unwrap_or(id)
+    }
+
+    /// `value` with `decimals` digits after the separator and grouped
+    /// thousands, e.g. `12,345.6` or `12 345,6`.
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let fixed = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let negative = value < 0.0 && fixed.bytes().any(|b| b.is_ascii_digit() && b != b'0');
+
+        let mut text = String::with_capacity(fixed.len() + integer.len() / 3 + 1);
+        if negative {
+            text.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                text.push(self.locale.group_separator());
+            }
+            text.push(digit);
+        }
+        if !fraction.is_empty() {
+            text.push(self.locale.decimal_separator());
+            text.push_str(fraction);
+        }
+        text
+    }
+
+    /// Calendar date (UTC) of `days` since J2000, e.g. `Jul 20, 1969` or
+    /// `20 juil. 1969`.
+    pub fn date(&self, days: f64) -> String {
+        let (year, month, day) = url_state::civil_date(days);
+        let month_name = self
+            .text("date.months")
+            .split_whitespace()
+            .nth(month as usize - 1)
+            .unwrap_or_default();
+        self.format(
+            "date.format",
+            &[
+                ("year", &year.to_string()),
+                ("month", month_name),
+                ("day", &day.to_string()),
+            ],
+        )
+    }
+}
+
+// ── DOM ──
+
+/// The browser's preferred languages, most preferred first.
+pub fn browser_languages() -> Vec<String> {
+    let Some(navigator) = web_sys::window().map(|w| w.navigator()) else {
+        return Vec::new();
+    };
+    let languages: Vec<String> = navigator
+        .languages()
+        .iter()
+        .filter_map(|tag| tag.as_string())
+        .collect();
+    if languages.is_empty() {
+        navigator.language().into_iter().collect()
+    } else {
+        languages
+    }
+}
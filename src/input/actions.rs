@@ -0,0 +1,444 @@
+//! Input actions and the remappable key-binding table.
+//!
+//! Keyboard events are translated into [`Action`]s through [`KeyBindings`],
+//! then applied to anything implementing [`ActionTarget`] by [`dispatch`].
+//! Nothing here touches the DOM, so the whole path is testable natively.
+//!
+//! Default bindings live in `config/keybindings.conf`; users can override
+//! individual keys with the same `key = action` format (see
+//! [`KeyBindings::apply`]).
+
+use std::fmt;
+
+use crate::constants::DEFAULT_DAYS_PER_SECOND;
+use crate::i18n::I18n;
+use crate::renderer::fly_camera::FlyDirection;
+use crate::simulation::time::SimulationTime;
+
+/// Default binding table, embedded at compile time.
+const DEFAULT_BINDINGS: &str = include_str!("../../config/keybindings.conf");
+
+/// Something the user can trigger from the keyboard or a HUD button.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    TogglePause,
+    SpeedUp,
+    SpeedDown,
+    ResetSpeed,
+    /// Restore the default clock, camera and display, and forget the saved session.
+    ResetDefaults,
+    Recenter,
+    TopDownView,
+    ToggleFlyMode,
+    SaveBookmark,
+    NextBookmark,
+    /// Play the bundled tour, or stop the one playing.
+    ToggleTour,
+    /// Thrust in fly mode while the key is held.
+    Fly(FlyDirection),
+    /// Select and fly to the body with this name.
+    SelectBody(String),
+    Deselect,
+    /// Remove the measuring tool's lines.
+    ClearMeasurement,
+    ToggleHud,
+    CycleLabels,
+    ToggleOrbits,
+    ToggleTrails,
+    ToggleHelp,
+}
+
+impl Action {
+    /// Parse a config identifier such as `toggle_pause` or `select_body:Earth`.
+    pub fn parse(id: &str) -> Option<Action> {
+        if let Some(name) = id.strip_prefix("select_body:") {
+            let name = name.trim();
+            return (!name.is_empty()).then(|| Action::SelectBody(name.to_string()));
+        }
+        Some(match id {
+            "toggle_pause" => Action::TogglePause,
+            "speed_up" => Action::SpeedUp,
+            "speed_down" => Action::SpeedDown,
+            "reset_speed" => Action::ResetSpeed,
+            "reset_defaults" => Action::ResetDefaults,
+            "recenter" => Action::Recenter,
+            "top_down_view" => Action::TopDownView,
+            "toggle_fly_mode" => Action::ToggleFlyMode,
+            "save_bookmark" => Action::SaveBookmark,
+            "next_bookmark" => Action::NextBookmark,
+            "toggle_tour" => Action::ToggleTour,
+            "fly_forward" => Action::Fly(FlyDirection::Forward),
+            "fly_back" => Action::Fly(FlyDirection::Back),
+            "fly_left" => Action::Fly(FlyDirection::Left),
+            "fly_right" => Action::Fly(FlyDirection::Right),
+            "fly_up" => Action::Fly(FlyDirection::Up),
+            "fly_down" => Action::Fly(FlyDirection::Down),
+            "deselect" => Action::Deselect,
+            "clear_measurement" => Action::ClearMeasurement,
+            "toggle_hud" => Action::ToggleHud,
+            "cycle_labels" => Action::CycleLabels,
+            "toggle_orbits" => Action::ToggleOrbits,
+            "toggle_trails" => Action::ToggleTrails,
+            "toggle_help" => Action::ToggleHelp,
+            _ => return None,
+        })
+    }
+
+    /// Config identifier; the inverse of [`Action::parse`].
+    pub fn id(&self) -> String {
+        match self {
+            Action::TogglePause => "toggle_pause".into(),
+            Action::SpeedUp => "speed_up".into(),
+            Action::SpeedDown => "speed_down".into(),
+            Action::ResetSpeed => "reset_speed".into(),
+            Action::ResetDefaults => "reset_defaults".into(),
+            Action::Recenter => "recenter".into(),
+            Action::TopDownView => "top_down_view".into(),
+            Action::ToggleFlyMode => "toggle_fly_mode".into(),
+            Action::SaveBookmark => "save_bookmark".into(),
+            Action::NextBookmark => "next_bookmark".into(),
+            Action::ToggleTour => "toggle_tour".into(),
+            Action::Fly(direction) => match direction {
+                FlyDirection::Forward => "fly_forward".into(),
+                FlyDirection::Back => "fly_back".into(),
+                FlyDirection::Left => "fly_left".into(),
+                FlyDirection::Right => "fly_right".into(),
+                FlyDirection::Up => "fly_up".into(),
+                FlyDirection::Down => "fly_down".into(),
+            },
+            Action::SelectBody(name) => format!("select_body:{name}"),
+            Action::Deselect => "deselect".into(),
+            Action::ClearMeasurement => "clear_measurement".into(),
+            Action::ToggleHud => "toggle_hud".into(),
+            Action::CycleLabels => "cycle_labels".into(),
+            Action::ToggleOrbits => "toggle_orbits".into(),
+            Action::ToggleTrails => "toggle_trails".into(),
+            Action::ToggleHelp => "toggle_help".into(),
+        }
+    }
+
+    /// Description for the help overlay, from the `action.<id>` message.
+    pub fn description(&self, i18n: &I18n) -> String {
+        match self {
+            Action::SelectBody(name) => {
+                i18n.format("action.select_body", &[("body", i18n.body_name(name))])
+            }
+            action => i18n.text(&format!("action.{}", action.id())).to_string(),
+        }
+    }
+
+    /// Help-overlay section the action is listed under (its `section.<id>`
+    /// message is the heading).
+    pub fn section(&self) -> &'static str {
+        match self {
+            Action::Recenter | Action::TopDownView => "camera",
+            Action::ToggleFlyMode | Action::Fly(_) => "fly",
+            Action::SaveBookmark | Action::NextBookmark | Action::ToggleTour => "tours",
+            Action::TogglePause
+            | Action::SpeedUp
+            | Action::SpeedDown
+            | Action::ResetSpeed
+            | Action::ResetDefaults => "simulation",
+            Action::SelectBody(_) | Action::Deselect | Action::ClearMeasurement => "planets",
+            Action::ToggleHud
+            | Action::CycleLabels
+            | Action::ToggleOrbits
+            | Action::ToggleTrails
+            | Action::ToggleHelp => "display",
+        }
+    }
+
+    /// Whether the action lasts while its key is held (see [`release`]).
+    pub fn is_held(&self) -> bool {
+        matches!(self, Action::Fly(_))
+    }
+}
+
+/// Canonical form of a `KeyboardEvent.key` value.
+///
+/// Single characters are lower-cased (so `R` and `r` are the same binding)
+/// and the space bar is spelled `Space`.
+pub fn normalize_key(key: &str) -> String {
+    match key {
+        " " | "Spacebar" => "Space".to_string(),
+        k if k.chars().count() == 1 => k.to_lowercase(),
+        k => k.to_string(),
+    }
+}
+
+/// Short label for a normalised key, as shown in the help overlay.
+pub fn key_label(key: &str) -> String {
+    match key {
+        "ArrowUp" => "↑".into(),
+        "ArrowDown" => "↓".into(),
+        "ArrowLeft" => "←".into(),
+        "ArrowRight" => "→".into(),
+        "Escape" => "Esc".into(),
+        "Backspace" => "⌫".into(),
+        "-" => "−".into(),
+        k if k.chars().count() == 1 => k.to_uppercase(),
+        k => k.into(),
+    }
+}
+
+/// A malformed line in a binding config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingError {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key bindings, line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for BindingError {}
+
+/// One row of the help overlay: every key bound to the same action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpRow {
+    /// Section heading, localised.
+    pub section: String,
+    /// Key labels joined with ` / `, e.g. `+ / ↑`.
+    pub keys: String,
+    pub description: String,
+    /// [`Action::id`], so the overlay can trigger the action on click.
+    pub action: String,
+}
+
+/// Key → action table. Each key triggers at most one action; an action may
+/// have several keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    /// `(normalised key, action)` in config order.
+    entries: Vec<(String, Action)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::parse(DEFAULT_BINDINGS).expect("config/keybindings.conf is valid")
+    }
+}
+
+impl KeyBindings {
+    /// Parse a complete binding table.
+    pub fn parse(text: &str) -> Result<Self, BindingError> {
+        let mut bindings = Self { entries: Vec::new() };
+        bindings.apply(text)?;
+        Ok(bindings)
+    }
+
+    /// Apply `key = action` overrides on top of the current table.
+    ///
+    /// Blank lines and `#` comments are ignored; `key = none` unbinds a key.
+    /// Nothing is changed if any line is malformed.
+    pub fn apply(&mut self, text: &str) -> Result<(), BindingError> {
+        let mut updated = self.clone();
+        for (i, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| BindingError { line: i + 1, message };
+            let (key, action) = line
+                .rsplit_once('=')
+                .ok_or_else(|| error(format!("expected `key = action`, got `{line}`")))?;
+            let (key, action) = (key.trim(), action.trim());
+            if key.is_empty() {
+                return Err(error("missing key".into()));
+            }
+            if action == "none" {
+                updated.unbind(key);
+            } else {
+                let action =
+                    Action::parse(action).ok_or_else(|| error(format!("unknown action `{action}`")))?;
+                updated.bind(key, action);
+            }
+        }
+        *self = updated;
+        Ok(())
+    }
+
+    /// Bind `key` to `action`, replacing whatever the key did before.
+    pub fn bind(&mut self, key: &str, action: Action) {
+        let key = normalize_key(key);
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = action,
+            None => self.entries.push((key, action)),
+        }
+    }
+
+    /// Remove any binding for `key`.
+    pub fn unbind(&mut self, key: &str) {
+        let key = normalize_key(key);
+        self.entries.retain(|(k, _)| *k != key);
+    }
+
+    /// Action bound to a raw `KeyboardEvent.key` value.
+    pub fn action_for(&self, key: &str) -> Option<&Action> {
+        let key = normalize_key(key);
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, a)| a)
+    }
+
+    /// Normalised keys bound to `action`, in config order.
+    pub fn keys_for(&self, action: &Action) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, a)| a == action)
+            .map(|(k, _)| k.as_str())
+            .collect()
+    }
+
+    /// Help-overlay rows, one per bound action, grouped by section.
+    pub fn help_rows(&self, i18n: &I18n) -> Vec<HelpRow> {
+        let mut actions: Vec<&Action> = Vec::new();
+        for (_, action) in &self.entries {
+            if !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
+        const SECTIONS: [&str; 6] = ["camera", "fly", "tours", "simulation", "planets", "display"];
+        actions.sort_by_key(|a| SECTIONS.iter().position(|s| *s == a.section()));
+        actions
+            .into_iter()
+            .map(|action| {
+                let keys: Vec<String> = self.keys_for(action).into_iter().map(key_label).collect();
+                HelpRow {
+                    section: i18n.text(&format!("section.{}", action.section())).to_string(),
+                    keys: keys.join(" / "),
+                    description: action.description(i18n),
+                    action: action.id(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Receiver of dispatched actions — implemented by the app state, and by
+/// mocks in tests.
+pub trait ActionTarget {
+    /// Simulation clock, for the time-control actions.
+    fn time(&mut self) -> &mut SimulationTime;
+    /// Called once after any action that changed the clock (HUD refresh).
+    fn time_changed(&mut self);
+    /// Select the body called `name` (case-insensitive); unknown names are ignored.
+    fn select_body(&mut self, name: &str);
+    fn deselect(&mut self);
+    fn clear_measurement(&mut self);
+    fn recenter(&mut self);
+    fn top_down_view(&mut self);
+    /// Restore defaults and forget the saved session.
+    fn reset_defaults(&mut self);
+    fn toggle_fly_mode(&mut self);
+    /// Press or release a fly-mode thrust key; ignored outside fly mode.
+    fn fly_thrust(&mut self, direction: FlyDirection, pressed: bool);
+    fn save_bookmark(&mut self);
+    fn next_bookmark(&mut self);
+    fn toggle_tour(&mut self);
+    fn cycle_labels(&mut self);
+    fn toggle_orbits(&mut self);
+    fn toggle_trails(&mut self);
+    fn toggle_hud(&mut self);
+    fn toggle_help(&mut self);
+}
+
+/// Apply `action` to `target`.
+pub fn dispatch(target: &mut impl ActionTarget, action: &Action) {
+    let time = match action {
+        Action::TogglePause => {
+            target.time().toggle_pause();
+            true
+        }
+        Action::SpeedUp => {
+            target.time().speed_up();
+            true
+        }
+        Action::SpeedDown => {
+            target.time().speed_down();
+            true
+        }
+        Action::ResetSpeed => {
+            let time = target.time();
+            time.set_speed(DEFAULT_DAYS_PER_SECOND);
+            time.paused = false;
+            true
+        }
+        Action::ResetDefaults => {
+            target.reset_defaults();
+            true
+        }
+        Action::Recenter => {
+            target.recenter();
+            false
+        }
+        Action::TopDownView => {
+            target.top_down_view();
+            false
+        }
+        Action::ToggleFlyMode => {
+            target.toggle_fly_mode();
+            false
+        }
+        Action::Fly(direction) => {
+            target.fly_thrust(*direction, true);
+            false
+        }
+        Action::SaveBookmark => {
+            target.save_bookmark();
+            false
+        }
+        Action::NextBookmark => {
+            target.next_bookmark();
+            false
+        }
+        Action::ToggleTour => {
+            target.toggle_tour();
+            false
+        }
+        Action::SelectBody(name) => {
+            target.select_body(name);
+            false
+        }
+        Action::Deselect => {
+            target.deselect();
+            false
+        }
+        Action::ClearMeasurement => {
+            target.clear_measurement();
+            false
+        }
+        Action::ToggleHud => {
+            target.toggle_hud();
+            false
+        }
+        Action::CycleLabels => {
+            target.cycle_labels();
+            false
+        }
+        Action::ToggleOrbits => {
+            target.toggle_orbits();
+            false
+        }
+        Action::ToggleTrails => {
+            target.toggle_trails();
+            false
+        }
+        Action::ToggleHelp => {
+            target.toggle_help();
+            false
+        }
+    };
+    if time {
+        target.time_changed();
+    }
+}
+
+/// End a held action when its key goes up; other actions ignore key-up.
+pub fn release(target: &mut impl ActionTarget, action: &Action) {
+    if let Action::Fly(direction) = action {
+        target.fly_thrust(*direction, false);
+    }
+}
@@ -0,0 +1,81 @@
+//! Gamepad polling for fly mode.
+//!
+//! The Gamepad API has no events for stick movement, so the first connected
+//! pad is polled once per frame from the render loop. [`read`] maps the raw
+//! axes and buttons of the W3C "standard" layout to fly input and is kept
+//! free of DOM types so it can be tested natively.
+
+use glam::{Vec2, Vec3};
+use wasm_bindgen::JsCast;
+
+use crate::constants::{FLY_GAMEPAD_LOOK_RATE, GAMEPAD_DEADZONE};
+use crate::renderer::fly_camera::FlyInput;
+
+/// Standard-layout button indices.
+const BUTTON_TOGGLE_FLY: usize = 3; // Y / △
+const BUTTON_DESCEND: usize = 6; // left trigger
+const BUTTON_ASCEND: usize = 7; // right trigger
+
+/// One frame of gamepad state.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadReading {
+    pub input: FlyInput,
+    /// Whether the fly-mode toggle button is held (edge detection is up to the caller).
+    pub toggle_fly: bool,
+}
+
+/// Apply a radial deadzone, rescaling the remainder back to `0..=1`.
+pub fn apply_deadzone(stick: Vec2) -> Vec2 {
+    let length = stick.length();
+    if length <= GAMEPAD_DEADZONE {
+        return Vec2::ZERO;
+    }
+    let scaled = ((length - GAMEPAD_DEADZONE) / (1.0 - GAMEPAD_DEADZONE)).min(1.0);
+    stick * (scaled / length)
+}
+
+/// Map standard-layout `axes` and button `values` to fly input.
+///
+/// Left stick moves, right stick looks, triggers climb and descend.
+pub fn read(axes: &[f32], buttons: &[f32]) -> GamepadReading {
+    let axis = |i: usize| axes.get(i).copied().unwrap_or(0.0);
+    let button = |i: usize| buttons.get(i).copied().unwrap_or(0.0);
+
+    let left = apply_deadzone(Vec2::new(axis(0), axis(1)));
+    let right = apply_deadzone(Vec2::new(axis(2), axis(3)));
+
+    GamepadReading {
+        input: FlyInput {
+            // Stick Y axes point down, so pushing forward is negative.
+            thrust: Vec3::new(
+                left.x,
+                button(BUTTON_ASCEND) - button(BUTTON_DESCEND),
+                -left.y,
+            ),
+            look_rate: right * FLY_GAMEPAD_LOOK_RATE,
+        },
+        toggle_fly: button(BUTTON_TOGGLE_FLY) > 0.5,
+    }
+}
+
+/// Poll the first connected gamepad with the standard mapping, if any.
+pub fn poll() -> Option<GamepadReading> {
+    let pads = web_sys::window()?.navigator().get_gamepads().ok()?;
+    let pad = pads
+        .iter()
+        .filter_map(|p| p.dyn_into::<web_sys::Gamepad>().ok())
+        .find(|p| p.connected() && p.mapping() == web_sys::GamepadMappingType::Standard)?;
+
+    let axes: Vec<f32> = pad
+        .axes()
+        .iter()
+        .map(|a| a.as_f64().unwrap_or(0.0) as f32)
+        .collect();
+    let buttons: Vec<f32> = pad
+        .buttons()
+        .iter()
+        .filter_map(|b| b.dyn_into::<web_sys::GamepadButton>().ok())
+        .map(|b| b.value() as f32)
+        .collect();
+    Some(read(&axes, &buttons))
+}
@@ -0,0 +1,261 @@
+//! Touch gesture recognition.
+//!
+//! A pure state machine fed with the full set of touches on the canvas after
+//! every touch event (start, move, end and cancel all look the same to it),
+//! plus the frame time, so it can be tested without a browser. It turns
+//! them into [`Gesture`]s:
+//!
+//! - one finger: tap, double-tap, long-press, or a drag once it moves
+//!   further than [`TAP_SLOP_PX`];
+//! - two fingers: pan (midpoint motion), pinch (spread) and twist
+//!   (rotation), each once it has moved the fingers further than
+//!   [`TAP_SLOP_PX`] since they went down, so the jitter of a pinch does
+//!   not also pan or turn the view.
+//!
+//! Lifting one of two fingers continues as a one-finger drag; a tap is only
+//! recognised when a single finger goes down and up without travelling.
+
+use glam::Vec2;
+
+use super::hover::Pointer;
+use crate::constants::{DOUBLE_TAP_SECONDS, DOUBLE_TAP_SLOP_PX, LONG_PRESS_SECONDS, TAP_SLOP_PX};
+
+/// One touch on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    /// `Touch.identifier`, stable while the finger stays down.
+    pub id: i32,
+    /// Position in CSS pixels from the canvas's top-left.
+    pub position: Vec2,
+}
+
+/// What the fingers did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap(Pointer),
+    /// Second tap close to the first, soon after it (the first one was
+    /// reported as a [`Gesture::Tap`]).
+    DoubleTap(Pointer),
+    /// One finger held still for [`LONG_PRESS_SECONDS`].
+    LongPress(Pointer),
+    /// One finger moved by this many CSS pixels.
+    Drag(Vec2),
+    /// The midpoint of two fingers moved by this many CSS pixels.
+    Pan(Vec2),
+    /// Two fingers spread apart by this many CSS pixels (negative: pinched in).
+    Pinch(f32),
+    /// Two fingers turned by this many radians, clockwise on screen.
+    Twist(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum State {
+    #[default]
+    Idle,
+    /// One finger down that has not moved beyond the tap slop yet.
+    Pressing { id: i32, start: Vec2, since: f64 },
+    /// One finger dragging.
+    Dragging { id: i32, last: Vec2 },
+    /// The first two fingers down, where they went down and at their last
+    /// positions.
+    TwoFingers {
+        ids: [i32; 2],
+        start: [Vec2; 2],
+        points: [Vec2; 2],
+        active: TwoFingerGestures,
+    },
+    /// Finished (long-press fired); waits for every finger to lift.
+    Spent,
+}
+
+/// Which two-finger gestures have passed the slop since the fingers went
+/// down.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct TwoFingerGestures {
+    pan: bool,
+    pinch: bool,
+    twist: bool,
+}
+
+/// Gesture state of one canvas.
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    state: State,
+    /// Seconds of frame time seen so far.
+    clock: f64,
+    /// Time and position of the last single tap, for double-taps.
+    last_tap: Option<(f64, Vec2)>,
+    /// Canvas size in CSS pixels, for the pointers of taps.
+    canvas: Vec2,
+}
+
+impl GestureRecognizer {
+    /// The touches on a `canvas`-sized canvas changed to `touches`.
+    pub fn touches(&mut self, touches: &[TouchPoint], canvas: Vec2, mut emit: impl FnMut(Gesture)) {
+        self.canvas = canvas;
+        self.state = match touches {
+            [] => {
+                if let State::Pressing { start, since, .. } = self.state {
+                    emit(self.release(start, since));
+                }
+                State::Idle
+            }
+            [touch] => match self.state {
+                State::Idle => State::Pressing {
+                    id: touch.id,
+                    start: touch.position,
+                    since: self.clock,
+                },
+                State::Pressing { id, start, .. }
+                    if id == touch.id && touch.position.distance(start) > TAP_SLOP_PX =>
+                {
+                    emit(Gesture::Drag(touch.position - start));
+                    State::Dragging {
+                        id,
+                        last: touch.position,
+                    }
+                }
+                State::Pressing { id, .. } if id == touch.id => self.state,
+                State::Dragging { id, last } if id == touch.id => {
+                    let delta = touch.position - last;
+                    if delta != Vec2::ZERO {
+                        emit(Gesture::Drag(delta));
+                    }
+                    State::Dragging {
+                        id,
+                        last: touch.position,
+                    }
+                }
+                State::Spent => State::Spent,
+                // Another finger is left over: carry on dragging with it.
+                _ => State::Dragging {
+                    id: touch.id,
+                    last: touch.position,
+                },
+            },
+            [a, b, ..] => {
+                let points = [a.position, b.position];
+                let (start, active) = match self.state {
+                    State::TwoFingers {
+                        ids,
+                        start,
+                        points: previous,
+                        mut active,
+                    } if ids == [a.id, b.id] => {
+                        two_finger_gestures(start, previous, points, &mut active, &mut emit);
+                        (start, active)
+                    }
+                    _ => (points, TwoFingerGestures::default()),
+                };
+                State::TwoFingers {
+                    ids: [a.id, b.id],
+                    start,
+                    points,
+                    active,
+                }
+            }
+        };
+    }
+
+    /// The browser cancelled the touches: drop the gesture without a word.
+    pub fn cancel(&mut self) {
+        self.state = State::Idle;
+    }
+
+    /// Advance by `dt` seconds of frame time; a finger held still long
+    /// enough becomes a long-press.
+    pub fn update(&mut self, dt: f32, mut emit: impl FnMut(Gesture)) {
+        self.clock += dt as f64;
+        if let State::Pressing { start, since, .. } = self.state
+            && self.clock - since >= LONG_PRESS_SECONDS as f64
+        {
+            emit(Gesture::LongPress(self.pointer(start)));
+            self.state = State::Spent;
+        }
+    }
+
+    /// Gesture for a finger lifted at `start` without travelling, pressed
+    /// since `since`.
+    fn release(&mut self, start: Vec2, since: f64) -> Gesture {
+        let pointer = self.pointer(start);
+        if self.clock - since >= LONG_PRESS_SECONDS as f64 {
+            return Gesture::LongPress(pointer);
+        }
+        if let Some((time, position)) = self.last_tap
+            && self.clock - time <= DOUBLE_TAP_SECONDS as f64
+            && position.distance(start) <= DOUBLE_TAP_SLOP_PX
+        {
+            self.last_tap = None;
+            return Gesture::DoubleTap(pointer);
+        }
+        self.last_tap = Some((self.clock, start));
+        Gesture::Tap(pointer)
+    }
+
+    fn pointer(&self, position: Vec2) -> Pointer {
+        Pointer {
+            x: position.x,
+            y: position.y,
+            width: self.canvas.x,
+            height: self.canvas.y,
+        }
+    }
+}
+
+/// Pan, pinch and twist between two frames of a two-finger gesture that
+/// started at `start`; only the non-zero ones are emitted.
+///
+/// A gesture stays silent until its motion since `start` passes
+/// [`TAP_SLOP_PX`]; it then reports everything since `start` at once, and
+/// every frame's change from `previous` after that.
+fn two_finger_gestures(
+    start: [Vec2; 2],
+    previous: [Vec2; 2],
+    current: [Vec2; 2],
+    active: &mut TwoFingerGestures,
+    emit: &mut impl FnMut(Gesture),
+) {
+    // The frame to measure from, if the gesture is (now) active.
+    let since = |active: &mut bool, travelled: f32| {
+        if *active {
+            Some(previous)
+        } else if travelled > TAP_SLOP_PX {
+            *active = true;
+            Some(start)
+        } else {
+            None
+        }
+    };
+    let midpoint = |[a, b]: [Vec2; 2]| (a + b) * 0.5;
+    let span = |[a, b]: [Vec2; 2]| b - a;
+    // Screen y points down, so a positive angle turns clockwise.
+    let angle = |from: [Vec2; 2]| {
+        let angle = span(from).angle_to(span(current));
+        if angle.is_finite() { angle } else { 0.0 }
+    };
+
+    let travelled = midpoint(current).distance(midpoint(start));
+    if let Some(from) = since(&mut active.pan, travelled) {
+        let pan = midpoint(current) - midpoint(from);
+        if pan != Vec2::ZERO {
+            emit(Gesture::Pan(pan));
+        }
+    }
+
+    let spread = |from: [Vec2; 2]| span(current).length() - span(from).length();
+    if let Some(from) = since(&mut active.pinch, spread(start).abs()) {
+        let spread = spread(from);
+        if spread != 0.0 {
+            emit(Gesture::Pinch(spread));
+        }
+    }
+
+    // How far each finger has gone round the midpoint.
+    let arc = angle(start).abs() * span(current).length() * 0.5;
+    if let Some(from) = since(&mut active.twist, arc) {
+        let twist = angle(from);
+        if twist != 0.0 {
+            emit(Gesture::Twist(twist));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Pointer hover: which body is under the mouse.
+//!
+//! Mouse moves only record where the pointer is; the hit test itself runs
+//! from the frame loop at most every [`HOVER_PICK_INTERVAL_SECONDS`], so a
+//! burst of events costs one ray cast. Picking keeps going while the pointer
+//! rests, because the bodies move under it.
+
+use crate::constants::HOVER_PICK_INTERVAL_SECONDS;
+
+/// Pointer position over the canvas, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pointer {
+    pub x: f32,
+    pub y: f32,
+    /// Canvas size in CSS pixels at the time of the move.
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Hover state of one viewer.
+#[derive(Debug, Default)]
+pub struct Hover {
+    /// Where the pointer rests, while it is over the canvas and not dragging.
+    pointer: Option<Pointer>,
+    /// Seconds since the last pick.
+    elapsed: f32,
+    /// Body under the pointer at the last pick.
+    body: Option<usize>,
+}
+
+impl Hover {
+    pub fn body(&self) -> Option<usize> {
+        self.body
+    }
+
+    /// The pointer moved to `pointer`.
+    pub fn pointer_moved(&mut self, pointer: Pointer) {
+        if self.pointer.is_none() {
+            // Pick on the next frame rather than waiting out the interval.
+            self.elapsed = HOVER_PICK_INTERVAL_SECONDS;
+        }
+        self.pointer = Some(pointer);
+    }
+
+    /// The pointer left the canvas or started a drag: nothing is hovered.
+    pub fn pointer_left(&mut self) {
+        self.pointer = None;
+        self.body = None;
+    }
+
+    /// Advance by `dt` seconds; returns the pointer to hit-test when a pick
+    /// is due.
+    pub fn due(&mut self, dt: f32) -> Option<Pointer> {
+        let pointer = self.pointer?;
+        self.elapsed += dt;
+        if self.elapsed < HOVER_PICK_INTERVAL_SECONDS {
+            return None;
+        }
+        self.elapsed = 0.0;
+        Some(pointer)
+    }
+
+    /// Record the result of a pick.
+    pub fn set_body(&mut self, body: Option<usize>) {
+        if self.pointer.is_some() {
+            self.body = body;
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Camera momentum: drags fling and wheel zoom glides.
+//!
+//! While a control is held (a mouse button or finger down), its input is
+//! applied to the camera immediately and the [`Momentum`] only watches it,
+//! keeping a smoothed velocity. Once released, the camera coasts on that
+//! velocity, which decays exponentially with the control's time constant.
+//! Each frame moves the camera by the exact integral of the decaying
+//! velocity over the frame, so a fling travels the same distance at 30 or
+//! 144 fps. Mouse wheels add velocity instead, sized so each notch still
+//! zooms by its full amount, just spread over a short glide.
+//!
+//! Trackpads already deliver their own momentum, so their scrolling is
+//! applied directly; [`WheelDevice::classify`] tells them apart.
+
+use std::ops::{Add, Mul};
+
+use glam::Vec2;
+
+use crate::constants::{
+    INERTIA_STOP_FRACTION, INERTIA_VELOCITY_WINDOW_SECONDS, PAN_INERTIA_SECONDS,
+    ROTATE_INERTIA_SECONDS, TRACKPAD_PINCH_ZOOM_GAIN, WHEEL_LINE_HEIGHT_PX, WHEEL_NOTCH_MIN_PX,
+    WHEEL_PAGE_HEIGHT_PX, ZOOM_INERTIA_SECONDS,
+};
+
+/// A control value momentum can carry: a scalar or a 2D delta.
+pub trait Velocity:
+    Copy + Default + PartialEq + Add<Output = Self> + Mul<f32, Output = Self>
+{
+    fn magnitude(self) -> f32;
+}
+
+impl Velocity for f32 {
+    fn magnitude(self) -> f32 {
+        self.abs()
+    }
+}
+
+impl Velocity for Vec2 {
+    fn magnitude(self) -> f32 {
+        self.length()
+    }
+}
+
+/// Decaying velocity of one camera control.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Momentum<V> {
+    /// Units per second.
+    velocity: V,
+    /// Input since the last update, while held.
+    pending: V,
+    held: bool,
+    /// Seconds for the velocity to decay by a factor of e.
+    time_constant: f32,
+    /// Speed below which coasting stops, reset to a fraction of the
+    /// release speed so slow and fast flings settle alike.
+    stop_speed: f32,
+}
+
+impl<V: Velocity> Momentum<V> {
+    pub fn new(time_constant: f32) -> Self {
+        Self {
+            velocity: V::default(),
+            pending: V::default(),
+            held: false,
+            time_constant,
+            stop_speed: 0.0,
+        }
+    }
+
+    /// The control was grabbed: stop coasting and start watching input.
+    pub fn grab(&mut self) {
+        if !self.held {
+            self.held = true;
+            self.velocity = V::default();
+            self.pending = V::default();
+        }
+    }
+
+    /// Input `delta`, already applied to the camera, while held.
+    pub fn track(&mut self, delta: V) {
+        if self.held {
+            self.pending = self.pending + delta;
+        }
+    }
+
+    /// The control was let go: coast on the recent velocity.
+    pub fn release(&mut self) {
+        if self.held {
+            self.held = false;
+            self.stop_speed = self.velocity.magnitude() * INERTIA_STOP_FRACTION;
+        }
+    }
+
+    /// Glide by `delta` in total, on top of any coasting (a wheel notch).
+    pub fn impulse(&mut self, delta: V) {
+        self.velocity = self.velocity + delta * (1.0 / self.time_constant);
+        self.stop_speed = self.velocity.magnitude() * INERTIA_STOP_FRACTION;
+    }
+
+    /// Drop all momentum.
+    pub fn stop(&mut self) {
+        self.velocity = V::default();
+        self.pending = V::default();
+    }
+
+    /// Advance `dt` seconds. Returns how far the camera coasts this frame
+    /// (nothing while held).
+    pub fn update(&mut self, dt: f32) -> V {
+        if dt <= 0.0 {
+            return V::default();
+        }
+        if self.held {
+            // Exponentially weighted average of the recent input speed.
+            let weight = 1.0 - (-dt / INERTIA_VELOCITY_WINDOW_SECONDS).exp();
+            let speed = self.pending * (1.0 / dt);
+            self.velocity = self.velocity * (1.0 - weight) + speed * weight;
+            self.pending = V::default();
+            return V::default();
+        }
+        if self.velocity == V::default() {
+            return V::default();
+        }
+        let decay = (-dt / self.time_constant).exp();
+        let step = self.velocity * (self.time_constant * (1.0 - decay));
+        self.velocity = self.velocity * decay;
+        if self.velocity.magnitude() <= self.stop_speed {
+            self.velocity = V::default();
+        }
+        step
+    }
+}
+
+/// Momentum of every orbit-camera control.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraMomentum {
+    /// Drag rotation, in drag pixels.
+    pub rotate: Momentum<Vec2>,
+    /// Zoom, in [`crate::renderer::camera::Camera::zoom`] units.
+    pub zoom: Momentum<f32>,
+    /// Target panning, in drag pixels.
+    pub pan: Momentum<Vec2>,
+}
+
+impl Default for CameraMomentum {
+    fn default() -> Self {
+        Self {
+            rotate: Momentum::new(ROTATE_INERTIA_SECONDS),
+            zoom: Momentum::new(ZOOM_INERTIA_SECONDS),
+            pan: Momentum::new(PAN_INERTIA_SECONDS),
+        }
+    }
+}
+
+impl CameraMomentum {
+    pub fn stop(&mut self) {
+        self.rotate.stop();
+        self.zoom.stop();
+        self.pan.stop();
+    }
+}
+
+// ── Wheel devices ──
+
+/// `WheelEvent.deltaMode` values.
+pub const DOM_DELTA_PIXEL: u32 = 0;
+pub const DOM_DELTA_LINE: u32 = 1;
+
+/// What produced a wheel event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WheelDevice {
+    /// A notched mouse wheel: coarse steps, smoothed into a glide.
+    Wheel,
+    /// A trackpad or smooth-scrolling device: fine steps with the
+    /// system's own momentum, applied as they come.
+    Trackpad,
+    /// A trackpad pinch, which browsers report as a Ctrl+wheel.
+    Pinch,
+}
+
+impl WheelDevice {
+    /// Guess the device from a wheel event. Line and page deltas only come
+    /// from wheels; in pixel mode, horizontal motion or fractional steps
+    /// smaller than a notch mean a trackpad.
+    pub fn classify(delta_mode: u32, delta_x: f32, delta_y: f32, ctrl_key: bool) -> Self {
+        if delta_mode != DOM_DELTA_PIXEL {
+            return WheelDevice::Wheel;
+        }
+        if ctrl_key {
+            WheelDevice::Pinch
+        } else if delta_x != 0.0 || delta_y.abs() < WHEEL_NOTCH_MIN_PX || delta_y.fract() != 0.0 {
+            WheelDevice::Trackpad
+        } else {
+            WheelDevice::Wheel
+        }
+    }
+
+    /// Whether this device's zoom glides rather than being applied directly.
+    pub fn glides(self) -> bool {
+        self == WheelDevice::Wheel
+    }
+
+    /// Zoom per pixel of wheel delta, relative to a mouse wheel.
+    pub fn zoom_gain(self) -> f32 {
+        match self {
+            WheelDevice::Pinch => TRACKPAD_PINCH_ZOOM_GAIN,
+            WheelDevice::Wheel | WheelDevice::Trackpad => 1.0,
+        }
+    }
+}
+
+/// A wheel `delta` in pixels, whatever its `delta_mode`.
+pub fn wheel_pixels(delta: f32, delta_mode: u32) -> f32 {
+    match delta_mode {
+        DOM_DELTA_PIXEL => delta,
+        DOM_DELTA_LINE => delta * WHEEL_LINE_HEIGHT_PX,
+        _ => delta * WHEEL_PAGE_HEIGHT_PX,
+    }
+}
@@ -1,40 +1,80 @@
 //! Input handling — mouse, touch, keyboard, and planet-selection events.
 //!
 //! All closures capture an `Rc<RefCell<AppState>>` and mutate the camera
-//! or input-tracking fields. Closures are leaked intentionally because
-//! they must live for the entire application lifetime.
+//! or input-tracking fields. They are registered with the viewer's
+//! [`EventListeners`], which removes them when the viewer is destroyed.
+//!
+//! Keyboard input goes through the remappable action map in [`actions`];
+//! gamepads are polled each frame through [`gamepad`]. Tab and Shift-Tab on
+//! the focused canvas step through the bodies (see [`crate::accessibility`]).
+//! Mouse moves feed [`hover`], which the frame loop hit-tests through
+//! [`update_hover`]. Touches go through the recogniser in [`gestures`] and
+//! end up in the same click, double-click and drag logic as the mouse.
+//! Right- or middle-dragging (or two fingers) pans the orbit target.
+//! Drags and wheel zooms carry on with the momentum in [`inertia`].
+
+pub mod actions;
+pub mod gamepad;
+pub mod gestures;
+pub mod hover;
+pub mod inertia;
 
 use std::cell::RefCell;
 use std::rc::Rc;
+
+use glam::{DVec3, Vec2, Vec3};
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlCanvasElement;
 
+use crate::accessibility::cycle_body;
+use crate::api::ApiEvent;
 use crate::app::AppState;
 use crate::constants::{
-    CAMERA_DISTANCE, CLICK_DRAG_THRESHOLD, DEFAULT_DAYS_PER_SECOND, PLANET_CLICK_RADIUS_FACTOR,
-    PLANET_ZOOM_FACTOR, TOUCH_ZOOM_MULTIPLIER,
+    CAMERA_DISTANCE, CLICK_DRAG_THRESHOLD, PLANET_CLICK_RADIUS_FACTOR, PLANET_ZOOM_FACTOR,
+    TOUCH_ZOOM_MULTIPLIER,
 };
-use crate::renderer::camera::Camera;
-use glam::Vec3;
-
-/// Attach all input event listeners to the given canvas.
-///
-/// Every closure is `.forget()`-ed so it lives as long as the page.
-/// This is safe because the app never needs to remove them.
-pub fn setup_input(canvas: &HtmlCanvasElement, state: Rc<RefCell<AppState>>) {
-    bind_mouse_events(canvas, &state);
-    bind_wheel_event(canvas, &state);
-    bind_touch_events(canvas, &state);
-    bind_keyboard_events(&state);
+use crate::lifecycle::EventListeners;
+use crate::renderer::camera::{Camera, relative_to_eye};
+use crate::renderer::fly_camera::FlyDirection;
+use crate::renderer::picking::PickTarget;
+use crate::simulation::time::SimulationTime;
+use crate::tour::{DEFAULT_TOUR, Tour};
+
+use actions::{Action, ActionTarget, dispatch, release};
+use gestures::{Gesture, TouchPoint};
+use hover::Pointer;
+use inertia::{WheelDevice, wheel_pixels};
+
+/// Attach all input event listeners to the given canvas; with
+/// `page_controls`, also to the document's keyboard and the page's HUD
+/// and tour events.
+pub fn setup_input(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<AppState>>,
+    page_controls: bool,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
+    bind_mouse_events(canvas, state, listeners)?;
+    bind_wheel_event(canvas, state, listeners)?;
+    bind_touch_events(canvas, state, listeners)?;
+    bind_focus_events(canvas, state, listeners)?;
+    if page_controls {
+        bind_keyboard_events(state, listeners)?;
+        bind_tour_event(state, listeners)?;
+    }
+    Ok(())
 }
 
 // ── Planet selection helpers ─────────────────────────────────────────────
 
 /// Cast a ray from the camera through `(mouse_x, mouse_y)` (in CSS pixels,
 /// relative to the canvas) and return the index of the nearest body hit, if any.
+///
+/// Works in camera-relative space, like the renderer: body positions must
+/// come from [`refresh_hit_spheres`].
 fn raycast_planets(
     camera: &Camera,
-    body_positions: &[(Vec3, f32)], // (position, display_radius)
+    body_positions: &[(Vec3, f32)], // (camera-relative position, display_radius)
     mouse_x: f32,
     mouse_y: f32,
     canvas_w: f32,
@@ -83,205 +123,252 @@ fn raycast_planets(
     nearest.map(|(i, _)| i)
 }
 
-/// Select a celestial body by index: animate the camera toward it and update
-/// the info panel.  Does nothing if `idx` is already selected.
-fn select_planet(state: &mut AppState, idx: usize) {
-    if state.selected_planet == Some(idx) {
+/// Refill `state.hit_spheres` with every body as a camera-relative hit
+/// sphere for [`raycast_planets`], reusing its allocation.
+fn refresh_hit_spheres(state: &mut AppState) {
+    let eye = state.renderer.camera.eye_position();
+    state.hit_spheres.clear();
+    state.hit_spheres.extend(
+        state
+            .simulation
+            .bodies
+            .iter()
+            .map(|b| (relative_to_eye(b.position, eye), b.display_radius)),
+    );
+}
+
+/// Index of the body whose hit sphere is under `pointer`, by ray cast.
+fn pick_body(state: &mut AppState, pointer: Pointer) -> Option<usize> {
+    refresh_hit_spheres(state);
+    raycast_planets(
+        &state.renderer.camera,
+        &state.hit_spheres,
+        pointer.x,
+        pointer.y,
+        pointer.width,
+        pointer.height,
+    )
+}
+
+/// Body under a click: whatever the GPU ID buffer shows within the pick
+/// radius (a body, or the planet of an orbit or trail), else the ray cast.
+fn click_body(state: &mut AppState, pointer: Pointer) -> Option<usize> {
+    let s = &mut *state;
+    s.renderer
+        .pick(
+            &s.simulation.bodies,
+            &s.i18n,
+            Vec2::new(pointer.x, pointer.y),
+            Vec2::new(pointer.width, pointer.height),
+        )
+        .map(PickTarget::body)
+        .or_else(|| pick_body(state, pointer))
+}
+
+/// Select the body at `pointer`, or leave the selection on empty space.
+/// With `measure` (Shift-click), add or remove the body from the measuring
+/// tool instead; empty space clears the measurement.
+fn click(state: &mut AppState, pointer: Pointer, measure: bool) {
+    let hit = click_body(state, pointer);
+    if measure {
+        match hit {
+            Some(idx) => state.renderer.measurement.toggle(idx),
+            None => state.renderer.measurement.clear(),
+        }
+        return;
+    }
+    match hit {
+        Some(idx) => select_planet(state, idx),
+        // Clicking empty space while flying should not end the flight.
+        None if state.fly.is_some() => {}
+        None => deselect_all(state),
+    }
+}
+
+/// Toggle the camera lock on the selected body, or select and lock the
+/// body at `pointer` if none is selected.
+fn double_click(state: &mut AppState, pointer: Pointer) {
+    if state.selected_planet.is_some() {
+        toggle_camera_lock(state);
+    } else if let Some(idx) = click_body(state, pointer) {
+        select_planet(state, idx);
+        toggle_camera_lock(state);
+    }
+}
+
+/// Pointer position of a mouse event on `canvas`.
+fn event_pointer(e: &web_sys::MouseEvent, canvas: &HtmlCanvasElement) -> Pointer {
+    Pointer {
+        x: e.offset_x() as f32,
+        y: e.offset_y() as f32,
+        width: canvas.client_width() as f32,
+        height: canvas.client_height() as f32,
+    }
+}
+
+/// Hit-test the hovered body when a pick is due (called once per frame) and
+/// hand it to the renderer's highlight.
+pub(crate) fn update_hover(state: &mut AppState, dt: f32) {
+    if let Some(pointer) = state.hover.due(dt) {
+        let body = pick_body(state, pointer);
+        state.hover.set_body(body);
+    }
+    state.renderer.hovered = state.hover.body();
+}
+
+/// Select a celestial body by index: animate the camera toward it and report
+/// the selection.  Does nothing if `idx` is already selected, unless the
+/// camera is in fly mode (which this leaves).
+pub(crate) fn select_planet(state: &mut AppState, idx: usize) {
+    if state.selected_planet == Some(idx) && state.fly.is_none() {
         return;
     }
     if idx >= state.simulation.bodies.len() {
         return;
     }
+    state.set_fly_mode(false);
 
-    // Extract all data we need before mutating (avoids split-borrow issues).
-    let (name, radius_km, dist_au, period_days, incl_rad, is_star, display_r, body_pos) = {
+    let (name, display_r, body_pos) = {
         let b = &state.simulation.bodies[idx];
-        (
-            b.name,
-            b.real_radius_km,
-            b.semi_major_axis_au,
-            b.orbital_period_days,
-            b.inclination_rad,
-            b.is_star,
-            b.display_radius,
-            b.position,
-        )
+        (b.id, b.display_radius, b.position)
     };
 
     let zoom_dist = (display_r * PLANET_ZOOM_FACTOR).max(state.renderer.camera.min_distance * 1.5);
     state.renderer.camera.set_target(body_pos, zoom_dist);
 
-    // Changing selection clears any existing camera lock.
+    // Changing selection clears any existing camera lock and pan.
     state.camera_locked = false;
+    state.pan_offset = DVec3::ZERO;
     state.selected_planet = Some(idx);
-
-    show_planet_panel(
-        name,
-        radius_km,
-        dist_au,
-        period_days,
-        incl_rad,
-        is_star,
-        false,
-    );
+    state.emit(ApiEvent::Select(name.to_string()));
 }
 
 /// Deselect the current body and return the camera to the overview.
-fn deselect_all(state: &mut AppState) {
-    state.selected_planet = None;
+pub(crate) fn deselect_all(state: &mut AppState) {
+    state.set_fly_mode(false);
+    state.stop_tour();
+    if state.selected_planet.take().is_some() {
+        state.emit(ApiEvent::Deselect);
+    }
     state.camera_locked = false;
-    state
-        .renderer
-        .camera
-        .set_target(Vec3::ZERO, CAMERA_DISTANCE);
-    hide_planet_panel();
+    state.pan_offset = DVec3::ZERO;
+    // The Sun drifts with the galaxy, so the overview centres on where it is.
+    let sun = state.sun_position();
+    state.renderer.camera.set_target(sun, CAMERA_DISTANCE);
 }
 
-/// Toggle the camera-lock on the currently selected planet.
-fn toggle_camera_lock(state: &mut AppState) {
-    if state.selected_planet.is_none() {
-        return;
+/// Select body `idx` and lock the camera onto it (used by bookmarks and tours).
+pub(crate) fn focus_body(state: &mut AppState, idx: usize) {
+    select_planet(state, idx);
+    if !state.camera_locked {
+        toggle_camera_lock(state);
     }
-    state.camera_locked = !state.camera_locked;
-    update_planet_panel_lock(state.camera_locked);
 }
 
-// ── DOM helpers ──────────────────────────────────────────────────────────
-
-fn show_planet_panel(
-    name: &str,
-    radius_km: f64,
-    dist_au: f64,
-    period_days: f64,
-    inclination_rad: f64,
-    is_star: bool,
-    locked: bool,
-) {
-    let Some(doc) = web_sys::window().and_then(|w| w.document()) else {
+/// Toggle the camera-lock on the currently selected planet.
+pub(crate) fn toggle_camera_lock(state: &mut AppState) {
+    if state.selected_planet.is_none() {
         return;
-    };
-
-    let set = |id: &str, html: &str| {
-        if let Some(el) = doc.get_element_by_id(id) {
-            el.set_inner_html(html);
-        }
-    };
-
-    set("planet-name", name);
-    set("planet-radius", &format!("{radius_km:.0} km"));
-
-    if is_star {
-        set("planet-distance", "Center of system");
-        set("planet-period", "—");
-    } else {
-        set("planet-distance", &format!("{dist_au:.3} AU"));
-        set("planet-period", &format!("{period_days:.1} days"));
-    }
-
-    set(
-        "planet-inclination",
-        &format!("{:.2}°", inclination_rad.to_degrees()),
-    );
-    set(
-        "planet-lock-hint",
-        if locked {
-            "🔒 Locked · DOUBLE-CLICK to unlock"
-        } else {
-            "DOUBLE-CLICK to lock camera"
-        },
-    );
-
-    if let Some(panel) = doc.get_element_by_id("planet-info") {
-        let _ = panel.class_list().remove_1("hidden");
-    }
-}
-
-fn hide_planet_panel() {
-    if let Some(panel) = web_sys::window()
-        .and_then(|w| w.document())
-        .and_then(|d| d.get_element_by_id("planet-info"))
-    {
-        let _ = panel.class_list().add_1("hidden");
-    }
-}
-
-fn update_planet_panel_lock(locked: bool) {
-    if let Some(el) = web_sys::window()
-        .and_then(|w| w.document())
-        .and_then(|d| d.get_element_by_id("planet-lock-hint"))
-    {
-        el.set_inner_html(if locked {
-            "🔒 Locked · DOUBLE-CLICK to unlock"
-        } else {
-            "DOUBLE-CLICK to lock camera"
-        });
     }
+    state.camera_locked = !state.camera_locked;
+    // Locking centres the body again.
+    state.pan_offset = DVec3::ZERO;
 }
 
 // ── Mouse ────────────────────────────────────────────────────────────────
 
-fn bind_mouse_events(canvas: &HtmlCanvasElement, state: &Rc<RefCell<AppState>>) {
+fn bind_mouse_events(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
     // Mouse down
     {
         let state = Rc::clone(state);
         let closure = Closure::wrap(Box::new(move |e: web_sys::MouseEvent| {
+            // Right and middle buttons pan; keep the middle one from
+            // starting the browser's autoscroll.
+            let panning = matches!(e.button(), 1 | 2);
+            if e.button() == 1 {
+                e.prevent_default();
+            }
             let mut s = state.borrow_mut();
             s.mouse_down = true;
+            s.mouse_panning = panning;
             s.last_mouse_x = e.client_x() as f32;
             s.last_mouse_y = e.client_y() as f32;
             s.mouse_drag_distance = 0.0;
+            if panning {
+                s.momentum.pan.grab();
+            } else {
+                s.momentum.rotate.grab();
+            }
+            // No highlight while dragging the view.
+            s.hover.pointer_left();
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("mousedown", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind mousedown listener");
-        closure.forget();
+        listeners.add(canvas, "mousedown", closure)?;
     }
 
     // Mouse up
     {
         let state = Rc::clone(state);
         let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
-            state.borrow_mut().mouse_down = false;
+            let mut s = state.borrow_mut();
+            s.mouse_down = false;
+            s.momentum.rotate.release();
+            s.momentum.pan.release();
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("mouseup", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind mouseup listener");
-        closure.forget();
+        listeners.add(canvas, "mouseup", closure)?;
     }
 
-    // Mouse move
+    // Mouse move — drag the view, or record the pointer for hover picking.
     {
         let state = Rc::clone(state);
+        let canvas_move = canvas.clone();
         let closure = Closure::wrap(Box::new(move |e: web_sys::MouseEvent| {
             let mut s = state.borrow_mut();
             if s.mouse_down {
                 let dx = e.client_x() as f32 - s.last_mouse_x;
                 let dy = e.client_y() as f32 - s.last_mouse_y;
                 s.mouse_drag_distance += dx.hypot(dy);
-                s.renderer.camera.rotate(dx, dy);
+                if s.mouse_panning {
+                    s.pan_view(dx, dy);
+                    s.momentum.pan.track(Vec2::new(dx, dy));
+                } else {
+                    s.drag_view(dx, dy);
+                    s.momentum.rotate.track(Vec2::new(dx, dy));
+                }
+            } else {
+                s.hover.pointer_moved(event_pointer(&e, &canvas_move));
             }
             s.last_mouse_x = e.client_x() as f32;
             s.last_mouse_y = e.client_y() as f32;
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("mousemove", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind mousemove listener");
-        closure.forget();
+        listeners.add(canvas, "mousemove", closure)?;
     }
 
-    // Mouse leave — stop dragging if the pointer exits the canvas.
+    // Mouse leave — stop dragging and hovering if the pointer exits the canvas.
     {
         let state = Rc::clone(state);
         let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
-            state.borrow_mut().mouse_down = false;
+            let mut s = state.borrow_mut();
+            s.mouse_down = false;
+            s.momentum.rotate.release();
+            s.momentum.pan.release();
+            s.hover.pointer_left();
+        }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+        listeners.add(canvas, "mouseleave", closure)?;
+    }
+
+    // Context menu — the right button pans instead.
+    {
+        let closure = Closure::wrap(Box::new(move |e: web_sys::MouseEvent| {
+            e.prevent_default();
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("mouseleave", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind mouseleave listener");
-        closure.forget();
+        listeners.add(canvas, "contextmenu", closure)?;
     }
 
-    // Click — raycasting to select a planet
+    // Click — select the body under the pointer (Shift: measure it)
     {
         let state = Rc::clone(state);
         let canvas_click = canvas.clone();
@@ -292,31 +379,10 @@ fn bind_mouse_events(canvas: &HtmlCanvasElement, state: &Rc<RefCell<AppState>>)
                 return;
             }
 
-            let x = e.offset_x() as f32;
-            let y = e.offset_y() as f32;
-            let w = canvas_click.client_width() as f32;
-            let h = canvas_click.client_height() as f32;
-
-            // Snapshot body positions to avoid a split-borrow on `s`.
-            let body_data: Vec<(Vec3, f32)> = s
-                .simulation
-                .bodies
-                .iter()
-                .map(|b| (b.position, b.display_radius))
-                .collect();
-
-            let hit = raycast_planets(&s.renderer.camera, &body_data, x, y, w, h);
-
-            match hit {
-                Some(idx) => select_planet(&mut s, idx),
-                None => deselect_all(&mut s),
-            }
+            click(&mut s, event_pointer(&e, &canvas_click), e.shift_key());
             s.mouse_drag_distance = 0.0;
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind click listener");
-        closure.forget();
+        listeners.add(canvas, "click", closure)?;
     }
 
     // Double-click — toggle camera lock on selected planet
@@ -324,241 +390,377 @@ fn bind_mouse_events(canvas: &HtmlCanvasElement, state: &Rc<RefCell<AppState>>)
         let state = Rc::clone(state);
         let canvas_dbl = canvas.clone();
         let closure = Closure::wrap(Box::new(move |e: web_sys::MouseEvent| {
-            let mut s = state.borrow_mut();
-
-            if s.selected_planet.is_some() {
-                // Toggle lock on the already-selected planet.
-                toggle_camera_lock(&mut s);
-            } else {
-                // Nothing selected yet — try to select and immediately lock.
-                let x = e.offset_x() as f32;
-                let y = e.offset_y() as f32;
-                let w = canvas_dbl.client_width() as f32;
-                let h = canvas_dbl.client_height() as f32;
-
-                let body_data: Vec<(Vec3, f32)> = s
-                    .simulation
-                    .bodies
-                    .iter()
-                    .map(|b| (b.position, b.display_radius))
-                    .collect();
-
-                if let Some(idx) = raycast_planets(&s.renderer.camera, &body_data, x, y, w, h) {
-                    select_planet(&mut s, idx);
-                    toggle_camera_lock(&mut s);
-                }
-            }
+            double_click(&mut state.borrow_mut(), event_pointer(&e, &canvas_dbl));
         }) as Box<dyn FnMut(web_sys::MouseEvent)>);
-        canvas
-            .add_event_listener_with_callback("dblclick", closure.as_ref().unchecked_ref())
-            .expect("Failed to bind dblclick listener");
-        closure.forget();
+        listeners.add(canvas, "dblclick", closure)?;
     }
+    Ok(())
 }
 
 // ── Scroll wheel ─────────────────────────────────────────────────────────
 
-fn bind_wheel_event(canvas: &HtmlCanvasElement, state: &Rc<RefCell<AppState>>) {
+fn bind_wheel_event(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
     let state = Rc::clone(state);
     let closure = Closure::wrap(Box::new(move |e: web_sys::WheelEvent| {
         e.prevent_default();
-        state.borrow_mut().renderer.camera.zoom(e.delta_y() as f32);
+        let mode = e.delta_mode();
+        let device =
+            WheelDevice::classify(mode, e.delta_x() as f32, e.delta_y() as f32, e.ctrl_key());
+        let delta = wheel_pixels(e.delta_y() as f32, mode) * device.zoom_gain();
+        let mut s = state.borrow_mut();
+        if device.glides() && !s.renderer.camera.reduced_motion {
+            s.momentum.zoom.impulse(delta);
+        } else {
+            s.renderer.camera.zoom(delta);
+        }
     }) as Box<dyn FnMut(web_sys::WheelEvent)>);
 
     let opts = web_sys::AddEventListenerOptions::new();
     opts.set_passive(false);
-    canvas
-        .add_event_listener_with_callback_and_add_event_listener_options(
-            "wheel",
-            closure.as_ref().unchecked_ref(),
-            &opts,
-        )
-        .expect("Failed to bind wheel listener");
-    closure.forget();
+    listeners.add_with_options(canvas, "wheel", closure, &opts)
 }
 
 // ── Touch ────────────────────────────────────────────────────────────────
 
-fn bind_touch_events(canvas: &HtmlCanvasElement, state: &Rc<RefCell<AppState>>) {
+fn bind_touch_events(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
     let touch_opts = || {
         let o = web_sys::AddEventListenerOptions::new();
         o.set_passive(false);
         o
     };
 
-    // Touch start
-    {
+    // Every change to the touches goes to the gesture recogniser. Cancelling
+    // the default keeps the page from scrolling and the browser from
+    // synthesising mouse events and clicks.
+    for event in ["touchstart", "touchmove", "touchend"] {
         let state = Rc::clone(state);
+        let canvas_touch = canvas.clone();
         let closure = Closure::wrap(Box::new(move |e: web_sys::TouchEvent| {
             e.prevent_default();
-            let mut s = state.borrow_mut();
-            let touches = e.touches();
-            if touches.length() == 1 {
-                if let Some(t) = touches.get(0) {
-                    s.last_touch_x = t.client_x() as f32;
-                    s.last_touch_y = t.client_y() as f32;
-                }
-                s.touch_distance = None;
-            } else if touches.length() == 2
-                && let (Some(t0), Some(t1)) = (touches.get(0), touches.get(1))
-            {
-                let dx = (t1.client_x() - t0.client_x()) as f32;
-                let dy = (t1.client_y() - t0.client_y()) as f32;
-                s.touch_distance = Some((dx * dx + dy * dy).sqrt());
-            }
+            handle_touches(&mut state.borrow_mut(), &e, &canvas_touch);
         }) as Box<dyn FnMut(web_sys::TouchEvent)>);
-        canvas
-            .add_event_listener_with_callback_and_add_event_listener_options(
-                "touchstart",
-                closure.as_ref().unchecked_ref(),
-                &touch_opts(),
-            )
-            .expect("Failed to bind touchstart listener");
-        closure.forget();
+        listeners.add_with_options(canvas, event, closure, &touch_opts())?;
     }
 
-    // Touch move
+    // Touch cancel — the browser took the touches over.
     {
         let state = Rc::clone(state);
-        let closure = Closure::wrap(Box::new(move |e: web_sys::TouchEvent| {
-            e.prevent_default();
+        let closure = Closure::wrap(Box::new(move |_: web_sys::TouchEvent| {
             let mut s = state.borrow_mut();
-            let touches = e.touches();
-            if touches.length() == 1 {
-                if let Some(t) = touches.get(0) {
-                    let x = t.client_x() as f32;
-                    let y = t.client_y() as f32;
-                    let dx = x - s.last_touch_x;
-                    let dy = y - s.last_touch_y;
-                    s.renderer.camera.rotate(dx, dy);
-                    s.last_touch_x = x;
-                    s.last_touch_y = y;
-                }
-            } else if touches.length() == 2
-                && let (Some(t0), Some(t1)) = (touches.get(0), touches.get(1))
-            {
-                let dx = (t1.client_x() - t0.client_x()) as f32;
-                let dy = (t1.client_y() - t0.client_y()) as f32;
-                let new_dist = (dx * dx + dy * dy).sqrt();
-                if let Some(old_dist) = s.touch_distance {
-                    let delta = old_dist - new_dist;
-                    s.renderer.camera.zoom(delta * TOUCH_ZOOM_MULTIPLIER);
-                }
-                s.touch_distance = Some(new_dist);
-            }
+            s.gestures.cancel();
+            s.momentum.rotate.release();
+            s.momentum.zoom.release();
+            s.momentum.pan.release();
+            s.momentum.stop();
         }) as Box<dyn FnMut(web_sys::TouchEvent)>);
-        canvas
-            .add_event_listener_with_callback_and_add_event_listener_options(
-                "touchmove",
-                closure.as_ref().unchecked_ref(),
-                &touch_opts(),
-            )
-            .expect("Failed to bind touchmove listener");
-        closure.forget();
+        listeners.add_with_options(canvas, "touchcancel", closure, &touch_opts())?;
     }
+    Ok(())
 }
 
-// ── Keyboard ─────────────────────────────────────────────────────────────
+/// Feed the first two touches of `e` to the gesture recogniser and act on
+/// what it recognises.
+fn handle_touches(state: &mut AppState, e: &web_sys::TouchEvent, canvas: &HtmlCanvasElement) {
+    let rect = canvas.get_bounding_client_rect();
+    let origin = Vec2::new(rect.left() as f32, rect.top() as f32);
+    let list = e.touches();
+    let mut points = [TouchPoint {
+        id: 0,
+        position: Vec2::ZERO,
+    }; 2];
+    let mut count = 0;
+    for touch in (0..list.length()).filter_map(|i| list.get(i)).take(points.len()) {
+        let client = Vec2::new(touch.client_x() as f32, touch.client_y() as f32);
+        points[count] = TouchPoint {
+            id: touch.identifier(),
+            position: client - origin,
+        };
+        count += 1;
+    }
+    let size = Vec2::new(canvas.client_width() as f32, canvas.client_height() as f32);
+
+    // Fingers on the glass hold the camera; lifting the last one flings it.
+    if count > 0 {
+        state.momentum.rotate.grab();
+        state.momentum.zoom.grab();
+        state.momentum.pan.grab();
+    } else {
+        state.momentum.rotate.release();
+        state.momentum.zoom.release();
+        state.momentum.pan.release();
+    }
 
-fn bind_keyboard_events(state: &Rc<RefCell<AppState>>) {
+    // Gesture handlers need the whole state, the recogniser included.
+    let mut gestures = std::mem::take(&mut state.gestures);
+    gestures.touches(&points[..count], size, |gesture| apply_gesture(state, gesture));
+    state.gestures = gestures;
+}
+
+/// Advance the gesture clock (called once per frame), firing long-presses.
+pub(crate) fn update_gestures(state: &mut AppState, dt: f32) {
+    let mut gestures = std::mem::take(&mut state.gestures);
+    gestures.update(dt, |gesture| apply_gesture(state, gesture));
+    state.gestures = gestures;
+}
+
+/// Touch gestures drive the same logic as the mouse: a tap clicks, a
+/// double-tap double-clicks and a long-press shift-clicks (measures).
+fn apply_gesture(state: &mut AppState, gesture: Gesture) {
+    match gesture {
+        Gesture::Tap(pointer) => click(state, pointer, false),
+        Gesture::DoubleTap(pointer) => double_click(state, pointer),
+        Gesture::LongPress(pointer) => click(state, pointer, true),
+        Gesture::Drag(delta) => {
+            state.drag_view(delta.x, delta.y);
+            state.momentum.rotate.track(delta);
+        }
+        Gesture::Pan(delta) => {
+            state.pan_view(delta.x, delta.y);
+            state.momentum.pan.track(delta);
+        }
+        Gesture::Pinch(spread) => {
+            let zoom = -spread * TOUCH_ZOOM_MULTIPLIER;
+            state.renderer.camera.zoom(zoom);
+            state.momentum.zoom.track(zoom);
+        }
+        Gesture::Twist(radians) => state.twist_view(radians),
+    }
+}
+
+// ── Keyboard focus ───────────────────────────────────────────────────────
+
+/// Tab and Shift-Tab on the focused canvas select the next and previous
+/// body. Past the last (or before the first) the selection is cleared and
+/// the key is left to the browser, so focus moves on.
+fn bind_focus_events(
+    canvas: &HtmlCanvasElement,
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
     let state = Rc::clone(state);
     let closure = Closure::wrap(Box::new(move |e: web_sys::KeyboardEvent| {
-        match e.key().as_str() {
-            // Space → toggle pause
-            " " => {
-                e.prevent_default();
-                let mut s = state.borrow_mut();
-                s.simulation.time.toggle_pause();
-                crate::hud::update(
-                    s.simulation.time.current_days,
-                    s.simulation.time.days_per_second,
-                    s.simulation.time.paused,
-                    0.0,
-                );
-            }
-            // Arrow Up / + → speed up
-            "ArrowUp" | "+" => {
-                e.prevent_default();
-                let mut s = state.borrow_mut();
-                s.simulation.time.speed_up();
-                crate::hud::update(
-                    s.simulation.time.current_days,
-                    s.simulation.time.days_per_second,
-                    s.simulation.time.paused,
-                    0.0,
-                );
-            }
-            // Arrow Down / - → slow down
-            "ArrowDown" | "-" => {
+        if e.key() != "Tab" || e.ctrl_key() || e.meta_key() || e.alt_key() {
+            return;
+        }
+        let mut s = state.borrow_mut();
+        let count = s.simulation.bodies.len();
+        match cycle_body(s.selected_planet, count, e.shift_key()) {
+            Some(idx) => {
                 e.prevent_default();
-                let mut s = state.borrow_mut();
-                s.simulation.time.speed_down();
-                crate::hud::update(
-                    s.simulation.time.current_days,
-                    s.simulation.time.days_per_second,
-                    s.simulation.time.paused,
-                    0.0,
-                );
+                select_planet(&mut s, idx);
             }
-            // R → reset speed to default
-            "r" | "R" => {
-                let mut s = state.borrow_mut();
-                s.simulation.time.set_speed(DEFAULT_DAYS_PER_SECOND);
-                s.simulation.time.paused = false;
-                crate::hud::update(
-                    s.simulation.time.current_days,
-                    s.simulation.time.days_per_second,
-                    s.simulation.time.paused,
-                    0.0,
-                );
-            }
-            // H → toggle HUD visibility
-            "h" | "H" => {
-                crate::hud::toggle();
+            None if s.selected_planet.is_some() => deselect_all(&mut s),
+            None => {}
+        }
+    }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+    listeners.add(canvas, "keydown", closure)
+}
+
+// ── Keyboard & actions ───────────────────────────────────────────────────
+
+/// localStorage key holding user overrides for the key-binding table.
+const BINDINGS_STORAGE_KEY: &str = "solara.keybindings";
+
+impl ActionTarget for AppState {
+    fn time(&mut self) -> &mut SimulationTime {
+        &mut self.simulation.time
+    }
+
+    fn time_changed(&mut self) {
+        self.hud.invalidate();
+    }
+
+    fn select_body(&mut self, name: &str) {
+        if let Some(idx) = self
+            .simulation
+            .bodies
+            .iter()
+            .position(|b| b.id.eq_ignore_ascii_case(name))
+        {
+            select_planet(self, idx);
+        }
+    }
+
+    fn deselect(&mut self) {
+        deselect_all(self);
+    }
+
+    fn recenter(&mut self) {
+        deselect_all(self);
+    }
+
+    fn top_down_view(&mut self) {
+        self.set_fly_mode(false);
+        self.stop_tour();
+        self.renderer.camera.set_orientation(0.0, crate::constants::PHI_CLAMP); // look from above
+    }
+
+    fn reset_defaults(&mut self) {
+        self.reset_to_defaults();
+    }
+
+    fn toggle_fly_mode(&mut self) {
+        self.set_fly_mode(self.fly.is_none());
+    }
+
+    fn fly_thrust(&mut self, direction: FlyDirection, pressed: bool) {
+        if let Some(fly) = self.fly.as_mut() {
+            fly.set_held(direction, pressed);
+        }
+    }
+
+    fn save_bookmark(&mut self) {
+        AppState::save_bookmark(self);
+    }
+
+    fn next_bookmark(&mut self) {
+        AppState::next_bookmark(self);
+    }
+
+    fn toggle_tour(&mut self) {
+        if self.tour.is_some() {
+            self.stop_tour();
+            return;
+        }
+        match Tour::from_json(DEFAULT_TOUR) {
+            Ok(tour) => self.play_tour(tour),
+            Err(err) => log::warn!("⚠️ Bundled {err}"),
+        }
+    }
+
+    fn clear_measurement(&mut self) {
+        self.renderer.measurement.clear();
+    }
+
+    fn cycle_labels(&mut self) {
+        self.renderer.label_mode = self.renderer.label_mode.next();
+    }
+
+    fn toggle_orbits(&mut self) {
+        self.renderer.layers.orbits = !self.renderer.layers.orbits;
+    }
+
+    fn toggle_trails(&mut self) {
+        self.renderer.layers.trails = !self.renderer.layers.trails;
+    }
+
+    fn toggle_hud(&mut self) {
+        self.hud.toggle();
+    }
+
+    fn toggle_help(&mut self) {
+        self.hud.toggle_help();
+    }
+}
+
+/// Apply user overrides from localStorage on top of the default bindings,
+/// then publish the resulting table to the help overlay.
+fn load_bindings(state: &mut AppState) {
+    let stored = web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(BINDINGS_STORAGE_KEY).ok().flatten());
+    if let Some(text) = stored
+        && let Err(err) = state.bindings.apply(&text)
+    {
+        log::warn!("⚠️ Ignoring stored {err}");
+    }
+    state.hud.set_bindings(&state.bindings.help_rows(&state.i18n));
+}
+
+fn bind_keyboard_events(
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
+    load_bindings(&mut state.borrow_mut());
+    let window = web_sys::window().ok_or("No window")?;
+    // Keys bind to the document so they work without canvas focus.
+    let document = window.document().ok_or("No document")?;
+
+    {
+        let state = Rc::clone(state);
+        let closure = Closure::wrap(Box::new(move |e: web_sys::KeyboardEvent| {
+            // Leave browser shortcuts (Ctrl+R, Cmd+T, …) alone.
+            if e.ctrl_key() || e.meta_key() || e.alt_key() {
+                return;
             }
-            // Home → re-center camera on Sun, deselect planet
-            "Home" => {
-                e.prevent_default();
-                let mut s = state.borrow_mut();
-                deselect_all(&mut s);
-                // Reset camera to default distance & angles
-                s.renderer
-                    .camera
-                    .set_target(glam::Vec3::ZERO, crate::constants::CAMERA_DISTANCE);
+            let mut s = state.borrow_mut();
+            let Some(action) = s.bindings.action_for(&e.key()).cloned() else {
+                return;
+            };
+            e.prevent_default();
+            dispatch(&mut *s, &action);
+        }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+        listeners.add(&document, "keydown", closure)?;
+    }
+
+    // Key-up ends held actions (fly thrust).
+    {
+        let state = Rc::clone(state);
+        let closure = Closure::wrap(Box::new(move |e: web_sys::KeyboardEvent| {
+            let mut s = state.borrow_mut();
+            if let Some(action) = s.bindings.action_for(&e.key()).cloned() {
+                release(&mut *s, &action);
             }
-            // T → top-down view
-            "t" | "T" => {
-                e.prevent_default();
-                let mut s = state.borrow_mut();
-                s.renderer.camera.phi = crate::constants::PHI_CLAMP; // look from above
-                s.renderer.camera.theta = 0.0;
+        }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+        listeners.add(&document, "keyup", closure)?;
+    }
+
+    // Key-ups are never delivered once the window loses focus, so drop all
+    // held keys then.
+    {
+        let state = Rc::clone(state);
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            if let Some(fly) = state.borrow_mut().fly.as_mut() {
+                fly.release_all();
             }
-            // Escape → deselect planet, return to overview
-            "Escape" => {
-                e.prevent_default();
-                deselect_all(&mut state.borrow_mut());
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        listeners.add(&window, "blur", closure)?;
+    }
+
+    // HUD buttons fire `solara-action` with an action id, independent of key bindings.
+    {
+        let state = Rc::clone(state);
+        let closure = Closure::wrap(Box::new(move |e: web_sys::CustomEvent| {
+            let Some(action) = e.detail().as_string().and_then(|id| Action::parse(&id)) else {
+                return;
+            };
+            // A click has no key-up to end a held action.
+            if action.is_held() {
+                return;
             }
-            // 1–8 → select Mercury through Neptune directly.
-            // This relies on the fixed body ordering in data::solar_system:
-            // index 0 = Sun, 1 = Mercury, …, 8 = Neptune.
-            "1" => select_planet(&mut state.borrow_mut(), 1),
-            "2" => select_planet(&mut state.borrow_mut(), 2),
-            "3" => select_planet(&mut state.borrow_mut(), 3),
-            "4" => select_planet(&mut state.borrow_mut(), 4),
-            "5" => select_planet(&mut state.borrow_mut(), 5),
-            "6" => select_planet(&mut state.borrow_mut(), 6),
-            "7" => select_planet(&mut state.borrow_mut(), 7),
-            "8" => select_planet(&mut state.borrow_mut(), 8),
-            _ => {}
-        }
-    }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+            dispatch(&mut *state.borrow_mut(), &action);
+        }) as Box<dyn FnMut(web_sys::CustomEvent)>);
+        listeners.add(&window, "solara-action", closure)?;
+    }
+    Ok(())
+}
 
-    // Bind to document so it works even without canvas focus
-    let document = web_sys::window()
-        .and_then(|w| w.document())
-        .expect("Failed to get document for keyboard events");
-    document
-        .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())
-        .expect("Failed to bind keydown listener");
-    closure.forget();
+// ── Tours ────────────────────────────────────────────────────────────────
+
+/// Play tours delivered by `window.solaraPlayTour(url)` as a `solara-tour`
+/// event carrying the file's JSON text.
+fn bind_tour_event(
+    state: &Rc<RefCell<AppState>>,
+    listeners: &mut EventListeners,
+) -> Result<(), JsValue> {
+    let state = Rc::clone(state);
+    let closure = Closure::wrap(Box::new(move |e: web_sys::CustomEvent| {
+        let Some(text) = e.detail().as_string() else {
+            return;
+        };
+        match Tour::from_json(&text) {
+            Ok(tour) => state.borrow_mut().play_tour(tour),
+            Err(err) => log::warn!("⚠️ Ignoring {err}"),
+        }
+    }) as Box<dyn FnMut(web_sys::CustomEvent)>);
+    let window = web_sys::window().ok_or("No window")?;
+    listeners.add(&window, "solara-tour", closure)
 }
@@ -7,157 +7,88 @@
 //!
 //! | Module        | Purpose                                          |
 //! |---------------|--------------------------------------------------|
+//! | [`accessibility`]| Live-region announcements, focus, preferences |
+//! | [`api`]       | Public JavaScript API (`SolaraHandle`)           |
 //! | [`app`]       | Shared application state                         |
 //! | [`constants`] | Centralised tuneable values                      |
 //! | [`data`]      | NASA-sourced solar system data                   |
-//! | [`hud`]       | HUD DOM updates                                  |
+//! | [`hud`]       | HUD updates through page callbacks               |
+//! | [`i18n`]      | Message catalogues, locale-aware formatting      |
 //! | [`input`]     | Browser event → camera mutations                 |
+//! | [`lifecycle`] | Removable DOM listeners, stoppable render loop   |
+//! | [`persistence`]| Session save / restore in localStorage          |
 //! | [`renderer`]  | WebGL2 draw pipeline, shaders, textures, meshes  |
 //! | [`simulation`]| Kepler orbits, time control, celestial bodies    |
+//! | [`tour`]      | Camera bookmarks and scripted tours              |
+//! | [`url_state`] | Shareable view state in the URL hash             |
+//! | [`viewer`]    | Viewer construction, options and teardown        |
 
+mod accessibility;
+mod api;
 mod app;
 mod constants;
 mod data;
 mod hud;
+mod i18n;
 mod input;
+mod lifecycle;
+mod persistence;
 mod renderer;
 mod simulation;
 mod splash;
+mod tour;
+mod url_state;
+mod viewer;
 
-use std::cell::RefCell;
-use std::rc::Rc;
-
-use app::AppState;
-use constants::*;
-use renderer::Renderer;
-use simulation::Simulation;
 use wasm_bindgen::prelude::*;
-use web_sys::WebGl2RenderingContext as GL;
-
-// ─── Helpers ─────────────────────────────────────────────────────────────
-
-/// Schedule the next animation frame.
-fn request_animation_frame(f: &Closure<dyn FnMut(f64)>) -> i32 {
-    web_sys::window()
-        .and_then(|w| w.request_animation_frame(f.as_ref().unchecked_ref()).ok())
-        .unwrap_or(0)
-}
 
 // ─── Entry point ─────────────────────────────────────────────────────────
 
+/// Module initialisation: panic hook and logging. Viewers are created from
+/// JavaScript with `new SolaraHandle(canvas, options)` (see [`api`]).
 #[wasm_bindgen(start)]
-pub fn start() -> Result<(), JsValue> {
+pub fn start() {
     console_error_panic_hook::set_once();
     console_log::init_with_level(log::Level::Info).ok();
 
     log::info!("🚀 SOLARA is starting...");
-
-    splash::update_step("wasm", "done");
-    splash::update_step("webgl", "loading");
-
-    // ── Canvas & WebGL2 ──
-    let window = web_sys::window().ok_or("No window")?;
-    let document = window.document().ok_or("No document")?;
-    let canvas = document
-        .get_element_by_id("solara-canvas")
-        .ok_or("Canvas #solara-canvas not found")?
-        .dyn_into::<web_sys::HtmlCanvasElement>()?;
-
-    let dpr = window.device_pixel_ratio();
-    let width = (window.inner_width()?.as_f64().unwrap() * dpr) as u32;
-    let height = (window.inner_height()?.as_f64().unwrap() * dpr) as u32;
-    canvas.set_width(width);
-    canvas.set_height(height);
-
-    let gl: GL = canvas
-        .get_context("webgl2")?
-        .ok_or("WebGL2 not supported")?
-        .dyn_into::<GL>()?;
-    gl.viewport(0, 0, width as i32, height as i32);
-
-    splash::update_step("webgl", "done");
-    splash::update_step("simulation", "loading");
-
-    // ── Simulation ──
-    let bodies = data::solar_system::create_solar_system();
-    let simulation = Simulation::new(bodies.clone());
-
-    splash::update_step("simulation", "done");
-    splash::update_step("renderer", "loading");
-
-    // ── Renderer ──
-    let renderer = Renderer::new(gl, width, height, &bodies)?;
-    log::info!("✨ Renderer initialized ({width}×{height})");
-
-    splash::update_step("renderer", "done");
-
-    // ── Textures (async) ──
-    let gl_ref = renderer.gl_handle();
-    let tex_ref = renderer.textures_handle();
-    renderer::texture::start_loading_textures(&gl_ref, &tex_ref, &bodies);
-    log::info!("📥 Texture loading started for {} bodies", bodies.len());
-
-    // ── Shared state ──
-    let state = Rc::new(RefCell::new(AppState::new(renderer, simulation)));
-
-    // ── Input ──
-    input::setup_input(&canvas, Rc::clone(&state));
-
-    // ── Window resize ──
-    {
-        let state_resize = Rc::clone(&state);
-        let canvas_resize = canvas.clone();
-        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
-            let win = web_sys::window().unwrap();
-            let dpr = win.device_pixel_ratio();
-            let w = (win.inner_width().unwrap().as_f64().unwrap() * dpr) as u32;
-            let h = (win.inner_height().unwrap().as_f64().unwrap() * dpr) as u32;
-            canvas_resize.set_width(w);
-            canvas_resize.set_height(h);
-            state_resize.borrow_mut().renderer.resize(w, h);
-        }) as Box<dyn FnMut(web_sys::Event)>);
-        window
-            .add_event_listener_with_callback("solara-resize", closure.as_ref().unchecked_ref())?;
-        closure.forget();
-    }
-
-    // ── Render loop ──
-    type AnimFrameClosure = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
-    let f: AnimFrameClosure = Rc::new(RefCell::new(None));
-    let g = Rc::clone(&f);
-    let last_time = Rc::new(RefCell::new(0.0_f64));
-
-    *g.borrow_mut() = Some(Closure::wrap(Box::new(move |timestamp: f64| {
-        let mut lt = last_time.borrow_mut();
-        let dt = if *lt == 0.0 {
-            FIRST_FRAME_DT
-        } else {
-            ((timestamp - *lt) / 1000.0).min(MAX_FRAME_DT)
-        };
-        *lt = timestamp;
-
-        state.borrow_mut().tick(dt);
-
-        request_animation_frame(f.borrow().as_ref().unwrap());
-    }) as Box<dyn FnMut(f64)>));
-
-    request_animation_frame(g.borrow().as_ref().unwrap());
-
-    log::info!("🌍 SOLARA render loop started");
-    Ok(())
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
+    use crate::accessibility::{self, Announcer, Preferences, SelectedStatus, Status};
+    use crate::api::{BodyState, EventKind, FlyToOptions};
     use crate::constants::*;
     use crate::data::solar_system::create_solar_system;
-    use crate::renderer::camera::Camera;
+    use crate::input::actions::{self, Action, ActionTarget, KeyBindings};
+    use crate::i18n::{Catalogue, I18n, Locale};
+    use crate::hud::{
+        self, FpsMeter, HudEvent, HudSnapshot, HudThrottle, LiveBody, SelectedBody, SpeedState,
+    };
+    use crate::input::gamepad;
+    use crate::lifecycle::frame_dt;
+    use crate::persistence::{self, Session, SessionError};
+    use crate::renderer::fly_camera::{FlyCamera, FlyDirection, FlyInput, cruise_speed};
+    use crate::renderer::camera::{Camera, CameraPose, Easing, relative_to_eye};
+    use crate::renderer::frame_uniforms;
+    use crate::renderer::{font, labels};
+    use crate::renderer::lod;
+    use crate::renderer::measure::{self, Measurement};
     use crate::renderer::mesh;
+    use crate::renderer::render_pass::{
+        INSTANCE_FLAG_TEXTURED, Layers, PLANET_INSTANCE_FLOATS, orbit_color,
+        push_planet_instance,
+    };
+    use crate::renderer::shader;
+    use crate::renderer::texture;
     use crate::simulation::Simulation;
     use crate::simulation::orbit;
     use crate::simulation::time::SimulationTime;
+    use crate::tour::{self, Bookmark, Tour, TourError, TourPlayer, TourStep};
+    use crate::url_state::{self, ViewState};
+    use crate::viewer::ViewerOptions;
 
     // ── Solar system data ──
 
@@ -173,13 +104,13 @@ mod tests {
         let bodies = create_solar_system();
         let sim = Simulation::new(bodies);
         let sun = sim.bodies.iter().find(|b| b.is_star).unwrap();
-        assert_eq!(sun.position, glam::Vec3::ZERO);
+        assert_eq!(sun.position, glam::DVec3::ZERO);
     }
 
     #[test]
     fn earth_returns_after_one_orbit() {
         let bodies = create_solar_system();
-        let earth = bodies.iter().find(|b| b.name == "Earth").unwrap();
+        let earth = bodies.iter().find(|b| b.id == "Earth").unwrap();
         let pos_start = earth.position_at(0.0);
         let pos_full = earth.position_at(365.25);
         let distance = pos_start.distance(pos_full);
@@ -192,8 +123,8 @@ mod tests {
     #[test]
     fn mercury_faster_than_neptune() {
         let bodies = create_solar_system();
-        let mercury = bodies.iter().find(|b| b.name == "Mercury").unwrap();
-        let neptune = bodies.iter().find(|b| b.name == "Neptune").unwrap();
+        let mercury = bodies.iter().find(|b| b.id == "Mercury").unwrap();
+        let neptune = bodies.iter().find(|b| b.id == "Neptune").unwrap();
         let merc = mercury
             .position_at(0.0)
             .distance(mercury.position_at(100.0));
@@ -210,11 +141,49 @@ mod tests {
             assert!(
                 body.texture_file.is_some(),
                 "{} should have a texture file",
-                body.name
+                body.id
             );
         }
     }
 
+    #[test]
+    fn catalogue_physical_data_is_plausible() {
+        for body in create_solar_system() {
+            assert!((0.0..1.0).contains(&body.eccentricity), "{}", body.id);
+            assert!(body.mass_kg > 0.0 && body.day_length_hours > 0.0, "{}", body.id);
+        }
+    }
+
+    #[test]
+    fn earth_gravity_escape_and_orbital_speed() {
+        let bodies = create_solar_system();
+        let earth = bodies.iter().find(|b| b.id == "Earth").unwrap();
+        assert!((earth.surface_gravity() - 9.8).abs() < 0.05);
+        assert!((earth.escape_velocity_km_s() - 11.2).abs() < 0.05);
+        assert!((earth.orbital_speed_km_s() - 29.8).abs() < 0.1);
+        let mars = bodies.iter().find(|b| b.id == "Mars").unwrap();
+        assert!(mars.orbital_speed_km_s() < earth.orbital_speed_km_s());
+    }
+
+    #[test]
+    fn live_body_light_time_and_orbit_angle() {
+        let bodies = create_solar_system();
+        let mars = bodies.iter().find(|b| b.id == "Mars").unwrap();
+        let earth = bodies.iter().find(|b| b.id == "Earth").unwrap();
+        let days = 1234.5;
+        let live = LiveBody::new(mars, mars.position_at(days), Some(earth.position_at(days)));
+        // Sunlight takes about 12.7 minutes to reach Mars at 1.52 AU.
+        let selected = SelectedBody::new(mars, false, &I18n::default());
+        assert!((selected.sun_light_seconds / 60.0 - 12.7).abs() < 0.1);
+        let earth_au = live.earth_distance_au.unwrap();
+        assert!(earth_au > 0.5 && earth_au < 2.6);
+        assert!((live.earth_light_seconds.unwrap() - earth_au * 499.0).abs() < 1.0);
+
+        let omega = std::f64::consts::TAU / mars.orbital_period_days;
+        let expected = (mars.start_angle_rad + omega * days).rem_euclid(std::f64::consts::TAU);
+        assert!((live.orbit_angle - expected).abs() < 1e-9);
+    }
+
     // ── Simulation / time ──
 
     #[test]
@@ -363,6 +332,31 @@ mod tests {
         assert!(cam.phi <= PHI_CLAMP);
     }
 
+    #[test]
+    fn camera_pans_in_the_view_plane_in_proportion_to_distance() {
+        let mut cam = Camera::new(1.0);
+        let project = |cam: &Camera, world: glam::DVec3| {
+            let p = cam.view_matrix() * (world - cam.eye_position()).as_vec3().extend(1.0);
+            glam::Vec2::new(p.x, p.y)
+        };
+        let marker = cam.target;
+        let before = project(&cam, marker);
+        let offset = cam.pan_offset(10.0, 0.0);
+        // The target moves across the view, not towards it.
+        assert!(offset.as_vec3().dot(cam.eye_offset()).abs() < 1e-3);
+        cam.shift(offset);
+        // Dragging right carries the scene right with the pointer.
+        let after = project(&cam, marker);
+        assert!(after.x > before.x && (after.y - before.y).abs() < 1e-3);
+        // Dragging down carries it down the screen.
+        cam.shift(cam.pan_offset(0.0, 10.0));
+        assert!(project(&cam, marker).y < after.y);
+
+        let near = cam.pan_offset(10.0, 0.0).length();
+        cam.distance *= 4.0;
+        assert!((cam.pan_offset(10.0, 0.0).length() / near - 4.0).abs() < 1e-3);
+    }
+
     #[test]
     fn camera_set_aspect() {
         let mut cam = Camera::new(1.0);
@@ -383,13 +377,70 @@ mod tests {
         }
     }
 
+    // ── Floating origin ──
+
+    #[test]
+    fn camera_relative_positions_stay_precise_after_centuries() {
+        let mut sim = Simulation::new(create_solar_system());
+        sim.time.set_speed(100.0);
+        // 2 000 one-second frames at 100 days/s ≈ 550 years of galactic drift.
+        for _ in 0..2_000 {
+            sim.update(1.0);
+        }
+        let earth = sim.bodies.iter().find(|b| b.id == "Earth").unwrap();
+        assert!(
+            earth.position.length() > 500_000.0,
+            "Drift should carry Earth far from the origin, got {}",
+            earth.position.length()
+        );
+
+        let mut cam = Camera::new(1.0);
+        cam.target = earth.position;
+        cam.distance = earth.display_radius * 3.0;
+        let eye = cam.eye_position();
+
+        // A vertex on Earth's surface, the way the planet pass places it.
+        let vertex = earth.position + glam::DVec3::X * earth.display_radius as f64;
+        let exact = vertex - eye;
+
+        let relative = relative_to_eye(vertex, eye).as_dvec3();
+        assert!(
+            (relative - exact).length() < 1e-5,
+            "Camera-relative vertex off by {}",
+            (relative - exact).length()
+        );
+
+        // Narrowing world positions to f32 first loses the detail entirely.
+        let naive = (vertex.as_vec3() - eye.as_vec3()).as_dvec3();
+        assert!((naive - exact).length() > 1e-3);
+    }
+
+    #[test]
+    fn camera_view_matrix_has_no_translation() {
+        let mut cam = Camera::new(1.0);
+        cam.target = glam::DVec3::new(1.0e6, -2.0e6, 3.0e6);
+        let view = cam.view_matrix();
+        assert!(view.w_axis.truncate().length() < 1e-6);
+        // The target lies straight ahead of the eye at `distance`.
+        let target = view.transform_point3(relative_to_eye(cam.target, cam.eye_position()));
+        assert!((target - glam::Vec3::new(0.0, 0.0, -cam.distance)).length() < 1e-3);
+    }
+
     // ── Mesh generation ──
 
+    /// `mesh`'s indices widened to `u32`.
+    fn mesh_indices(mesh: &mesh::Mesh) -> Vec<u32> {
+        match &mesh.indices {
+            mesh::Indices::U16(v) => v.iter().map(|&i| i as u32).collect(),
+            mesh::Indices::U32(v) => v.clone(),
+        }
+    }
+
     #[test]
     fn sphere_has_vertices_and_indices() {
-        let sphere = mesh::generate_sphere();
+        let sphere = mesh::generate_sphere_custom(32, 24);
         assert!(!sphere.vertices.is_empty(), "Sphere should have vertices");
-        assert!(!sphere.indices.is_empty(), "Sphere should have indices");
+        assert!(sphere.indices.len() > 0, "Sphere should have indices");
         // 8 floats per vertex (pos.xyz + norm.xyz + uv.xy)
         assert_eq!(
             sphere.vertices.len() % 8,
@@ -402,7 +453,7 @@ mod tests {
     fn ring_has_vertices_and_indices() {
         let ring = mesh::generate_ring();
         assert!(!ring.vertices.is_empty());
-        assert!(!ring.indices.is_empty());
+        assert!(ring.indices.len() > 0);
     }
 
     #[test]
@@ -415,138 +466,1973 @@ mod tests {
         );
     }
 
-    // ── Orbit geometry ──
+    /// Every triangle of `mesh` must face away from the sphere centre.
+    fn assert_outward_winding(mesh: &mesh::Mesh) {
+        let pos = |i: u32| {
+            let o = i as usize * 8;
+            glam::Vec3::from_slice(&mesh.vertices[o..o + 3])
+        };
+        let indices = mesh_indices(mesh);
+        for tri in indices.chunks(3) {
+            let (a, b, c) = (pos(tri[0]), pos(tri[1]), pos(tri[2]));
+            let normal = (b - a).cross(c - a);
+            if normal.length() < 1e-7 {
+                continue; // degenerate pole triangle of a UV sphere
+            }
+            assert!(
+                normal.dot(a + b + c) > 0.0,
+                "Triangle {tri:?} winds clockwise seen from outside"
+            );
+        }
+    }
 
     #[test]
-    fn orbit_path_is_closed_loop() {
-        let path = orbit::generate_orbit_path(1.0, 0.0);
+    fn uv_sphere_winds_counter_clockwise() {
+        assert_outward_winding(&mesh::generate_sphere_custom(16, 12));
+    }
+
+    #[test]
+    fn cube_sphere_winds_counter_clockwise() {
+        assert_outward_winding(&mesh::generate_cube_sphere(6));
+    }
+
+    #[test]
+    fn cube_sphere_vertices_are_on_unit_sphere() {
+        let cube = mesh::generate_cube_sphere(8);
+        for v in cube.vertices.chunks(8) {
+            let len = glam::Vec3::from_slice(&v[0..3]).length();
+            assert!((len - 1.0).abs() < 1e-5, "Vertex off the unit sphere: {len}");
+        }
+    }
+
+    #[test]
+    fn cube_sphere_triangles_do_not_straddle_uv_seam() {
+        let cube = mesh::generate_cube_sphere(8);
+        let u = |i: u32| cube.vertices[i as usize * 8 + 6];
+        let indices = mesh_indices(&cube);
+        for tri in indices.chunks(3) {
+            let us = [u(tri[0]), u(tri[1]), u(tri[2])];
+            let span = us.iter().cloned().fold(f32::MIN, f32::max)
+                - us.iter().cloned().fold(f32::MAX, f32::min);
+            assert!(span < 0.5, "Triangle {tri:?} spans {span} of the texture width");
+        }
+    }
+
+    #[test]
+    fn cube_sphere_has_no_pole_pinching() {
+        // A UV sphere collapses a whole ring of triangles onto each pole;
+        // a cube-sphere keeps every triangle close to the mean area.
+        let cube = mesh::generate_cube_sphere(12);
+        let pos = |i: u32| {
+            let o = i as usize * 8;
+            glam::Vec3::from_slice(&cube.vertices[o..o + 3])
+        };
+        let indices = mesh_indices(&cube);
+        let areas: Vec<f32> = indices
+            .chunks(3)
+            .map(|t| (pos(t[1]) - pos(t[0])).cross(pos(t[2]) - pos(t[0])).length() / 2.0)
+            .collect();
+        let mean = areas.iter().sum::<f32>() / areas.len() as f32;
+        let min = areas.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(min > mean * 0.3, "Smallest triangle {min} vs mean {mean}");
+    }
+
+    #[test]
+    fn sphere_lods_are_finest_first() {
+        for kind in [mesh::SphereKind::Uv, mesh::SphereKind::CubeSphere] {
+            let lods = mesh::generate_sphere_lods(kind);
+            assert_eq!(lods.len(), SPHERE_LOD_THRESHOLDS_PX.len() + 1);
+            for pair in lods.windows(2) {
+                assert!(pair[0].indices.len() > pair[1].indices.len());
+            }
+        }
+    }
+
+    #[test]
+    fn small_meshes_use_16_bit_indices() {
+        let ring = mesh::generate_ring();
+        assert!(matches!(ring.indices, mesh::Indices::U16(_)));
+        assert_eq!(ring.indices.gl_type(), web_sys::WebGl2RenderingContext::UNSIGNED_SHORT);
+    }
+
+    #[test]
+    fn large_meshes_switch_to_32_bit_indices() {
+        // 300 × 300 segments → 301² = 90 601 vertices, beyond the u16 range.
+        let sphere = mesh::generate_sphere_custom(300, 300);
+        let vertex_count = sphere.vertices.len() / mesh::VERTEX_FLOATS;
+        assert!(vertex_count > mesh::MAX_U16_VERTICES);
+        assert!(matches!(sphere.indices, mesh::Indices::U32(_)));
+        assert_eq!(sphere.indices.gl_type(), web_sys::WebGl2RenderingContext::UNSIGNED_INT);
+        let max = *mesh_indices(&sphere).iter().max().unwrap() as usize;
+        assert_eq!(max, vertex_count - 1);
+    }
+
+    #[test]
+    fn mesh_builder_rejects_out_of_bounds_index() {
+        let mut b = mesh::MeshBuilder::default();
+        let a = b.vertex([0.0; 3], [0.0, 1.0, 0.0], [0.0; 2]);
+        let c = b.vertex([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0]);
+        b.triangle(a, c, 2);
         assert_eq!(
-            path.len(),
-            ORBIT_SEGMENTS + 1,
-            "Path should have SEGMENTS+1 points"
-        );
-        let first = path.first().unwrap();
-        let last = path.last().unwrap();
-        assert!(
-            first.distance(*last) < 0.01,
-            "First and last points should nearly coincide"
+            b.build().err(),
+            Some(mesh::MeshError::IndexOutOfBounds { index: 2, vertex_count: 2 })
         );
     }
 
     #[test]
-    fn orbit_radius_scales_with_au() {
-        let inner = orbit::generate_orbit_path(1.0, 0.0);
-        let outer = orbit::generate_orbit_path(5.0, 0.0);
-        let r_inner = inner[0].length();
-        let r_outer = outer[0].length();
-        assert!(
-            r_outer > r_inner * 4.0,
-            "5 AU orbit should be much larger than 1 AU orbit"
+    fn mesh_builder_rejects_bad_stride() {
+        assert!(matches!(
+            mesh::Mesh::new(vec![0.0; 8 * 3 + 2], vec![0, 1, 2]),
+            Err(mesh::MeshError::StrideMismatch { floats: 26, stride: 8 })
+        ));
+    }
+
+    #[test]
+    fn mesh_builder_rejects_empty_and_non_finite() {
+        assert!(matches!(mesh::MeshBuilder::default().build(), Err(mesh::MeshError::Empty)));
+
+        let mut b = mesh::MeshBuilder::default();
+        b.vertex([f32::NAN, 0.0, 0.0], [0.0; 3], [0.0; 2]);
+        b.vertex([0.0; 3], [0.0; 3], [0.0; 2]);
+        b.vertex([0.0; 3], [0.0; 3], [0.0; 2]);
+        b.triangle(0, 1, 2);
+        assert!(matches!(b.build(), Err(mesh::MeshError::NonFinite { float_offset: 0 })));
+    }
+
+    // ── Level of detail ──
+
+    #[test]
+    fn lod_selection_follows_thresholds() {
+        let t = [100.0, 20.0, 5.0];
+        assert_eq!(lod::select_lod(500.0, &t), 0);
+        assert_eq!(lod::select_lod(100.0, &t), 0);
+        assert_eq!(lod::select_lod(50.0, &t), 1);
+        assert_eq!(lod::select_lod(6.0, &t), 2);
+        assert_eq!(lod::select_lod(1.0, &t), 3);
+    }
+
+    #[test]
+    fn projected_radius_shrinks_with_distance() {
+        let cam = Camera::new(1.0);
+        let proj = cam.projection_matrix();
+        let near = lod::projected_radius_px(1.0, 10.0, &proj, 1000.0);
+        let far = lod::projected_radius_px(1.0, 100.0, &proj, 1000.0);
+        assert!(near > far * 9.0, "Radius should scale ~1/distance");
+        assert_eq!(
+            lod::projected_radius_px(2.0, 1.0, &proj, 1000.0),
+            f32::INFINITY,
+            "Eye inside the sphere should pick the finest LOD"
         );
     }
 
+    // ── Instanced planet pass ──
+
     #[test]
-    fn orbit_with_inclination_has_y_component() {
-        let flat = orbit::generate_orbit_path(1.0, 0.0);
-        let tilted = orbit::generate_orbit_path(1.0, 0.3);
-        let max_y_flat: f32 = flat.iter().map(|p| p.y.abs()).fold(0.0, f32::max);
-        let max_y_tilted: f32 = tilted.iter().map(|p| p.y.abs()).fold(0.0, f32::max);
-        assert!(
-            max_y_tilted > max_y_flat + 0.1,
-            "Tilted orbit should have larger Y extent"
+    fn frame_block_uses_std140_offsets() {
+        let view = glam::Mat4::from_translation(glam::Vec3::new(1.0, 2.0, 3.0));
+        let proj = glam::Mat4::from_scale(glam::Vec3::splat(2.0));
+        let data = frame_uniforms::pack(
+            &view,
+            &proj,
+            glam::Vec3::new(4.0, 5.0, 6.0),
+            glam::Vec3::new(7.0, 8.0, 9.0),
+            1.5,
         );
+        assert_eq!(data.len() * 4, 176, "2 × mat4 + 3 × vec4 = 176 bytes");
+        assert_eq!(data[12..15], [1.0, 2.0, 3.0], "view translation column");
+        assert_eq!(data[16], 2.0, "projection starts at byte 64");
+        assert_eq!(data[32..36], [4.0, 5.0, 6.0, 0.0], "light at byte 128");
+        assert_eq!(data[36..40], [7.0, 8.0, 9.0, 0.0], "eye at byte 144");
+        assert_eq!(data[40], 1.5, "time at byte 160");
     }
 
-    // ── Constants consistency ──
+    #[test]
+    fn planet_instance_layout_matches_stride() {
+        let mut out = Vec::new();
+        let model = glam::Mat4::from_translation(glam::Vec3::X);
+        push_planet_instance(&mut out, &model, [0.1, 0.2, 0.3], INSTANCE_FLAG_TEXTURED, 4);
+        push_planet_instance(&mut out, &model, [0.4, 0.5, 0.6], 0, 0);
+        assert_eq!(out.len(), 2 * PLANET_INSTANCE_FLOATS);
+        assert_eq!(out[16..21], [0.1, 0.2, 0.3, 1.0, 4.0]);
+        assert_eq!(out[PLANET_INSTANCE_FLOATS + 12], 1.0, "second instance translation");
+    }
 
     #[test]
-    fn camera_near_less_than_far() {
-        const { assert!(CAMERA_NEAR < CAMERA_FAR) };
+    fn texture_array_mip_chain_reaches_one_pixel() {
+        assert_eq!(texture::mip_levels(1024, 512), 11);
+        assert_eq!(texture::mip_levels(1, 1), 1);
+        assert_eq!(texture::mip_levels(0, 0), 1);
     }
 
+    // ── Labels ──
+
     #[test]
-    fn starfield_radius_exceeds_camera_max() {
-        const {
-            assert!(
-                STARFIELD_RADIUS > CAMERA_MAX_DISTANCE,
-                "Stars should be beyond max zoom-out distance"
-            )
+    fn font_atlas_is_a_distance_field() {
+        assert_eq!(font::ATLAS.len(), font::ATLAS_WIDTH * font::ATLAS_HEIGHT);
+        // Centre of the '|' cell is inside the stroke, its corner is far outside.
+        let (min, max) = font::glyph_uv('|');
+        let texel = |uv: glam::Vec2| {
+            let x = (uv.x * font::ATLAS_WIDTH as f32) as usize;
+            let y = (uv.y * font::ATLAS_HEIGHT as f32) as usize;
+            font::ATLAS[y * font::ATLAS_WIDTH + x]
         };
+        assert!(texel((min + max) * 0.5) > 128);
+        assert_eq!(texel(min), 0);
     }
 
-    // ── Planet selection / camera transition ──
+    #[test]
+    fn text_layout_advances_and_skips_spaces() {
+        let quads: Vec<_> = font::layout("Io 1", glam::Vec2::ZERO, 14.0).collect();
+        assert_eq!(quads.len(), 3, "Spaces produce no quad");
+        assert!(quads.windows(2).all(|q| q[1].min.x > q[0].min.x));
+        assert!((font::text_width("Io 1", 28.0) - 2.0 * font::text_width("Io 1", 14.0)).abs() < 1e-3);
+        assert_eq!(font::text_width("", 14.0), 0.0);
+        // Accented letters have their own cells; anything else falls back to '?'.
+        assert_ne!(font::glyph_uv('é'), font::glyph_uv('?'));
+        assert_ne!(font::glyph_uv('é'), font::glyph_uv('e'));
+        assert_eq!(font::glyph_uv('ß'), font::glyph_uv('?'));
+        let french = "Vénus\u{202f}1,52";
+        assert_eq!(font::layout(french, glam::Vec2::ZERO, 14.0).count(), 9, "Narrow spaces produce no quad");
+    }
 
     #[test]
-    fn camera_set_target_sets_lerp_fields() {
-        let mut cam = Camera::new(1.0);
-        let target = glam::Vec3::new(10.0, 0.0, 0.0);
-        cam.set_target(target, 50.0);
-        assert!(cam.lerp_target.is_some(), "lerp_target should be set");
-        assert!(cam.lerp_distance.is_some(), "lerp_distance should be set");
-        assert_eq!(cam.lerp_target.unwrap(), target);
-        assert_eq!(cam.lerp_distance.unwrap(), 50.0);
+    fn label_culling_keeps_higher_priority() {
+        let rect = |x: f32| labels::Rect {
+            min: glam::Vec2::new(x, 0.0),
+            max: glam::Vec2::new(x + 50.0, 10.0),
+        };
+        let placements = [
+            labels::LabelPlacement { rect: rect(0.0), priority: 1.0 },
+            labels::LabelPlacement { rect: rect(30.0), priority: 5.0 },
+            labels::LabelPlacement { rect: rect(100.0), priority: 0.5 },
+        ];
+        assert_eq!(labels::cull_overlaps(&placements), vec![1, 2]);
     }
 
     #[test]
-    fn camera_set_target_clamps_distance() {
-        let mut cam = Camera::new(1.0);
-        // Request a distance below min
-        cam.set_target(glam::Vec3::ZERO, 0.001);
-        assert!(
-            cam.lerp_distance.unwrap() >= cam.min_distance,
-            "lerp_distance should be clamped to min_distance"
+    fn label_fade_and_projection() {
+        assert_eq!(labels::fade_alpha(0.0), 1.0);
+        assert_eq!(labels::fade_alpha(LABEL_FADE_END + 1.0), 0.0);
+        let mid = labels::fade_alpha((LABEL_FADE_START + LABEL_FADE_END) * 0.5);
+        assert!(mid > 0.0 && mid < 1.0);
+
+        let cam = Camera::new(2.0);
+        let vp = cam.projection_matrix() * cam.view_matrix();
+        let viewport = glam::Vec2::new(800.0, 400.0);
+        let ahead = -cam.eye_offset();
+        let centre = labels::project_to_screen(&vp, ahead, viewport).unwrap();
+        assert!((centre - viewport * 0.5).length() < 1e-2);
+        assert!(labels::project_to_screen(&vp, -ahead, viewport).is_none());
+    }
+
+    #[test]
+    fn label_mode_cycles() {
+        let start = labels::LabelMode::default();
+        assert_eq!(start.next().next().next(), start);
+        assert_ne!(start.next(), start);
+    }
+
+    // ── Measuring tool ──
+
+    #[test]
+    fn measurement_toggles_bodies_and_starts_over_after_three() {
+        let mut m = Measurement::default();
+        m.toggle(3);
+        m.toggle(4);
+        assert_eq!(m.bodies(), [3, 4]);
+        m.toggle(3);
+        assert_eq!(m.bodies(), [4], "picking a measured body drops it");
+        m.toggle(5);
+        m.toggle(6);
+        assert_eq!(m.bodies(), [4, 5, 6]);
+        m.toggle(1);
+        assert_eq!(m.bodies(), [1], "a fourth body starts a new measurement");
+        m.clear();
+        assert!(m.bodies().is_empty());
+    }
+
+    #[test]
+    fn measured_distance_in_km_au_and_light_minutes() {
+        let one_au = AU_TO_DISPLAY as f64;
+        let d = measure::Distance::between(glam::DVec3::ZERO, glam::DVec3::new(0.0, 0.0, one_au));
+        assert!((d.au - 1.0).abs() < 1e-9);
+        assert!((d.km - AU_KM).abs() < 1e-3);
+        // Sunlight takes about 8.3 minutes to reach Earth.
+        assert!((d.light_minutes - 8.317).abs() < 0.01, "{}", d.light_minutes);
+        assert_eq!(
+            d.label(&I18n::default()),
+            "1.00 AU · 149.6 million km · 8.3 light-min"
         );
-        // Request a distance above max
-        cam.set_target(glam::Vec3::ZERO, 99_999.0);
-        assert!(
-            cam.lerp_distance.unwrap() <= cam.max_distance,
-            "lerp_distance should be clamped to max_distance"
+        assert_eq!(
+            d.label(&I18n::new(Locale::Fr)),
+            "1,00 ua · 149,6 millions de km · 8,3 min-lumière"
         );
+        for c in d.label(&I18n::new(Locale::Fr)).chars() {
+            assert_ne!(font::glyph_uv(c), font::glyph_uv('?'), "{c}");
+        }
     }
 
     #[test]
-    fn camera_update_transition_moves_toward_target() {
-        let mut cam = Camera::new(1.0);
-        let target = glam::Vec3::new(100.0, 0.0, 0.0);
-        cam.set_target(target, 20.0);
+    fn measured_angle_at_the_middle_body() {
+        use glam::DVec3;
+        let vertex = DVec3::new(1.0, 2.0, 3.0);
+        let angle = |a: DVec3, b: DVec3| measure::angle_deg(vertex + a, vertex, vertex + b);
+        assert!((angle(DVec3::X, DVec3::Z).unwrap() - 90.0).abs() < 1e-9);
+        assert!((angle(DVec3::X, DVec3::X * 5.0).unwrap()).abs() < 1e-6);
+        assert!((angle(DVec3::X, -DVec3::X).unwrap() - 180.0).abs() < 1e-9);
+        assert!((angle(DVec3::X, DVec3::new(1.0, 1.0, 0.0)).unwrap() - 45.0).abs() < 1e-9);
+        assert_eq!(angle(DVec3::ZERO, DVec3::X), None, "coincident bodies");
+        assert_eq!(measure::angle_label(63.44, &I18n::default()), "63.4°");
+        assert_ne!(font::glyph_uv('°'), font::glyph_uv('?'));
+    }
 
-        let initial_dist = cam.target.distance(target);
-        cam.update_transition(0.1); // one large step
-        let after_dist = cam.target.distance(target);
+    // ── Hover ──
 
-        assert!(
-            after_dist < initial_dist,
-            "Camera should move closer to lerp_target after update_transition"
-        );
+    #[test]
+    fn hover_picks_at_most_once_per_interval() {
+        use crate::input::hover::{Hover, Pointer};
+        let pointer = |x| Pointer {
+            x,
+            y: 20.0,
+            width: 800.0,
+            height: 600.0,
+        };
+        let mut hover = Hover::default();
+        assert_eq!(hover.due(1.0), None, "no pointer, no pick");
+
+        // Entering the canvas picks on the next frame…
+        hover.pointer_moved(pointer(10.0));
+        assert_eq!(hover.due(0.0), Some(pointer(10.0)));
+        hover.set_body(Some(3));
+        // …then moves within the interval only update the pending pointer.
+        hover.pointer_moved(pointer(11.0));
+        hover.pointer_moved(pointer(12.0));
+        assert_eq!(hover.due(HOVER_PICK_INTERVAL_SECONDS * 0.5), None);
+        assert_eq!(hover.due(HOVER_PICK_INTERVAL_SECONDS * 0.5), Some(pointer(12.0)));
+        // A resting pointer keeps being picked, as bodies move under it.
+        assert_eq!(hover.due(HOVER_PICK_INTERVAL_SECONDS), Some(pointer(12.0)));
+        assert_eq!(hover.body(), Some(3));
+
+        hover.pointer_left();
+        assert_eq!(hover.body(), None);
+        assert_eq!(hover.due(1.0), None);
+        hover.set_body(Some(1));
+        assert_eq!(hover.body(), None, "a late pick does not outlive the pointer");
     }
 
     #[test]
-    fn camera_update_transition_converges() {
-        let mut cam = Camera::new(1.0);
-        let target = glam::Vec3::new(50.0, 0.0, 0.0);
-        cam.set_target(target, 30.0);
+    fn hover_tooltip_names_the_body_and_its_distance() {
+        let en = I18n::default();
+        let fr = I18n::new(Locale::Fr);
+        assert_eq!(
+            labels::tooltip_text("mars", false, 1.524, &en),
+            "Mars · 1.52 AU from the Sun"
+        );
+        assert_eq!(labels::tooltip_text("sun", true, 3.0, &en), "Sun · 3.00 AU away");
+        assert_eq!(
+            labels::tooltip_text("earth", false, 1.0, &fr),
+            "Terre · à 1,00 ua du Soleil"
+        );
+        for c in labels::tooltip_text("sun", true, 3.0, &fr).chars() {
+            assert_ne!(font::glyph_uv(c), font::glyph_uv('?'), "{c}");
+        }
+    }
 
-        // Run many frames
-        for _ in 0..200 {
-            cam.update_transition(0.016);
+    // ── Touch gestures ──
+
+    fn touch(id: i32, x: f32, y: f32) -> crate::input::gestures::TouchPoint {
+        crate::input::gestures::TouchPoint {
+            id,
+            position: glam::Vec2::new(x, y),
         }
+    }
 
-        assert!(
-            cam.target.distance(target) < 0.1,
-            "Camera target should converge to lerp_target"
+    /// Feed `touches` to `recognizer` and collect what it recognises.
+    fn gestures_for(
+        recognizer: &mut crate::input::gestures::GestureRecognizer,
+        touches: &[crate::input::gestures::TouchPoint],
+    ) -> Vec<crate::input::gestures::Gesture> {
+        let mut out = Vec::new();
+        recognizer.touches(touches, glam::Vec2::new(400.0, 300.0), |g| out.push(g));
+        out
+    }
+
+    #[test]
+    fn taps_double_taps_and_long_presses() {
+        use crate::input::gestures::{Gesture, GestureRecognizer};
+        use crate::input::hover::Pointer;
+        let at = |x, y| Pointer {
+            x,
+            y,
+            width: 400.0,
+            height: 300.0,
+        };
+        let mut g = GestureRecognizer::default();
+        let tick = |g: &mut GestureRecognizer, dt: f32| {
+            let mut out = Vec::new();
+            g.update(dt, |gesture| out.push(gesture));
+            out
+        };
+
+        assert!(gestures_for(&mut g, &[touch(1, 100.0, 100.0)]).is_empty());
+        // Jitter within the slop is still a tap.
+        assert!(gestures_for(&mut g, &[touch(1, 104.0, 103.0)]).is_empty());
+        assert!(tick(&mut g, 0.1).is_empty());
+        assert_eq!(gestures_for(&mut g, &[]), [Gesture::Tap(at(100.0, 100.0))]);
+
+        // A second tap nearby, soon after, is a double-tap…
+        assert!(tick(&mut g, 0.1).is_empty());
+        gestures_for(&mut g, &[touch(2, 110.0, 95.0)]);
+        assert_eq!(gestures_for(&mut g, &[]), [Gesture::DoubleTap(at(110.0, 95.0))]);
+        // …and a third starts over.
+        gestures_for(&mut g, &[touch(3, 110.0, 95.0)]);
+        assert_eq!(gestures_for(&mut g, &[]), [Gesture::Tap(at(110.0, 95.0))]);
+
+        // Too late for a double-tap.
+        tick(&mut g, DOUBLE_TAP_SECONDS + 0.05);
+        gestures_for(&mut g, &[touch(4, 110.0, 95.0)]);
+        assert_eq!(gestures_for(&mut g, &[]), [Gesture::Tap(at(110.0, 95.0))]);
+        // Too far away for one.
+        gestures_for(&mut g, &[touch(5, 300.0, 95.0)]);
+        assert_eq!(gestures_for(&mut g, &[]), [Gesture::Tap(at(300.0, 95.0))]);
+
+        // Holding still fires a long-press from the frame clock, once.
+        gestures_for(&mut g, &[touch(6, 50.0, 60.0)]);
+        assert!(tick(&mut g, LONG_PRESS_SECONDS * 0.5).is_empty());
+        assert_eq!(tick(&mut g, LONG_PRESS_SECONDS * 0.5), [Gesture::LongPress(at(50.0, 60.0))]);
+        assert!(tick(&mut g, 1.0).is_empty());
+        assert!(gestures_for(&mut g, &[touch(6, 80.0, 60.0)]).is_empty());
+        assert!(gestures_for(&mut g, &[]).is_empty(), "no tap after a long-press");
+    }
+
+    #[test]
+    fn one_finger_drags_once_past_the_slop() {
+        use crate::input::gestures::{Gesture, GestureRecognizer};
+        use glam::Vec2;
+        let mut g = GestureRecognizer::default();
+        gestures_for(&mut g, &[touch(1, 100.0, 100.0)]);
+        assert!(gestures_for(&mut g, &[touch(1, 105.0, 100.0)]).is_empty());
+        // The whole movement from the touch-down is reported at once.
+        assert_eq!(
+            gestures_for(&mut g, &[touch(1, 115.0, 100.0)]),
+            [Gesture::Drag(Vec2::new(15.0, 0.0))]
         );
-        assert!(
-            (cam.distance - 30.0).abs() < 0.1,
-            "Camera distance should converge to lerp_distance"
+        assert_eq!(
+            gestures_for(&mut g, &[touch(1, 112.0, 104.0)]),
+            [Gesture::Drag(Vec2::new(-3.0, 4.0))]
         );
-        assert!(
-            cam.lerp_target.is_none(),
-            "lerp_target should be None after convergence"
+        assert!(gestures_for(&mut g, &[touch(1, 112.0, 104.0)]).is_empty());
+        assert!(gestures_for(&mut g, &[]).is_empty(), "a drag is not a tap");
+
+        g.cancel();
+        gestures_for(&mut g, &[touch(2, 10.0, 10.0)]);
+        g.cancel();
+        assert!(gestures_for(&mut g, &[]).is_empty(), "cancelled touches do not tap");
+    }
+
+    #[test]
+    fn two_fingers_pan_pinch_and_twist() {
+        use crate::input::gestures::{Gesture, GestureRecognizer};
+        use glam::Vec2;
+        let mut g = GestureRecognizer::default();
+        gestures_for(&mut g, &[touch(1, 100.0, 100.0)]);
+        assert!(gestures_for(&mut g, &[touch(1, 100.0, 100.0), touch(2, 200.0, 100.0)]).is_empty());
+
+        // Both fingers slide down: a pure pan.
+        assert_eq!(
+            gestures_for(&mut g, &[touch(1, 100.0, 120.0), touch(2, 200.0, 120.0)]),
+            [Gesture::Pan(Vec2::new(0.0, 20.0))]
         );
-        assert!(
-            cam.lerp_distance.is_none(),
-            "lerp_distance should be None after convergence"
+        // Spreading symmetrically: a pure pinch.
+        assert_eq!(
+            gestures_for(&mut g, &[touch(1, 90.0, 120.0), touch(2, 210.0, 120.0)]),
+            [Gesture::Pinch(20.0)]
+        );
+        // Turning about the midpoint, clockwise on screen (y down).
+        let out = gestures_for(&mut g, &[touch(1, 150.0, 60.0), touch(2, 150.0, 180.0)]);
+        let [Gesture::Twist(angle)] = out[..] else {
+            panic!("{out:?}");
+        };
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5, "{angle}");
+
+        // Lifting one finger carries on as a drag with the other, no tap.
+        assert!(gestures_for(&mut g, &[touch(2, 150.0, 180.0)]).is_empty());
+        assert_eq!(
+            gestures_for(&mut g, &[touch(2, 150.0, 170.0)]),
+            [Gesture::Drag(Vec2::new(0.0, -10.0))]
         );
+        assert!(gestures_for(&mut g, &[]).is_empty());
+    }
+
+    #[test]
+    fn a_jittery_pinch_neither_pans_nor_twists() {
+        use crate::input::gestures::{Gesture, GestureRecognizer};
+        let mut g = GestureRecognizer::default();
+        gestures_for(&mut g, &[touch(1, 100.0, 100.0), touch(2, 200.0, 100.0)]);
+        let mut out = Vec::new();
+        for (step, jitter) in [2.0, -3.0, 1.5, -2.5, 3.0, -1.0].into_iter().enumerate() {
+            let spread = 8.0 * (step + 1) as f32;
+            out.extend(gestures_for(
+                &mut g,
+                &[
+                    touch(1, 100.0 - spread, 100.0 + jitter),
+                    touch(2, 200.0 + spread, 100.0 - jitter * 0.5),
+                ],
+            ));
+        }
+        assert!(!out.is_empty());
+        assert!(out.iter().all(|g| matches!(g, Gesture::Pinch(_))), "{out:?}");
+        // The first pinch carries the motion held back by the slop.
+        let total: f32 = out
+            .iter()
+            .map(|g| match g {
+                Gesture::Pinch(spread) => *spread,
+                _ => 0.0,
+            })
+            .sum();
+        assert!((total - 96.0).abs() < 0.5, "{total}");
+    }
+
+    #[test]
+    fn twisting_turns_the_scene_with_the_fingers() {
+        use glam::{DVec3, Vec2};
+        let mut camera = Camera::new(1.0);
+        camera.set_pose(CameraPose {
+            target: DVec3::ZERO,
+            theta: 0.4,
+            phi: PHI_CLAMP,
+            distance: 100.0,
+        });
+        let viewport = Vec2::new(800.0, 800.0);
+        let screen = |camera: &Camera| {
+            let vp = camera.projection_matrix() * camera.view_matrix();
+            let point = relative_to_eye(DVec3::new(30.0, 0.0, 0.0), camera.eye_position());
+            labels::project_to_screen(&vp, point, viewport).unwrap() - viewport * 0.5
+        };
+        let before = screen(&camera);
+        camera.spin(0.2);
+        let turned = before.angle_to(screen(&camera));
+        // Seen from above, the scene turns clockwise on screen, like the fingers.
+        assert!((turned - 0.2).abs() < 0.02, "{turned}");
+    }
+
+    // ── Camera momentum ──
+
+    /// Total coasting distance of a `velocity` fling released after a drag
+    /// held at that speed, stepped at `fps` for `seconds`.
+    fn fling(velocity: f32, fps: u32, seconds: f32) -> f32 {
+        use crate::input::inertia::Momentum;
+        let dt = 1.0 / fps as f32;
+        let mut momentum = Momentum::new(0.3);
+        momentum.grab();
+        for _ in 0..fps {
+            momentum.track(velocity * dt);
+            momentum.update(dt);
+        }
+        momentum.release();
+        (0..(seconds * fps as f32) as u32).map(|_| momentum.update(dt)).sum()
+    }
+
+    #[test]
+    fn flings_decay_independently_of_the_framerate() {
+        let (slow, fast) = (fling(100.0, 30, 0.5), fling(100.0, 144, 0.5));
+        let exact = 100.0 * 0.3 * (1.0 - (-0.5f32 / 0.3).exp());
+        assert!((slow - exact).abs() < 0.5, "{slow} vs {exact}");
+        assert!((fast - exact).abs() < 0.5, "{fast} vs {exact}");
+        // It settles: nothing is left after a few time constants.
+        assert!((fling(100.0, 60, 3.0) - 30.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn held_controls_do_not_coast_and_wheel_notches_glide_their_full_delta() {
+        use crate::input::inertia::Momentum;
+        let mut momentum = Momentum::new(0.12);
+        momentum.grab();
+        momentum.track(50.0);
+        assert_eq!(momentum.update(1.0 / 60.0), 0.0);
+        // A control released after resting does not fling.
+        for _ in 0..60 {
+            momentum.update(1.0 / 60.0);
+        }
+        momentum.release();
+        assert!(momentum.update(1.0 / 60.0).abs() < 1e-3);
+
+        let mut wheel = Momentum::new(0.12);
+        wheel.impulse(100.0);
+        wheel.impulse(100.0);
+        let total: f32 = (0..120).map(|_| wheel.update(1.0 / 60.0)).sum();
+        assert!((total - 200.0).abs() < 5.0, "{total}");
+        assert_eq!(wheel.update(1.0 / 60.0), 0.0);
+    }
+
+    #[test]
+    fn wheel_events_tell_mice_from_trackpads() {
+        use crate::input::inertia::{DOM_DELTA_LINE, DOM_DELTA_PIXEL, WheelDevice, wheel_pixels};
+        assert_eq!(WheelDevice::classify(DOM_DELTA_LINE, 0.0, 3.0, false), WheelDevice::Wheel);
+        assert_eq!(WheelDevice::classify(DOM_DELTA_PIXEL, 0.0, 100.0, false), WheelDevice::Wheel);
+        assert_eq!(WheelDevice::classify(DOM_DELTA_PIXEL, 0.0, 4.0, false), WheelDevice::Trackpad);
+        assert_eq!(WheelDevice::classify(DOM_DELTA_PIXEL, 2.0, 100.0, false), WheelDevice::Trackpad);
+        assert_eq!(WheelDevice::classify(DOM_DELTA_PIXEL, 0.0, 2.5, true), WheelDevice::Pinch);
+        assert!(WheelDevice::Wheel.glides() && !WheelDevice::Trackpad.glides());
+        assert_eq!(wheel_pixels(3.0, DOM_DELTA_LINE), 3.0 * crate::constants::WHEEL_LINE_HEIGHT_PX);
+        assert_eq!(wheel_pixels(-7.0, DOM_DELTA_PIXEL), -7.0);
+    }
+
+    // ── GPU picking ──
+
+    #[test]
+    fn pick_ids_round_trip_and_background_is_nothing() {
+        use crate::renderer::picking::PickTarget;
+        for target in [PickTarget::Body(0), PickTarget::Orbit(3), PickTarget::Trail(300)] {
+            assert_eq!(PickTarget::decode(target.encode()), Some(target));
+            assert_eq!(target.color().map(|c| (c * 255.0).round() as u8), target.encode());
+        }
+        assert_eq!(PickTarget::decode([0, 0, 0, 0]), None);
+        assert_eq!(PickTarget::Orbit(5).body(), 5);
+    }
+
+    #[test]
+    fn pick_window_maps_css_pixels_and_clamps_to_the_buffer() {
+        use crate::renderer::picking::PickWindow;
+        use glam::Vec2;
+        let canvas = Vec2::new(400.0, 300.0);
+        // A 2× drawing buffer; GL rows count from the bottom.
+        let w = PickWindow::around(Vec2::new(100.0, 50.0), canvas, (800, 600), 4).unwrap();
+        assert_eq!((w.x, w.y, w.width, w.height), (196, 495, 9, 9));
+        assert_eq!(w.center, (4, 4));
+        assert_eq!(w.byte_len(), 9 * 9 * 4);
+
+        let corner = PickWindow::around(Vec2::new(0.0, 0.0), canvas, (800, 600), 4).unwrap();
+        assert_eq!((corner.x, corner.y, corner.width, corner.height), (0, 595, 5, 5));
+        assert_eq!(corner.center, (0, 4));
+
+        assert_eq!(PickWindow::around(Vec2::new(400.0, 10.0), canvas, (800, 600), 4), None);
+        assert_eq!(PickWindow::around(Vec2::new(1.0, 1.0), Vec2::ZERO, (800, 600), 4), None);
+    }
+
+    #[test]
+    fn pick_prefers_bodies_then_the_nearest_line_within_the_radius() {
+        use crate::renderer::picking::{PickTarget, PickWindow};
+        let window = PickWindow {
+            x: 0,
+            y: 0,
+            width: 7,
+            height: 7,
+            center: (3, 3),
+            radius: 3,
+        };
+        let mut pixels = vec![0u8; window.byte_len()];
+        let paint = |pixels: &mut [u8], x: usize, y: usize, target: PickTarget| {
+            let i = (y * 7 + x) * 4;
+            pixels[i..i + 4].copy_from_slice(&target.encode());
+        };
+        assert_eq!(window.nearest(&vec![0u8; window.byte_len()]), None);
+
+        // A body in a corner is out of reach (distance √18 > 3).
+        paint(&mut pixels, 0, 0, PickTarget::Body(1));
+        assert_eq!(window.nearest(&pixels), None);
+
+        paint(&mut pixels, 3, 5, PickTarget::Trail(4));
+        paint(&mut pixels, 3, 1, PickTarget::Orbit(2));
+        paint(&mut pixels, 4, 3, PickTarget::Orbit(6));
+        assert_eq!(window.nearest(&pixels), Some(PickTarget::Orbit(6)), "nearest line");
+
+        paint(&mut pixels, 6, 3, PickTarget::Body(3));
+        assert_eq!(window.nearest(&pixels), Some(PickTarget::Body(3)), "bodies first");
+    }
+
+    // ── Key bindings & actions ──
+
+    #[test]
+    fn default_bindings_cover_previous_shortcuts() {
+        let b = KeyBindings::default();
+        assert_eq!(b.action_for(" "), Some(&Action::TogglePause));
+        assert_eq!(b.action_for("R"), Some(&Action::ResetSpeed));
+        assert_eq!(b.action_for("r"), Some(&Action::ResetSpeed));
+        assert_eq!(b.action_for("ArrowUp"), Some(&Action::SpeedUp));
+        assert_eq!(b.action_for("3"), Some(&Action::SelectBody("Earth".into())));
+        assert_eq!(b.action_for("z"), None);
+        // Every default body shortcut names a real body.
+        let bodies = create_solar_system();
+        for key in ["1", "2", "3", "4", "5", "6", "7", "8"] {
+            let Some(Action::SelectBody(name)) = b.action_for(key) else {
+                panic!("{key} should select a body");
+            };
+            assert!(bodies.iter().any(|body| body.id == name), "{name} not found");
+        }
+    }
+
+    #[test]
+    fn binding_overrides_remap_and_unbind() {
+        let mut b = KeyBindings::default();
+        b.apply("# remap pause\np = toggle_pause\nSpace = none\nr = select_body:Mars")
+            .unwrap();
+        assert_eq!(b.action_for("P"), Some(&Action::TogglePause));
+        assert_eq!(b.action_for(" "), None);
+        assert_eq!(b.action_for("r"), Some(&Action::SelectBody("Mars".into())));
+        assert_eq!(b.keys_for(&Action::TogglePause), vec!["p"]);
+    }
+
+    #[test]
+    fn binding_errors_report_line_and_leave_table_untouched() {
+        let mut b = KeyBindings::default();
+        let before = b.clone();
+        let err = b.apply("p = toggle_pause\n\nx = launch_rocket").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.to_string().contains("launch_rocket"));
+        assert_eq!(b, before);
+        assert!(KeyBindings::parse("no equals sign").is_err());
+    }
+
+    #[test]
+    fn action_ids_round_trip() {
+        for action in [
+            Action::TogglePause,
+            Action::SpeedUp,
+            Action::SpeedDown,
+            Action::ResetSpeed,
+            Action::ResetDefaults,
+            Action::Recenter,
+            Action::TopDownView,
+            Action::ToggleFlyMode,
+            Action::Fly(FlyDirection::Left),
+            Action::SaveBookmark,
+            Action::NextBookmark,
+            Action::ToggleTour,
+            Action::SelectBody("Saturn".into()),
+            Action::Deselect,
+            Action::ClearMeasurement,
+            Action::ToggleHud,
+            Action::CycleLabels,
+            Action::ToggleOrbits,
+            Action::ToggleTrails,
+            Action::ToggleHelp,
+        ] {
+            assert_eq!(Action::parse(&action.id()), Some(action));
+        }
+        assert_eq!(Action::parse("select_body:"), None);
+    }
+
+    #[test]
+    fn help_rows_group_keys_by_action() {
+        let rows = KeyBindings::default().help_rows(&I18n::default());
+        let speed_up = rows.iter().find(|r| r.action == "speed_up").unwrap();
+        assert_eq!(speed_up.keys, "+ / ↑");
+        assert_eq!(speed_up.section, "Simulation");
+        assert_eq!(rows.iter().filter(|r| r.action == "speed_up").count(), 1);
+        // Sections stay contiguous.
+        let sections: Vec<&str> = rows.iter().map(|r| r.section.as_str()).collect();
+        let mut deduped = sections.clone();
+        deduped.dedup();
+        assert_eq!(
+            deduped,
+            ["Camera", "Fly", "Tours", "Simulation", "Planets", "Display"]
+        );
+    }
+
+    /// Records what the dispatcher asked for, standing in for `AppState`.
+    #[derive(Default)]
+    struct MockTarget {
+        time: SimulationTime,
+        time_changes: usize,
+        selected: Vec<String>,
+        calls: Vec<&'static str>,
+        thrust: Vec<(FlyDirection, bool)>,
+    }
+
+    impl ActionTarget for MockTarget {
+        fn time(&mut self) -> &mut SimulationTime {
+            &mut self.time
+        }
+        fn time_changed(&mut self) {
+            self.time_changes += 1;
+        }
+        fn select_body(&mut self, name: &str) {
+            self.selected.push(name.to_string());
+        }
+        fn deselect(&mut self) {
+            self.calls.push("deselect");
+        }
+        fn clear_measurement(&mut self) {
+            self.calls.push("clear_measurement");
+        }
+        fn recenter(&mut self) {
+            self.calls.push("recenter");
+        }
+        fn top_down_view(&mut self) {
+            self.calls.push("top_down_view");
+        }
+        fn reset_defaults(&mut self) {
+            self.calls.push("reset_defaults");
+        }
+        fn toggle_fly_mode(&mut self) {
+            self.calls.push("toggle_fly_mode");
+        }
+        fn fly_thrust(&mut self, direction: FlyDirection, pressed: bool) {
+            self.thrust.push((direction, pressed));
+        }
+        fn save_bookmark(&mut self) {
+            self.calls.push("save_bookmark");
+        }
+        fn next_bookmark(&mut self) {
+            self.calls.push("next_bookmark");
+        }
+        fn toggle_tour(&mut self) {
+            self.calls.push("toggle_tour");
+        }
+        fn cycle_labels(&mut self) {
+            self.calls.push("cycle_labels");
+        }
+        fn toggle_orbits(&mut self) {
+            self.calls.push("toggle_orbits");
+        }
+        fn toggle_trails(&mut self) {
+            self.calls.push("toggle_trails");
+        }
+        fn toggle_hud(&mut self) {
+            self.calls.push("toggle_hud");
+        }
+        fn toggle_help(&mut self) {
+            self.calls.push("toggle_help");
+        }
+    }
+
+    #[test]
+    fn dispatch_drives_time_and_refreshes_once() {
+        let bindings = KeyBindings::default();
+        let mut target = MockTarget::default();
+        let press = |target: &mut MockTarget, key: &str| {
+            let action = bindings.action_for(key).unwrap().clone();
+            actions::dispatch(target, &action);
+        };
+
+        press(&mut target, " ");
+        assert!(target.time.paused);
+        assert_eq!(target.time_changes, 1);
+
+        press(&mut target, "+");
+        assert!(target.time.days_per_second > DEFAULT_DAYS_PER_SECOND);
+        press(&mut target, "r");
+        assert_eq!(target.time.days_per_second, DEFAULT_DAYS_PER_SECOND);
+        assert!(!target.time.paused);
+        assert_eq!(target.time_changes, 3);
+
+        press(&mut target, "5");
+        press(&mut target, "Escape");
+        press(&mut target, "l");
+        press(&mut target, "m");
+        assert_eq!(target.selected, ["Jupiter"]);
+        assert_eq!(target.calls, ["deselect", "cycle_labels", "clear_measurement"]);
+        assert_eq!(target.time_changes, 3, "Non-time actions skip the HUD refresh");
+    }
+
+    #[test]
+    fn held_fly_keys_press_and_release() {
+        let bindings = KeyBindings::default();
+        let mut target = MockTarget::default();
+        let w = bindings.action_for("W").unwrap().clone();
+        assert!(w.is_held());
+        actions::dispatch(&mut target, &w);
+        actions::release(&mut target, &w);
+        // Releasing a one-shot action does nothing.
+        actions::release(&mut target, bindings.action_for("f").unwrap());
+        assert_eq!(
+            target.thrust,
+            [(FlyDirection::Forward, true), (FlyDirection::Forward, false)]
+        );
+        assert!(target.calls.is_empty());
+    }
+
+    // ── Fly camera ──
+
+    #[test]
+    fn fly_mode_round_trip_keeps_the_view() {
+        let mut camera = Camera::new(1.5);
+        camera.target = glam::DVec3::new(120.0, -4.0, 33.0);
+        let anchor = glam::DVec3::new(1.0e6, 0.0, -2.0e5);
+        let eye = camera.eye_position();
+        let view = camera.view_matrix();
+
+        let fly = FlyCamera::from_orbit(&camera, anchor);
+        fly.apply_to(&mut camera, anchor);
+        assert!(camera.eye_position().distance(eye) < 1e-4);
+        assert!(camera.view_matrix().abs_diff_eq(view, 1e-5));
+    }
+
+    #[test]
+    fn fly_camera_moves_forward_and_looks_right() {
+        let camera = Camera::new(1.0);
+        let mut fly = FlyCamera::from_orbit(&camera, glam::DVec3::ZERO);
+        let start = fly.offset;
+        let input = FlyInput {
+            thrust: glam::Vec3::Z,
+            ..FlyInput::default()
+        };
+        for _ in 0..120 {
+            fly.update(1.0 / 60.0, input, 10.0);
+        }
+        let moved = (fly.offset - start).as_vec3();
+        assert!(moved.normalize().dot(fly.forward()) > 0.999);
+        assert!(moved.length() > 10.0 && moved.length() < 20.0);
+
+        let right = fly.right();
+        let before = fly.forward();
+        fly.look(50.0, 0.0);
+        assert!(fly.forward().dot(right) > before.dot(right));
+    }
+
+    #[test]
+    fn fly_held_keys_combine() {
+        let mut fly = FlyCamera::from_orbit(&Camera::new(1.0), glam::DVec3::ZERO);
+        fly.set_held(FlyDirection::Forward, true);
+        fly.set_held(FlyDirection::Left, true);
+        assert_eq!(fly.keyboard_thrust(), glam::Vec3::new(-1.0, 0.0, 1.0));
+        fly.release_all();
+        assert_eq!(fly.keyboard_thrust(), glam::Vec3::ZERO);
+    }
+
+    #[test]
+    fn cruise_speed_scales_with_clearance() {
+        let bodies = create_solar_system();
+        let earth = bodies.iter().find(|b| b.id == "Earth").unwrap();
+        let near = earth.position + glam::DVec3::X * (earth.display_radius as f64 + 1.0);
+        let far = earth.position + glam::DVec3::Y * 300.0;
+        let (slow, fast) = (cruise_speed(near, &bodies), cruise_speed(far, &bodies));
+        assert!(slow < fast, "{slow} should be below {fast}");
+        assert_eq!(cruise_speed(earth.position, &bodies), FLY_MIN_SPEED);
+        assert_eq!(cruise_speed(glam::DVec3::splat(1.0e7), &bodies), FLY_MAX_SPEED);
+    }
+
+    #[test]
+    fn gamepad_deadzone_and_mapping() {
+        assert_eq!(gamepad::apply_deadzone(glam::Vec2::new(0.1, -0.05)), glam::Vec2::ZERO);
+        let full = gamepad::apply_deadzone(glam::Vec2::new(1.0, 0.0));
+        assert!((full.x - 1.0).abs() < 1e-6);
+
+        let mut buttons = [0.0; 17];
+        buttons[3] = 1.0;
+        buttons[7] = 0.5;
+        let reading = gamepad::read(&[0.0, -1.0, 1.0, 0.0], &buttons);
+        assert!(reading.toggle_fly);
+        assert!((reading.input.thrust - glam::Vec3::new(0.0, 0.5, 1.0)).length() < 1e-6);
+        assert!(reading.input.look_rate.x > 0.0 && reading.input.look_rate.y == 0.0);
+        assert_eq!(gamepad::read(&[], &[]), gamepad::GamepadReading::default());
+    }
+
+    // ── Bookmarks & tours ──
+
+    #[test]
+    fn easing_curves_hit_endpoints_and_rise() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6);
+            let samples: Vec<f32> = (0..=20).map(|i| easing.apply(i as f32 / 20.0)).collect();
+            assert!(samples.windows(2).all(|w| w[1] >= w[0]), "{easing:?} not monotonic");
+        }
+        assert!(Easing::EaseIn.apply(0.25) < 0.25 && Easing::EaseOut.apply(0.25) > 0.25);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pose_interpolation_takes_the_short_way_round() {
+        let from = CameraPose {
+            target: glam::DVec3::ZERO,
+            theta: 3.0,
+            phi: 0.0,
+            distance: 10.0,
+        };
+        let to = CameraPose {
+            target: glam::DVec3::new(100.0, 0.0, 0.0),
+            theta: -3.0,
+            phi: 1.0,
+            distance: 1000.0,
+        };
+        let mid = from.interpolate(&to, 0.5);
+        // 3.0 → -3.0 crosses ±π rather than sweeping through 0.
+        assert!((mid.theta - std::f32::consts::PI).abs() < 0.05, "{}", mid.theta);
+        assert!((mid.distance - 100.0).abs() < 1e-3, "distance is geometric");
+        assert_eq!(mid.target, glam::DVec3::new(50.0, 0.0, 0.0));
+        let start = from.interpolate(&to, 0.0);
+        assert!((start.theta - from.theta).abs() < 1e-5 && start.phi.abs() < 1e-5);
+    }
+
+    #[test]
+    fn timed_transition_eases_and_lands_exactly() {
+        let mut cam = Camera::new(1.0);
+        let start = cam.pose();
+        let to = CameraPose {
+            target: glam::DVec3::new(40.0, 0.0, 0.0),
+            theta: 1.0,
+            phi: 0.2,
+            distance: 12.0,
+        };
+        cam.start_transition(to, 2.0, Easing::EaseIn);
+        cam.update_transition(1.0);
+        let halfway = start.interpolate(&to, Easing::EaseIn.apply(0.5));
+        assert!(cam.target.distance(halfway.target) < 1e-6);
+        // A moving target is tracked until arrival.
+        let moved = glam::DVec3::new(41.0, 0.0, 0.5);
+        cam.follow(moved);
+        cam.update_transition(1.5);
+        assert!(cam.transition.is_none());
+        assert_eq!(cam.target, moved);
+        assert_eq!((cam.theta, cam.phi, cam.distance), (1.0, 0.2, 12.0));
+        // With no transition running, follow() falls back to the lerp.
+        cam.follow(glam::DVec3::ZERO);
+        assert_eq!(cam.lerp_target, Some(glam::DVec3::ZERO));
+    }
+
+    #[test]
+    fn bundled_tour_is_valid_and_names_real_bodies() {
+        let tour = Tour::from_json(tour::DEFAULT_TOUR).unwrap();
+        let bodies = create_solar_system();
+        for bookmark in &tour.bookmarks {
+            assert!(
+                bodies.iter().any(|b| b.id.eq_ignore_ascii_case(&bookmark.body)),
+                "{} not found",
+                bookmark.body
+            );
+        }
+    }
+
+    #[test]
+    fn tour_validation_errors() {
+        let bookmarks = r#""bookmarks": [{ "name": "a", "body": "Earth", "theta": 0, "phi": 0, "distance": 10 }]"#;
+        assert!(matches!(Tour::from_json("{"), Err(TourError::Parse(_))));
+        assert_eq!(
+            Tour::from_json(&format!(r#"{{ "name": "t", {bookmarks}, "stops": [] }}"#)),
+            Err(TourError::Empty)
+        );
+        assert_eq!(
+            Tour::from_json(&format!(
+                r#"{{ "name": "t", {bookmarks}, "stops": [{{ "bookmark": "a" }}, {{ "bookmark": "b" }}] }}"#
+            )),
+            Err(TourError::UnknownBookmark {
+                stop: 1,
+                bookmark: "b".into()
+            })
+        );
+        assert_eq!(
+            Tour::from_json(&format!(
+                r#"{{ "name": "t", {bookmarks}, "stops": [{{ "bookmark": "a", "hold": -1 }}] }}"#
+            )),
+            Err(TourError::InvalidDuration { stop: 0 })
+        );
+        let ok = Tour::from_json(&format!(
+            r#"{{ "name": "t", {bookmarks}, "stops": [{{ "bookmark": "a", "easing": "linear" }}] }}"#
+        ))
+        .unwrap();
+        assert_eq!(ok.stops[0].travel, TOUR_DEFAULT_TRAVEL_SECONDS);
+        assert_eq!(ok.stops[0].easing, Easing::Linear);
+    }
+
+    #[test]
+    fn tour_player_sequences_stops() {
+        let tour = Tour::from_json(
+            r#"{ "name": "t",
+                 "bookmarks": [{ "name": "a", "body": "Sun", "theta": 0, "phi": 0, "distance": 50 }],
+                 "stops": [{ "bookmark": "a", "travel": 1, "hold": 1 }, { "bookmark": "a", "travel": 2 }] }"#,
+        )
+        .unwrap();
+        let mut player = TourPlayer::new(tour);
+        assert_eq!(player.advance(0.5), TourStep::Begin(0));
+        assert_eq!(player.advance(1.5), TourStep::Continue);
+        assert_eq!(player.advance(0.75), TourStep::Begin(1));
+        assert_eq!(player.stop(1).unwrap().1.name, "a");
+        assert_eq!(player.advance(1.0), TourStep::Continue);
+        // 0.25 s carried over from stop 0, plus 1.0 + 0.75 = 2.0.
+        assert_eq!(player.advance(0.75), TourStep::Finished);
+        assert_eq!(player.advance(1.0), TourStep::Finished);
+    }
+
+    #[test]
+    fn bookmark_json_round_trip() {
+        let mut cam = Camera::new(1.0);
+        cam.theta = 0.7;
+        let mut time = SimulationTime::new();
+        time.current_days = 1234.5;
+        let bookmark = Bookmark::capture("Mars 1", "Mars", &cam, &time);
+        let json = serde_json::to_string(&bookmark).unwrap();
+        assert_eq!(serde_json::from_str::<Bookmark>(&json).unwrap(), bookmark);
+        let pose = bookmark.pose(glam::DVec3::X);
+        assert_eq!((pose.target, pose.theta), (glam::DVec3::X, 0.7));
+    }
+
+    // ── URL state ──
+
+    #[test]
+    fn iso_dates_map_to_j2000_days() {
+        assert_eq!(url_state::days_to_iso(0.0), "2000-01-01T12:00:00Z");
+        assert_eq!(url_state::iso_to_days("2000-01-01T12:00Z"), Some(0.0));
+        assert_eq!(url_state::iso_to_days("2030-05-01"), Some(11_077.5));
+        assert_eq!(url_state::days_to_iso(11_077.5), "2030-05-01T00:00:00Z");
+        assert_eq!(url_state::iso_to_days("1999-12-31T12:00:00Z"), Some(-1.0));
+        assert_eq!(url_state::days_to_iso(-36_524.0), "1900-01-01T12:00:00Z");
+        assert!(url_state::iso_to_days("2024-02-29").is_some());
+        for bad in ["2023-02-29", "2030-13-01", "2030-05-01T24:00", "2030-05", "yesterday"] {
+            assert_eq!(url_state::iso_to_days(bad), None, "{bad}");
+        }
+        for days in [-10_000.25, 0.5, 123.456, 11_077.5, 40_000.0] {
+            let back = url_state::iso_to_days(&url_state::days_to_iso(days)).unwrap();
+            assert!((back - days).abs() < 1.0 / 86_400.0, "{days} → {back}");
+        }
+    }
+
+    #[test]
+    fn view_state_round_trips_through_the_hash() {
+        let view = ViewState {
+            days: Some(11_077.5),
+            speed: Some(0.5),
+            paused: Some(true),
+            body: Some("Jupiter".into()),
+            locked: Some(true),
+            theta: Some(-0.25),
+            phi: Some(PHI_CLAMP),
+            distance: Some(24.0),
+            layers: Some(Layers {
+                orbits: true,
+                trails: false,
+            }),
+            labels: Some(labels::LabelMode::NamesAndDistance),
+        };
+        let hash = view.to_hash();
+        assert_eq!(
+            hash,
+            "#date=2030-05-01T00:00:00Z&speed=0.5&paused=1&body=Jupiter&lock=1\
+             &theta=-0.250&phi=1.400&dist=24.00&layers=orbits&labels=distance"
+        );
+        assert_eq!(ViewState::from_hash(&hash).unwrap(), view);
+        // Encoding is stable, so unchanged views never rewrite the URL.
+        assert_eq!(ViewState::from_hash(&hash).unwrap().to_hash(), hash);
+    }
+
+    #[test]
+    fn view_state_accepts_short_links_and_rejects_bad_values() {
+        let view = ViewState::from_hash("date=2030-05-01&body=Comet%20Halley&layers=&future=1").unwrap();
+        assert_eq!(view.days, Some(11_077.5));
+        assert_eq!(view.body.as_deref(), Some("Comet Halley"));
+        assert_eq!(
+            view.layers,
+            Some(Layers {
+                orbits: false,
+                trails: false
+            })
+        );
+        assert_eq!(view.speed, None);
+        assert!(view.to_hash().contains("body=Comet%20Halley"));
+        assert_eq!(ViewState::from_hash("").unwrap(), ViewState::default());
+
+        let err = ViewState::from_hash("#speed=-2").unwrap_err();
+        assert_eq!((err.key.as_str(), err.value.as_str()), ("speed", "-2"));
+        for bad in ["paused=maybe", "dist=0", "theta=NaN", "layers=planets", "labels=loud", "date=soon"] {
+            assert!(ViewState::from_hash(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn label_mode_ids_round_trip() {
+        let mut mode = labels::LabelMode::default();
+        for _ in 0..3 {
+            assert_eq!(labels::LabelMode::parse(mode.id()), Some(mode));
+            mode = mode.next();
+        }
+    }
+
+    // ── Sessions ──
+
+    fn sample_session() -> Session {
+        let view = ViewState {
+            days: Some(4_321.5),
+            speed: Some(10.0),
+            paused: Some(true),
+            body: Some("Saturn".into()),
+            locked: Some(true),
+            theta: Some(1.25),
+            phi: Some(-0.5),
+            distance: Some(40.0),
+            layers: Some(Layers {
+                orbits: false,
+                trails: true,
+            }),
+            labels: Some(labels::LabelMode::Off),
+        };
+        let bookmark = Bookmark {
+            name: "rings".into(),
+            body: "Saturn".into(),
+            theta: 0.3,
+            phi: 0.1,
+            distance: 20.0,
+            time: None,
+            speed: None,
+        };
+        Session::capture(&view, &[bookmark])
+    }
+
+    #[test]
+    fn session_round_trips_through_json() {
+        let session = sample_session();
+        assert_eq!(session.version, persistence::SCHEMA_VERSION);
+        let back = Session::from_json(&session.to_json()).unwrap();
+        assert_eq!(back, session);
+        // The restored view is exactly what was captured.
+        let view = back.view();
+        assert_eq!(view.body.as_deref(), Some("Saturn"));
+        assert_eq!(view.locked, Some(true));
+        assert_eq!(view.labels, Some(labels::LabelMode::Off));
+        assert_eq!(view.to_hash(), Session::capture(&view, &[]).view().to_hash());
+    }
+
+    #[test]
+    fn session_versions_are_checked_and_migrated() {
+        let json = sample_session().to_json();
+        let future = json.replace("\"version\":1", "\"version\":99");
+        assert_eq!(
+            Session::from_json(&future),
+            Err(SessionError::UnsupportedVersion(99))
+        );
+        assert_eq!(
+            Session::from_json(&json.replace("\"version\":1,", "")),
+            Err(SessionError::MissingVersion)
+        );
+        assert!(matches!(Session::from_json("[]"), Err(SessionError::Parse(_))));
+
+        // A v1 → v2 migration runs on v1 input before deserializing.
+        fn add_speed(object: &mut serde_json::Map<String, serde_json::Value>) {
+            object.entry("speed").or_insert(1.0.into());
+        }
+        let without_speed = json.replace("\"speed\":10.0,", "");
+        let upgraded = Session::from_json_with(&without_speed, &[add_speed]).unwrap();
+        assert_eq!((upgraded.version, upgraded.speed), (2, 1.0));
+    }
+
+    #[test]
+    fn session_view_drops_unusable_values() {
+        let mut session = sample_session();
+        session.speed = -1.0;
+        session.distance = f32::NAN;
+        session.labels = "shouting".into();
+        session.body = None;
+        let view = session.view();
+        assert_eq!((view.speed, view.distance, view.labels), (None, None, None));
+        assert_eq!(view.locked, None);
+        assert_eq!(view.days, Some(4_321.5));
+    }
+
+    // ── JavaScript API ──
+
+    #[test]
+    fn api_events_and_fly_to_options_parse() {
+        assert_eq!(EventKind::parse("select"), Some(EventKind::Select));
+        assert_eq!(EventKind::parse("deselect"), Some(EventKind::Deselect));
+        assert_eq!(EventKind::parse("click"), None);
+
+        let options: FlyToOptions =
+            serde_json::from_str(r#"{ "distance": 4, "duration": 3, "easing": "ease_out" }"#).unwrap();
+        assert_eq!(options.distance, Some(4.0));
+        assert_eq!(options.easing, Some(Easing::EaseOut));
+        assert_eq!(options.theta, None);
+        assert!(serde_json::from_str::<FlyToOptions>(r#"{ "zoom": 2 }"#).is_err());
+    }
+
+    #[test]
+    fn fly_to_options_reject_non_finite_and_out_of_range_values() {
+        let options: FlyToOptions =
+            serde_json::from_str(r#"{ "theta": 1, "phi": -0.5, "distance": 4, "duration": 0 }"#)
+                .unwrap();
+        assert_eq!(options.validate(), Ok(()));
+        assert_eq!(FlyToOptions::default().validate(), Ok(()));
+
+        for bad in [
+            FlyToOptions { theta: Some(f32::NAN), ..FlyToOptions::default() },
+            FlyToOptions { phi: Some(f32::INFINITY), ..FlyToOptions::default() },
+            FlyToOptions { distance: Some(f32::NEG_INFINITY), ..FlyToOptions::default() },
+            FlyToOptions { duration: Some(f32::NAN), ..FlyToOptions::default() },
+            FlyToOptions { distance: Some(0.0), ..FlyToOptions::default() },
+            FlyToOptions { duration: Some(-1.0), ..FlyToOptions::default() },
+        ] {
+            assert!(bad.validate().is_err(), "{bad:?}");
+        }
+        let message = FlyToOptions { theta: Some(f32::NAN), ..FlyToOptions::default() }
+            .validate()
+            .unwrap_err();
+        assert!(message.contains("theta"), "{message}");
+    }
+
+    #[test]
+    fn body_state_is_heliocentric_in_au() {
+        let mut sim = Simulation::new(create_solar_system());
+        sim.time.current_days = 5_000.0;
+        sim.update(0.0);
+        let sun = sim.bodies.iter().find(|b| b.is_star).unwrap().position;
+        let earth = sim.bodies.iter().find(|b| b.id == "Earth").unwrap();
+        let i18n = I18n::new(Locale::Fr);
+        let state = BodyState::new(earth, sun, true, false, &i18n);
+        assert!((state.distance_au - earth.semi_major_axis_au).abs() < 1e-6);
+        assert!(state.selected && !state.locked);
+        assert_eq!((state.name.as_str(), state.display_name.as_str()), ("Earth", "Terre"));
+        assert!(!BodyState::new(earth, sun, false, true, &i18n).locked);
+    }
+
+    #[test]
+    fn viewer_options_default_to_a_self_contained_viewer() {
+        let options: ViewerOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options, ViewerOptions::default());
+        assert!(!options.page_controls && !options.url_hash && !options.splash);
+        assert_eq!(options.storage_key, None);
+        assert_eq!(options.catalogue().unwrap().len(), create_solar_system().len());
+
+        let options: ViewerOptions = serde_json::from_str(
+            r#"{ "texturePath": "assets/", "pageControls": true, "storageKey": "left",
+                 "sphereKind": "uv" }"#,
+        )
+        .unwrap();
+        assert_eq!(options.texture_path, "assets/");
+        assert!(options.page_controls);
+        assert_eq!(options.storage_key.as_deref(), Some("left"));
+        assert_eq!(options.sphere_kind, mesh::SphereKind::Uv);
+        assert_eq!(ViewerOptions::default().sphere_kind, mesh::SphereKind::CubeSphere);
+    }
+
+    #[test]
+    fn viewer_options_pick_bodies_and_initial_view() {
+        let options = ViewerOptions {
+            bodies: Some(vec!["earth".into(), "Mars".into()]),
+            view: Some("date=1969-07-20&body=Earth".into()),
+            ..ViewerOptions::default()
+        };
+        let names: Vec<&str> = options.catalogue().unwrap().iter().map(|b| b.id).collect();
+        assert_eq!(names, ["Sun", "Earth", "Mars"]);
+        let view = options.initial_view().unwrap().unwrap();
+        assert_eq!(view.body.as_deref(), Some("Earth"));
+
+        let bad = ViewerOptions {
+            bodies: Some(vec!["Vulcan".into()]),
+            view: Some("date=someday".into()),
+            ..ViewerOptions::default()
+        };
+        assert_eq!(bad.catalogue().unwrap_err(), "No body named Vulcan");
+        assert!(bad.initial_view().is_err());
+    }
+
+    #[test]
+    fn unix_millis_map_to_j2000_days() {
+        // 2000-01-01T12:00:00Z
+        assert_eq!(url_state::unix_millis_to_days(946_728_000_000.0), 0.0);
+        assert_eq!(url_state::unix_millis_to_days(946_728_000_000.0 + 86_400_000.0), 1.0);
+    }
+
+    #[test]
+    fn frame_dt_starts_small_and_is_clamped() {
+        // First frame, and the first after a hidden tab resumes.
+        assert_eq!(frame_dt(0.0, 123_456.0), FIRST_FRAME_DT);
+        assert!((frame_dt(1000.0, 1016.0) - 0.016).abs() < 1e-12);
+        // A long stall never becomes one huge simulation step.
+        assert_eq!(frame_dt(1000.0, 61_000.0), MAX_FRAME_DT);
+        // Timestamps going backwards do not run time in reverse.
+        assert_eq!(frame_dt(2000.0, 1000.0), 0.0);
+    }
+
+    // ── HUD ──
+
+    fn hud_snapshot() -> HudSnapshot {
+        HudSnapshot {
+            locale: "en",
+            date: "2000-01-01T12:00:00Z".into(),
+            date_label: "Jan 1, 2000".into(),
+            days: 0.0,
+            speed: SpeedState::new(1.0, false, &I18n::default()),
+            fps: FpsMeter::default().update(1.0 / 60.0),
+            gpu: Default::default(),
+            fly_mode: false,
+            selected: None,
+            live: None,
+            distances: Default::default(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hud_speed_label_switches_to_years() {
+        let en = I18n::default();
+        assert_eq!(hud::speed_label(2.0, false, &en), "×2.0 d/s");
+        assert_eq!(hud::speed_label(730.5, false, &en), "×2.0 yr/s");
+        assert_eq!(hud::speed_label(730.5, true, &en), "Paused");
+        let fr = I18n::new(Locale::Fr);
+        assert_eq!(hud::speed_label(2.5, false, &fr), "×2,5 j/s");
+        assert_eq!(hud::speed_label(730.5, true, &fr), "En pause");
+    }
+
+    #[test]
+    fn hud_fps_average_is_smoothed() {
+        let mut meter = FpsMeter::default();
+        let first = meter.update(1.0 / 60.0);
+        assert!((first.average - 60.0).abs() < 1e-3);
+        assert!((first.frame_ms - 1000.0 / 60.0).abs() < 1e-3);
+        let slow = meter.update(1.0 / 30.0);
+        assert!((slow.current - 30.0).abs() < 1e-3);
+        assert!(slow.average < 60.0 && slow.average > 55.0);
+        assert_eq!(meter.update(0.0).current, 0.0);
+    }
+
+    #[test]
+    fn hud_throttle_holds_back_telemetry_only() {
+        let mut throttle = HudThrottle::default();
+        let mut snapshot = hud_snapshot();
+        assert!(throttle.ready(&snapshot, 0.016), "first snapshot is sent");
+
+        // The clock moving on is telemetry: sent at most every interval.
+        snapshot.days += 0.016;
+        assert!(!throttle.ready(&snapshot, 0.016));
+        assert!(throttle.ready(&snapshot, HUD_UPDATE_INTERVAL_SECONDS));
+        assert!(
+            !throttle.ready(&snapshot, HUD_UPDATE_INTERVAL_SECONDS),
+            "an unchanged snapshot is not resent"
+        );
+
+        // Pausing, events and invalidation go out at once.
+        snapshot.speed = SpeedState::new(1.0, true, &I18n::default());
+        assert!(throttle.ready(&snapshot, 0.0));
+        snapshot.events.push(HudEvent::Deselect);
+        assert!(throttle.ready(&snapshot, 0.0));
+        snapshot.events.clear();
+        assert!(throttle.ready(&snapshot, 0.0), "an event ending is a change too");
+        throttle.invalidate();
+        assert!(throttle.ready(&snapshot, 0.0));
+    }
+
+    #[test]
+    fn hud_snapshot_serialises_camel_case_events() {
+        let mut snapshot = hud_snapshot();
+        snapshot.events.push(HudEvent::Select {
+            body: "Earth".into(),
+        });
+        snapshot.events.push(HudEvent::TourFinished);
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["speed"]["daysPerSecond"], 1.0);
+        assert_eq!(json["gpu"]["drawCalls"], 0);
+        assert_eq!(json["flyMode"], false);
+        assert!(json["selected"].is_null());
+        assert_eq!(json["events"][0]["type"], "select");
+        assert_eq!(json["events"][0]["body"], "Earth");
+        assert_eq!(json["events"][1]["type"], "tourFinished");
+    }
+
+    // ── Localisation ──
+
+    /// `{name}` placeholders of `message`, sorted.
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn translations_cover_the_english_catalogue() {
+        let en = Catalogue::bundled(Locale::En);
+        for locale in Locale::ALL {
+            let catalogue = Catalogue::bundled(locale);
+            for (key, message) in en.iter() {
+                let translated = catalogue
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} lacks `{key}`", locale.id()));
+                assert_eq!(
+                    placeholders(translated),
+                    placeholders(message),
+                    "{}: placeholders of `{key}`",
+                    locale.id()
+                );
+            }
+            assert_eq!(catalogue.iter().count(), en.iter().count(), "{}", locale.id());
+        }
+    }
+
+    #[test]
+    fn every_body_and_action_has_a_message() {
+        let en = Catalogue::bundled(Locale::En);
+        for body in create_solar_system() {
+            assert!(en.get(&format!("body.{}", body.id.to_lowercase())).is_some(), "{}", body.id);
+        }
+        // A missing message would show its key.
+        for row in KeyBindings::default().help_rows(&I18n::default()) {
+            assert!(!row.description.starts_with("action."), "{}", row.action);
+            assert!(!row.section.starts_with("section."), "{}", row.action);
+        }
+    }
+
+    #[test]
+    fn catalogue_errors_name_the_line() {
+        let err = Catalogue::parse("# comment\na = b\nno separator").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(
+            Catalogue::parse("a = 1\na = 2").unwrap_err().message,
+            "duplicate key `a`"
+        );
+        assert_eq!(Catalogue::parse("a = x = y").unwrap().get("a"), Some("x = y"));
+    }
+
+    #[test]
+    fn locale_is_negotiated_from_language_tags() {
+        assert_eq!(Locale::parse("fr-CA"), Some(Locale::Fr));
+        assert_eq!(Locale::parse("EN_gb"), Some(Locale::En));
+        assert_eq!(Locale::parse("de"), None);
+        assert_eq!(Locale::negotiate(["de-DE", "fr", "en"]), Locale::Fr);
+        assert_eq!(Locale::negotiate(["ja"]), Locale::En);
+        let options = ViewerOptions {
+            locale: Some("es".into()),
+            ..ViewerOptions::default()
+        };
+        // An unsupported option falls through to the browser's languages.
+        assert_eq!(options.locale(&["fr-FR".into()]), Locale::Fr);
+    }
+
+    #[test]
+    fn numbers_and_dates_follow_the_locale() {
+        let en = I18n::default();
+        let fr = I18n::new(Locale::Fr);
+        assert_eq!(en.number(1_234_567.891, 2), "1,234,567.89");
+        assert_eq!(fr.number(1_234_567.891, 2), "1\u{202f}234\u{202f}567,89");
+        assert_eq!(en.number(-0.004, 2), "0.00", "no negative zero");
+        assert_eq!(en.number(-12.5, 0), "-12");
+        assert_eq!(fr.number(999.0, 1), "999,0");
+        let apollo = url_state::iso_to_days("1969-07-20T20:17:00Z").unwrap();
+        assert_eq!(en.date(apollo), "Jul 20, 1969");
+        assert_eq!(fr.date(apollo), "20 juil. 1969");
+    }
+
+    #[test]
+    fn body_names_are_localised_but_ids_are_not() {
+        let fr = I18n::new(Locale::Fr);
+        assert_eq!(fr.body_name("Venus"), "Vénus");
+        assert_eq!(fr.body_name("Pluto"), "Pluto", "unknown ids show as is");
+        assert_eq!(
+            Action::SelectBody("Earth".into()).description(&fr),
+            "Sélectionner Terre"
+        );
+        assert_eq!(Action::SelectBody("Earth".into()).id(), "select_body:Earth");
+        let rows = KeyBindings::default().help_rows(&fr);
+        assert_eq!(rows[0].section, "Caméra");
+        // Every label glyph the French names need is in the font atlas.
+        for body in create_solar_system() {
+            for c in fr.body_name(body.id).chars() {
+                assert_ne!(font::glyph_uv(c), font::glyph_uv('?'), "{c}");
+            }
+        }
+    }
+
+    // ── Accessibility ──
+
+    fn overview_status() -> Status {
+        Status {
+            selected: None,
+            locked: false,
+            paused: false,
+            days_per_second: 1.0,
+            fly_mode: false,
+            touring: false,
+            layers: Layers::default(),
+            labels: labels::LabelMode::default(),
+        }
+    }
+
+    #[test]
+    fn announcements_describe_what_changed() {
+        let i18n = I18n::default();
+        let overview = overview_status();
+        let earth = Status {
+            selected: Some(SelectedStatus {
+                id: "Earth",
+                is_star: false,
+                sun_distance_au: 1.0,
+            }),
+            ..overview
+        };
+        let say = |from: &Status, to: &Status| accessibility::announcement(from, to, &i18n);
+
+        assert_eq!(say(&overview, &overview), None);
+        assert_eq!(
+            say(&overview, &earth).as_deref(),
+            Some("Earth selected, 1.00 AU from the Sun.")
+        );
+        assert_eq!(
+            say(&earth, &overview).as_deref(),
+            Some("Overview of the whole solar system.")
+        );
+
+        // Selecting and locking in one go is one announcement; the lock
+        // dropped by a new selection goes unmentioned.
+        let locked = Status { locked: true, ..earth };
+        assert_eq!(
+            say(&overview, &locked).as_deref(),
+            Some("Earth selected, 1.00 AU from the Sun. Camera following Earth.")
+        );
+        assert_eq!(say(&locked, &earth).as_deref(), Some("Camera released."));
+        assert_eq!(say(&locked, &overview).as_deref(), Some("Overview of the whole solar system."));
+
+        // Moving along the orbit is not news.
+        let moved = Status {
+            selected: earth.selected.map(|s| SelectedStatus { sun_distance_au: 1.01, ..s }),
+            ..earth
+        };
+        assert_eq!(say(&earth, &moved), None);
+
+        let paused = Status { paused: true, ..overview };
+        assert_eq!(say(&overview, &paused).as_deref(), Some("Simulation paused."));
+        assert_eq!(
+            say(&paused, &overview).as_deref(),
+            Some("Simulation running at ×1.0 d/s.")
+        );
+        let faster = Status { days_per_second: 10.0, ..overview };
+        assert_eq!(say(&overview, &faster).as_deref(), Some("Speed ×10.0 d/s."));
+
+        let toggled = Status {
+            layers: Layers {
+                orbits: false,
+                trails: true,
+            },
+            labels: labels::LabelMode::Off,
+            ..overview
+        };
+        assert_eq!(
+            say(&overview, &toggled).as_deref(),
+            Some("Orbits hidden. Labels hidden.")
+        );
+
+        let fr = I18n::new(Locale::Fr);
+        assert_eq!(
+            accessibility::announcement(&overview, &earth, &fr).as_deref(),
+            Some("Sélection : Terre, à 1,00 ua du Soleil.")
+        );
+    }
+
+    #[test]
+    fn announcer_starts_quietly_and_skips_unchanged_frames() {
+        let i18n = I18n::default();
+        let mut announcer = Announcer::default();
+        let status = overview_status();
+        assert_eq!(announcer.update(status, &i18n), None, "first frame is the baseline");
+        assert_eq!(announcer.update(status, &i18n), None);
+        let paused = Status { paused: true, ..status };
+        assert_eq!(announcer.update(paused, &i18n).as_deref(), Some("Simulation paused."));
+        assert_eq!(announcer.update(paused, &i18n), None);
+
+        // The selected body moving along its orbit is not a change.
+        let at = |sun_distance_au| Status {
+            selected: Some(SelectedStatus {
+                id: "Mars",
+                is_star: false,
+                sun_distance_au,
+            }),
+            ..status
+        };
+        assert_eq!(at(1.52), at(1.53));
+        assert!(announcer.update(at(1.52), &i18n).is_some());
+        assert_eq!(announcer.update(at(1.53), &i18n), None);
+    }
+
+    #[test]
+    fn tab_cycles_through_bodies_then_lets_focus_go() {
+        use accessibility::cycle_body;
+        assert_eq!(cycle_body(None, 3, false), Some(0));
+        assert_eq!(cycle_body(Some(0), 3, false), Some(1));
+        assert_eq!(cycle_body(Some(2), 3, false), None, "Tab past the last body");
+        assert_eq!(cycle_body(None, 3, true), Some(2));
+        assert_eq!(cycle_body(Some(1), 3, true), Some(0));
+        assert_eq!(cycle_body(Some(0), 3, true), None, "Shift-Tab before the first");
+        assert_eq!(cycle_body(None, 0, false), None);
+    }
+
+    #[test]
+    fn reduced_motion_camera_jumps() {
+        let mut cam = Camera::new(1.0);
+        cam.reduced_motion = true;
+        let target = glam::DVec3::new(50.0, 0.0, 0.0);
+        cam.set_target(target, 30.0);
+        cam.update_transition(0.016);
+        assert_eq!(cam.target, target);
+        assert_eq!(cam.distance, 30.0);
+        assert!(cam.transition.is_none());
+
+        let moved = glam::DVec3::new(51.0, 0.0, 0.0);
+        cam.follow(moved);
+        cam.update_transition(0.016);
+        assert_eq!(cam.target, moved, "followed points are tracked exactly");
+    }
+
+    #[test]
+    fn high_contrast_orbits_are_bright_and_opaque() {
+        for body in create_solar_system().iter().filter(|b| !b.is_star) {
+            assert_eq!(orbit_color(body.color, false), (body.color, ORBIT_ALPHA));
+            let (color, alpha) = orbit_color(body.color, true);
+            assert!(alpha > ORBIT_ALPHA);
+            let peak = color.into_iter().fold(0.0, f32::max);
+            assert!((peak - 1.0).abs() < 1e-6, "{}: {color:?}", body.id);
+            // Relative luminance well above the dark background's.
+            let luminance = 0.2126 * color[0] + 0.7152 * color[1] + 0.0722 * color[2];
+            assert!(luminance > 0.4, "{}: {luminance}", body.id);
+        }
+        assert_eq!(orbit_color([0.0; 3], true).0, [HIGH_CONTRAST_WHITE_MIX; 3]);
+    }
+
+    #[test]
+    fn viewer_options_override_browser_preferences() {
+        let browser = Preferences {
+            reduced_motion: true,
+            high_contrast: false,
+        };
+        assert_eq!(ViewerOptions::default().preferences(browser), browser);
+        let options: ViewerOptions =
+            serde_json::from_str(r#"{"reducedMotion": false, "highContrast": true}"#).unwrap();
+        assert_eq!(
+            options.preferences(browser),
+            Preferences {
+                reduced_motion: false,
+                high_contrast: true,
+            }
+        );
+    }
+
+    // ── Shader preprocessing ──
+
+    #[test]
+    fn shader_include_is_spliced() {
+        let src = "#version 300 es\n#include \"noise.glsl\"\nvoid main() {}";
+        let out = shader::resolve_includes(src, &[("noise.glsl", "float snoise(vec3 v);")]);
+        assert!(out.starts_with("#version 300 es\n"), "#version must stay first");
+        assert!(out.contains("float snoise(vec3 v);"));
+        assert!(!out.contains("#include"));
+    }
+
+    #[test]
+    fn shader_unknown_include_is_left_for_the_compiler() {
+        let src = "#include \"missing.glsl\"";
+        assert_eq!(shader::resolve_includes(src, &[]), src);
+    }
+
+    // ── Orbit geometry ──
+
+    #[test]
+    fn orbit_path_is_closed_loop() {
+        let path = orbit::generate_orbit_path(1.0, 0.0);
+        assert_eq!(
+            path.len(),
+            ORBIT_SEGMENTS + 1,
+            "Path should have SEGMENTS+1 points"
+        );
+        let first = path.first().unwrap();
+        let last = path.last().unwrap();
+        assert!(
+            first.distance(*last) < 0.01,
+            "First and last points should nearly coincide"
+        );
+    }
+
+    #[test]
+    fn orbit_radius_scales_with_au() {
+        let inner = orbit::generate_orbit_path(1.0, 0.0);
+        let outer = orbit::generate_orbit_path(5.0, 0.0);
+        let r_inner = inner[0].length();
+        let r_outer = outer[0].length();
+        assert!(
+            r_outer > r_inner * 4.0,
+            "5 AU orbit should be much larger than 1 AU orbit"
+        );
+    }
+
+    #[test]
+    fn orbit_with_inclination_has_y_component() {
+        let flat = orbit::generate_orbit_path(1.0, 0.0);
+        let tilted = orbit::generate_orbit_path(1.0, 0.3);
+        let max_y_flat: f32 = flat.iter().map(|p| p.y.abs()).fold(0.0, f32::max);
+        let max_y_tilted: f32 = tilted.iter().map(|p| p.y.abs()).fold(0.0, f32::max);
+        assert!(
+            max_y_tilted > max_y_flat + 0.1,
+            "Tilted orbit should have larger Y extent"
+        );
+    }
+
+    // ── Constants consistency ──
+
+    #[test]
+    fn camera_near_less_than_far() {
+        const { assert!(CAMERA_NEAR < CAMERA_FAR) };
+    }
+
+    #[test]
+    fn starfield_radius_exceeds_camera_max() {
+        const {
+            assert!(
+                STARFIELD_RADIUS > CAMERA_MAX_DISTANCE,
+                "Stars should be beyond max zoom-out distance"
+            )
+        };
+    }
+
+    // ── Planet selection / camera transition ──
+
+    #[test]
+    fn camera_set_target_starts_timed_transition() {
+        let mut cam = Camera::new(1.0);
+        let target = glam::DVec3::new(10.0, 0.0, 0.0);
+        cam.set_target(target, 50.0);
+        let transition = cam.transition.as_ref().expect("transition should start");
+        assert_eq!(transition.to.target, target);
+        assert_eq!(transition.to.distance, 50.0);
+        assert_eq!(transition.duration, CAMERA_TRANSITION_SECONDS);
+        assert_eq!(
+            (transition.to.theta, transition.to.phi),
+            (cam.theta, cam.phi),
+            "set_target keeps the angles"
+        );
+    }
+
+    #[test]
+    fn camera_set_target_clamps_distance() {
+        let mut cam = Camera::new(1.0);
+        // Request a distance below min
+        cam.set_target(glam::DVec3::ZERO, 0.001);
+        assert!(
+            cam.transition.as_ref().unwrap().to.distance >= cam.min_distance,
+            "distance should be clamped to min_distance"
+        );
+        // Request a distance above max
+        cam.set_target(glam::DVec3::ZERO, 99_999.0);
+        assert!(
+            cam.transition.as_ref().unwrap().to.distance <= cam.max_distance,
+            "distance should be clamped to max_distance"
+        );
+    }
+
+    #[test]
+    fn camera_update_transition_moves_toward_target() {
+        let mut cam = Camera::new(1.0);
+        let target = glam::DVec3::new(100.0, 0.0, 0.0);
+        cam.set_target(target, 20.0);
+
+        let initial_dist = cam.target.distance(target);
+        cam.update_transition(0.1); // one large step
+        let after_dist = cam.target.distance(target);
+
+        assert!(
+            after_dist < initial_dist,
+            "Camera should move closer to the target after update_transition"
+        );
+    }
+
+    #[test]
+    fn camera_update_transition_converges() {
+        let mut cam = Camera::new(1.0);
+        let target = glam::DVec3::new(50.0, 0.0, 0.0);
+        cam.set_target(target, 30.0);
+
+        // Run many frames
+        for _ in 0..200 {
+            cam.update_transition(0.016);
+        }
+
+        assert_eq!(cam.target, target, "Camera target should land on the target");
+        assert_eq!(cam.distance, 30.0, "Camera distance should land on the distance");
+        assert!(
+            cam.transition.is_none(),
+            "transition should be None once finished"
+        );
+    }
+
+    #[test]
+    fn camera_follow_lerp_converges() {
+        let mut cam = Camera::new(1.0);
+        let target = glam::DVec3::new(5.0, 0.0, 0.0);
+        cam.follow(target);
+        for _ in 0..200 {
+            cam.update_transition(0.016);
+        }
+        assert_eq!(cam.target, target);
+        assert!(cam.lerp_target.is_none(), "lerp_target should clear on arrival");
+    }
+
+    #[test]
+    fn camera_top_view_turns_smoothly() {
+        let mut cam = Camera::new(1.0);
+        let (theta, phi) = (cam.theta, cam.phi);
+        cam.set_orientation(0.0, PHI_CLAMP);
+        assert_eq!((cam.theta, cam.phi), (theta, phi), "no snap on the first frame");
+        cam.update_transition(CAMERA_TRANSITION_SECONDS / 2.0);
+        assert!(cam.phi > phi && cam.phi < PHI_CLAMP);
+        cam.update_transition(CAMERA_TRANSITION_SECONDS);
+        assert_eq!((cam.theta, cam.phi), (0.0, PHI_CLAMP));
+    }
+
+    #[test]
+    fn camera_transition_is_framerate_independent() {
+        let to = CameraPose {
+            target: glam::DVec3::new(30.0, 5.0, -8.0),
+            theta: 2.5,
+            phi: -0.4,
+            distance: 15.0,
+        };
+        let run = |steps: &[f32]| {
+            let mut cam = Camera::new(1.0);
+            cam.start_transition(to, 2.0, Easing::EaseInOut);
+            for &dt in steps {
+                cam.update_transition(dt);
+            }
+            cam.pose()
+        };
+        let smooth = run(&[1.0 / 144.0; 144]);
+        let choppy = run(&[0.3, 0.05, 0.4, 0.25]);
+        assert!(smooth.target.distance(choppy.target) < 1e-3);
+        assert!((smooth.theta - choppy.theta).abs() < 1e-3);
+        assert!((smooth.phi - choppy.phi).abs() < 1e-3);
+        assert!((smooth.distance - choppy.distance).abs() < 1e-3);
+
+        // Following a point closes the same share of the gap whatever the
+        // frame rate, and a long frame does not snap onto it.
+        let point = glam::DVec3::new(100.0, 0.0, 0.0);
+        let follow = |steps: &[f32]| {
+            let mut cam = Camera::new(1.0);
+            for &dt in steps {
+                cam.follow(point);
+                cam.update_transition(dt);
+            }
+            cam.target
+        };
+        let smooth = follow(&[1.0 / 144.0; 72]);
+        let choppy = follow(&[0.3, 0.2]);
+        assert!(smooth.distance(choppy) < 1e-2, "{smooth} vs {choppy}");
+        let expected = 100.0 * (-0.5 * crate::constants::CAMERA_LERP_SPEED as f64).exp();
+        assert!((point.distance(choppy) - expected).abs() < 1e-2);
+    }
+
+    #[test]
+    fn orientation_slerp_moves_at_even_angular_speed() {
+        let from = CameraPose {
+            target: glam::DVec3::ZERO,
+            theta: 0.0,
+            phi: 0.0,
+            distance: 10.0,
+        };
+        let to = CameraPose {
+            theta: std::f32::consts::FRAC_PI_2,
+            ..from
+        };
+        let quarter = from.interpolate(&to, 0.25);
+        assert!((quarter.theta - std::f32::consts::FRAC_PI_8).abs() < 1e-5);
+        assert!(quarter.phi.abs() < 1e-5);
+        // Orientation matches the spherical eye direction.
+        let dir = from.orientation() * glam::Vec3::X;
+        assert!((dir - glam::Vec3::X).length() < 1e-6);
+        let pose = CameraPose {
+            theta: 0.7,
+            phi: 0.3,
+            ..from
+        };
+        let mut cam = Camera::new(1.0);
+        cam.set_pose(pose);
+        let expected = cam.eye_offset().normalize();
+        assert!((pose.orientation() * glam::Vec3::X - expected).length() < 1e-5);
     }
 
     #[test]
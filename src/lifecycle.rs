@@ -0,0 +1,182 @@
+//! Viewer lifecycle: removable DOM listeners and a stoppable render loop.
+//!
+//! A viewer registers every DOM listener through [`EventListeners`] and
+//! draws from a [`FrameLoop`], so tearing it down removes the listeners,
+//! frees their closures and cancels the pending animation frame instead of
+//! leaking them for the life of the page. Both also tear themselves down
+//! when dropped, so a viewer that fails half-way through setup, or is
+//! dropped without being destroyed, leaves no callbacks behind.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::closure::WasmClosure;
+use wasm_bindgen::prelude::*;
+
+use crate::constants::{FIRST_FRAME_DT, MAX_FRAME_DT};
+
+// ── Event listeners ──
+
+struct Listener {
+    target: web_sys::EventTarget,
+    event: &'static str,
+    function: js_sys::Function,
+    /// The `Closure` behind `function`, kept alive until removal.
+    _closure: Box<dyn Any>,
+}
+
+/// DOM event listeners added by one viewer.
+#[derive(Default)]
+pub struct EventListeners {
+    entries: Vec<Listener>,
+}
+
+impl EventListeners {
+    /// Add `closure` as the `event` listener of `target`.
+    pub fn add<T: ?Sized + WasmClosure + 'static>(
+        &mut self,
+        target: &web_sys::EventTarget,
+        event: &'static str,
+        closure: Closure<T>,
+    ) -> Result<(), JsValue> {
+        self.add_with_options(
+            target,
+            event,
+            closure,
+            &web_sys::AddEventListenerOptions::new(),
+        )
+    }
+
+    /// [`add`](Self::add) with listener options (e.g. `passive: false`).
+    pub fn add_with_options<T: ?Sized + WasmClosure + 'static>(
+        &mut self,
+        target: &web_sys::EventTarget,
+        event: &'static str,
+        closure: Closure<T>,
+        options: &web_sys::AddEventListenerOptions,
+    ) -> Result<(), JsValue> {
+        let function: js_sys::Function = closure.as_ref().clone().unchecked_into();
+        target.add_event_listener_with_callback_and_add_event_listener_options(
+            event, &function, options,
+        )?;
+        self.entries.push(Listener {
+            target: target.clone(),
+            event,
+            function,
+            _closure: Box::new(closure),
+        });
+        Ok(())
+    }
+
+    /// Remove every listener and free its closure.
+    ///
+    /// A listener that is running right now finishes normally; wasm-bindgen
+    /// frees its closure once it returns.
+    pub fn remove_all(&mut self) {
+        for listener in self.entries.drain(..) {
+            let _ = listener
+                .target
+                .remove_event_listener_with_callback(listener.event, &listener.function);
+        }
+    }
+}
+
+impl Drop for EventListeners {
+    fn drop(&mut self) {
+        self.remove_all();
+    }
+}
+
+// ── Render loop ──
+
+/// Seconds between the frames stamped `last` and `now` (ms), clamped to
+/// [`MAX_FRAME_DT`]; [`FIRST_FRAME_DT`] when there was no previous frame.
+pub fn frame_dt(last: f64, now: f64) -> f64 {
+    if last == 0.0 {
+        FIRST_FRAME_DT
+    } else {
+        ((now - last) / 1000.0).clamp(0.0, MAX_FRAME_DT)
+    }
+}
+
+type FrameCallback = Closure<dyn FnMut(f64)>;
+
+/// A `requestAnimationFrame` loop that can be paused, resumed and stopped.
+pub struct FrameLoop {
+    callback: RefCell<Option<FrameCallback>>,
+    /// Id of the requested frame, if one is pending.
+    request: Cell<Option<i32>>,
+    /// Whether frames keep being requested.
+    running: Cell<bool>,
+    /// Timestamp of the previous frame; 0 until the first after a resume.
+    last_time: Cell<f64>,
+}
+
+impl FrameLoop {
+    /// Call `frame(dt)` once per animation frame, `dt` in seconds.
+    pub fn start(mut frame: impl FnMut(f64) + 'static) -> Rc<Self> {
+        let frames = Rc::new(Self {
+            callback: RefCell::new(None),
+            request: Cell::new(None),
+            running: Cell::new(false),
+            last_time: Cell::new(0.0),
+        });
+        let weak = Rc::downgrade(&frames);
+        *frames.callback.borrow_mut() = Some(Closure::wrap(Box::new(move |timestamp: f64| {
+            let Some(frames) = weak.upgrade() else {
+                return;
+            };
+            frames.request.set(None);
+            frame(frame_dt(frames.last_time.replace(timestamp), timestamp));
+            // `frame` may have paused or stopped the loop.
+            if frames.running.get() {
+                frames.schedule();
+            }
+        }) as Box<dyn FnMut(f64)>));
+        frames.resume();
+        frames
+    }
+
+    fn schedule(&self) {
+        let callback = self.callback.borrow();
+        let Some(callback) = callback.as_ref() else {
+            return;
+        };
+        self.request.set(web_sys::window().and_then(|w| {
+            w.request_animation_frame(callback.as_ref().unchecked_ref())
+                .ok()
+        }));
+    }
+
+    /// Stop requesting frames until [`resume`](Self::resume).
+    pub fn pause(&self) {
+        self.running.set(false);
+        if let Some(id) = self.request.take()
+            && let Some(window) = web_sys::window()
+        {
+            let _ = window.cancel_animation_frame(id);
+        }
+    }
+
+    /// Request frames again. The first frame after a pause gets the
+    /// start-up time step rather than the time spent paused.
+    pub fn resume(&self) {
+        self.last_time.set(0.0);
+        if !self.running.replace(true) && self.request.get().is_none() {
+            self.schedule();
+        }
+    }
+
+    /// Stop for good and free the frame callback.
+    pub fn stop(&self) {
+        self.pause();
+        self.callback.borrow_mut().take();
+    }
+}
+
+impl Drop for FrameLoop {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
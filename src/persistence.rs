@@ -0,0 +1,186 @@
+//! Session persistence in `localStorage`.
+//!
+//! The app state that survives a reload (clock, selection, camera, layer
+//! toggles and saved bookmarks) is stored as one JSON [`Session`] under the
+//! viewer's `storageKey` (`solara.session` on the bundled page). Every
+//! session records the schema [`version`] it was written with; older
+//! sessions are upgraded one version at a time through [`MIGRATIONS`] before
+//! being deserialized, and sessions from a newer build are ignored rather
+//! than misread.
+//!
+//! Only [`load`], [`save`] and [`clear`] touch the DOM.
+//!
+//! [`version`]: Session::version
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::constants::{CAMERA_DISTANCE, CAMERA_PHI, CAMERA_THETA, DEFAULT_DAYS_PER_SECOND};
+use crate::renderer::labels::LabelMode;
+use crate::renderer::render_pass::Layers;
+use crate::tour::Bookmark;
+use crate::url_state::ViewState;
+
+/// Version written by this build.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a stored session object by one schema version, in place.
+pub type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` upgrades a version `i + 1` session to version `i + 2`,
+/// so the table always has `SCHEMA_VERSION - 1` entries. When the schema
+/// changes, bump [`SCHEMA_VERSION`] and append a migration here.
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// Everything restored on reload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Schema version the session was written with.
+    pub version: u32,
+    /// Simulation date in days since J2000.
+    pub days: f64,
+    /// Simulation speed in days per second.
+    pub speed: f64,
+    pub paused: bool,
+    /// Name of the selected body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Whether the camera follows the selected body.
+    #[serde(default)]
+    pub locked: bool,
+    pub theta: f32,
+    pub phi: f32,
+    pub distance: f32,
+    pub layers: Layers,
+    /// [`LabelMode::id`] of the label mode.
+    pub labels: String,
+    /// Bookmarks saved during the session.
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Why a stored session was not restored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionError {
+    /// Not valid JSON, or not shaped like a session.
+    Parse(String),
+    /// The session has no `version` field.
+    MissingVersion,
+    /// Written by a newer build (or corrupt); this build cannot read it.
+    UnsupportedVersion(u64),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Parse(message) => write!(f, "saved session: {message}"),
+            SessionError::MissingVersion => write!(f, "saved session has no version"),
+            SessionError::UnsupportedVersion(version) => write!(
+                f,
+                "saved session has version {version}, this build reads up to {SCHEMA_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl Session {
+    /// Capture a complete view (see `AppState::view_state`) and the bookmarks.
+    pub fn capture(view: &ViewState, bookmarks: &[Bookmark]) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            days: view.days.unwrap_or_default(),
+            speed: view.speed.unwrap_or(DEFAULT_DAYS_PER_SECOND),
+            paused: view.paused.unwrap_or_default(),
+            body: view.body.clone(),
+            locked: view.locked.unwrap_or_default(),
+            theta: view.theta.unwrap_or(CAMERA_THETA),
+            phi: view.phi.unwrap_or(CAMERA_PHI),
+            distance: view.distance.unwrap_or(CAMERA_DISTANCE),
+            layers: view.layers.unwrap_or_default(),
+            labels: view.labels.unwrap_or_default().id().to_string(),
+            bookmarks: bookmarks.to_vec(),
+        }
+    }
+
+    /// The session as a view state; non-finite or out-of-range values are
+    /// dropped so they leave the defaults alone.
+    pub fn view(&self) -> ViewState {
+        let finite = |v: f32| v.is_finite().then_some(v);
+        ViewState {
+            days: self.days.is_finite().then_some(self.days),
+            speed: (self.speed.is_finite() && self.speed > 0.0).then_some(self.speed),
+            paused: Some(self.paused),
+            body: self.body.clone(),
+            locked: self.body.as_ref().map(|_| self.locked),
+            theta: finite(self.theta),
+            phi: finite(self.phi),
+            distance: finite(self.distance).filter(|d| *d > 0.0),
+            layers: Some(self.layers),
+            labels: LabelMode::parse(&self.labels),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse a stored session, upgrading it to [`SCHEMA_VERSION`] first.
+    pub fn from_json(text: &str) -> Result<Self, SessionError> {
+        Self::from_json_with(text, MIGRATIONS)
+    }
+
+    /// [`Session::from_json`] with an explicit migration table.
+    pub fn from_json_with(text: &str, migrations: &[Migration]) -> Result<Self, SessionError> {
+        let mut value: Value =
+            serde_json::from_str(text).map_err(|e| SessionError::Parse(e.to_string()))?;
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| SessionError::Parse("expected an object".into()))?;
+        let version = object
+            .get("version")
+            .ok_or(SessionError::MissingVersion)?
+            .as_u64()
+            .ok_or(SessionError::MissingVersion)?;
+        let latest = migrations.len() as u64 + 1;
+        if version == 0 || version > latest {
+            return Err(SessionError::UnsupportedVersion(version));
+        }
+        for migrate in &migrations[version as usize - 1..] {
+            migrate(object);
+        }
+        object.insert("version".into(), latest.into());
+        serde_json::from_value(value).map_err(|e| SessionError::Parse(e.to_string()))
+    }
+}
+
+// ── DOM ──
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// The session stored under `key`, if there is a readable one.
+pub fn load(key: &str) -> Option<Session> {
+    let text = storage()?.get_item(key).ok().flatten()?;
+    Session::from_json(&text)
+        .inspect_err(|err| log::warn!("⚠️ Ignoring {err}"))
+        .ok()
+}
+
+/// Store `json` (from [`Session::to_json`]) under `key`.
+pub fn save(key: &str, json: &str) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(key, json);
+    }
+}
+
+/// Forget the session stored under `key`.
+pub fn clear(key: &str) {
+    if let Some(storage) = storage() {
+        let _ = storage.remove_item(key);
+    }
+}
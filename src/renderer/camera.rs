@@ -3,11 +3,127 @@
 //! Uses spherical coordinates (theta, phi, distance) to orbit around a
 //! target point. All magic numbers come from [`constants`] so they can
 //! be tuned in one place.
+//!
+//! World positions are `f64`; rendering happens in *camera-relative* space,
+//! where the eye sits at the origin. The view matrix therefore only rotates,
+//! and every world position is moved into that space with
+//! [`relative_to_eye`] before it is narrowed to `f32`.
+//!
+//! Camera moves (selecting a body, the top-down view, bookmarks and tours)
+//! are fixed-duration eased [`Transition`]s between complete poses, so they
+//! take the same time at any frame rate. The orientation is slerped as a
+//! quaternion. Following a moving point once there uses an open-ended
+//! exponential lerp ([`Camera::follow`]).
+//!
+//! With [`Camera::reduced_motion`] set, both kinds of move land at once.
+//!
+//! Panning ([`Camera::pan_offset`]) slides the target across the view
+//! plane; the app keeps the panned offset relative to whatever it follows.
 
-use glam::{Mat4, Vec3};
+use std::f32::consts::{PI, TAU};
+
+use glam::{DVec3, Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
 
 use crate::constants::*;
 
+/// Easing curve for timed camera transitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Easing {
+    Linear,
+    /// Cubic, starting slowly.
+    EaseIn,
+    /// Cubic, arriving slowly.
+    EaseOut,
+    /// Cubic, slow at both ends.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map linear progress `t` in `[0, 1]` onto the curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Everything that determines what the orbital camera sees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub target: DVec3,
+    pub theta: f32,
+    pub phi: f32,
+    pub distance: f32,
+}
+
+impl CameraPose {
+    /// Orientation of the eye around the target, as the rotation taking +X
+    /// onto the target → eye direction (yaw by `theta`, then pitch by `phi`).
+    pub fn orientation(&self) -> Quat {
+        Quat::from_rotation_y(-self.theta) * Quat::from_rotation_z(self.phi)
+    }
+
+    /// Pose a fraction `t` of the way to `to`.
+    ///
+    /// The orientation is slerped, so the eye sweeps the shorter way round
+    /// at an even angular speed, and the distance is interpolated
+    /// geometrically so zooming across orders of magnitude feels even.
+    pub fn interpolate(&self, to: &CameraPose, t: f32) -> CameraPose {
+        let direction = self.orientation().slerp(to.orientation(), t) * Vec3::X;
+        let phi = direction.y.clamp(-1.0, 1.0).asin();
+        // Keep theta continuous with the start rather than wrapping to ±π.
+        let heading = direction.z.atan2(direction.x);
+        let theta = self.theta + (heading - self.theta + PI).rem_euclid(TAU) - PI;
+        CameraPose {
+            target: self.target.lerp(to.target, t as f64),
+            theta,
+            phi,
+            distance: self.distance * (to.distance / self.distance).powf(t),
+        }
+    }
+}
+
+/// A fixed-duration, eased move between two poses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub from: CameraPose,
+    pub to: CameraPose,
+    /// Length in seconds.
+    pub duration: f32,
+    /// Seconds played so far.
+    pub elapsed: f32,
+    pub easing: Easing,
+}
+
+impl Transition {
+    /// Pose at the current point of the transition.
+    pub fn pose(&self) -> CameraPose {
+        let t = if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        };
+        self.from.interpolate(&self.to, self.easing.apply(t))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
 /// Orbital camera that looks at a target from spherical coordinates.
 pub struct Camera {
     /// Horizontal angle in radians.
@@ -16,8 +132,8 @@ pub struct Camera {
     pub phi: f32,
     /// Distance from target.
     pub distance: f32,
-    /// Point the camera orbits around.
-    pub target: Vec3,
+    /// Point the camera orbits around (world space).
+    pub target: DVec3,
     /// Minimum zoom distance.
     pub min_distance: f32,
     /// Maximum zoom distance.
@@ -26,10 +142,13 @@ pub struct Camera {
     pub fov: f32,
     /// Viewport aspect ratio (width / height).
     pub aspect: f32,
-    /// Desired target for smooth transition (`None` when no animation is active).
-    pub lerp_target: Option<Vec3>,
-    /// Desired orbit distance for smooth transition (`None` when no animation is active).
-    pub lerp_distance: Option<f32>,
+    /// Moving point the target is easing towards (`None` when not following).
+    pub lerp_target: Option<DVec3>,
+    /// Timed transition in progress; takes precedence over `lerp_target`.
+    pub transition: Option<Transition>,
+    /// Jump instead of gliding: transitions take no time and followed
+    /// points are tracked exactly (`prefers-reduced-motion`).
+    pub reduced_motion: bool,
 }
 
 impl Camera {
@@ -38,27 +157,53 @@ impl Camera {
             theta: CAMERA_THETA,
             phi: CAMERA_PHI,
             distance: CAMERA_DISTANCE,
-            target: Vec3::ZERO,
+            target: DVec3::ZERO,
             min_distance: CAMERA_MIN_DISTANCE,
             max_distance: CAMERA_MAX_DISTANCE,
             fov: CAMERA_FOV_DEGREES.to_radians(),
             aspect,
             lerp_target: None,
-            lerp_distance: None,
+            transition: None,
+            reduced_motion: false,
+        }
+    }
+
+    /// Current pose.
+    pub fn pose(&self) -> CameraPose {
+        CameraPose {
+            target: self.target,
+            theta: self.theta,
+            phi: self.phi,
+            distance: self.distance,
         }
     }
 
-    /// Camera world position derived from spherical coordinates.
-    pub fn eye_position(&self) -> Vec3 {
+    /// Jump to `pose` (angles and distance are clamped to the usual limits).
+    pub fn set_pose(&mut self, pose: CameraPose) {
+        self.target = pose.target;
+        self.theta = pose.theta;
+        self.phi = pose.phi.clamp(-PHI_CLAMP, PHI_CLAMP);
+        self.distance = pose.distance.clamp(self.min_distance, self.max_distance);
+    }
+
+    /// Offset from the target to the eye, derived from spherical coordinates.
+    pub fn eye_offset(&self) -> Vec3 {
         let x = self.distance * self.phi.cos() * self.theta.cos();
         let y = self.distance * self.phi.sin();
         let z = self.distance * self.phi.cos() * self.theta.sin();
-        self.target + Vec3::new(x, y, z)
+        Vec3::new(x, y, z)
     }
 
-    /// View matrix (look-at, right-handed).
+    /// Camera world position.
+    pub fn eye_position(&self) -> DVec3 {
+        self.target + self.eye_offset().as_dvec3()
+    }
+
+    /// View matrix (look-at, right-handed) for camera-relative space.
+    ///
+    /// The eye is at the origin, so this is a pure rotation.
     pub fn view_matrix(&self) -> Mat4 {
-        Mat4::look_at_rh(self.eye_position(), self.target, Vec3::Y)
+        Mat4::look_at_rh(Vec3::ZERO, -self.eye_offset(), Vec3::Y)
     }
 
     /// Perspective projection matrix.
@@ -73,6 +218,34 @@ impl Camera {
         self.phi = self.phi.clamp(-PHI_CLAMP, PHI_CLAMP);
     }
 
+    /// Turn around the target's vertical axis by `radians`, clockwise on
+    /// screen when looking down (two-finger twist).
+    pub fn spin(&mut self, radians: f32) {
+        self.theta -= radians;
+    }
+
+    /// World-space move of the target for a pan drag of (`dx`, `dy`)
+    /// pixels: the scene follows the pointer across the view plane, faster
+    /// the further out the camera is.
+    pub fn pan_offset(&self, dx: f32, dy: f32) -> DVec3 {
+        let forward = -self.eye_offset().normalize();
+        let right = forward.cross(Vec3::Y).normalize();
+        let up = right.cross(forward);
+        ((up * dy - right * dx) * (self.distance * PAN_SENSITIVITY)).as_dvec3()
+    }
+
+    /// Move the target by `offset`, along with wherever it is heading.
+    pub fn shift(&mut self, offset: DVec3) {
+        self.target += offset;
+        if let Some(target) = self.lerp_target.as_mut() {
+            *target += offset;
+        }
+        if let Some(transition) = self.transition.as_mut() {
+            transition.from.target += offset;
+            transition.to.target += offset;
+        }
+    }
+
     /// Zoom from scroll-wheel delta.
     pub fn zoom(&mut self, delta: f32) {
         self.distance *= 1.0 + delta * ZOOM_SENSITIVITY;
@@ -84,32 +257,101 @@ impl Camera {
         self.aspect = aspect;
     }
 
-    /// Begin a smooth camera transition to a new `target` point and orbit `distance`.
-    pub fn set_target(&mut self, target: Vec3, distance: f32) {
-        self.lerp_target = Some(target);
-        self.lerp_distance = Some(distance.clamp(self.min_distance, self.max_distance));
+    /// Move to a new `target` point and orbit `distance`, keeping the
+    /// current angles, with the default duration and easing.
+    pub fn set_target(&mut self, target: DVec3, distance: f32) {
+        self.animate_to(CameraPose {
+            target,
+            distance,
+            ..self.pose()
+        });
+    }
+
+    /// Turn to the given angles around the current target, with the default
+    /// duration and easing.
+    pub fn set_orientation(&mut self, theta: f32, phi: f32) {
+        self.animate_to(CameraPose {
+            theta,
+            phi,
+            ..self.pose()
+        });
+    }
+
+    /// Transition to `to` over [`CAMERA_TRANSITION_SECONDS`] with the default easing.
+    pub fn animate_to(&mut self, to: CameraPose) {
+        self.start_transition(to, CAMERA_TRANSITION_SECONDS, Easing::default());
+    }
+
+    /// Begin a timed transition from the current pose to `to`, replacing any
+    /// animation in progress. With reduced motion it completes on the next
+    /// update.
+    pub fn start_transition(&mut self, to: CameraPose, duration: f32, easing: Easing) {
+        self.lerp_target = None;
+        let to = CameraPose {
+            phi: to.phi.clamp(-PHI_CLAMP, PHI_CLAMP),
+            distance: to.distance.clamp(self.min_distance, self.max_distance),
+            ..to
+        };
+        self.transition = Some(Transition {
+            from: self.pose(),
+            to,
+            duration: if self.reduced_motion { 0.0 } else { duration.max(0.0) },
+            elapsed: 0.0,
+            easing,
+        });
+    }
+
+    /// Keep the camera centred on a moving point: retargets a running timed
+    /// transition, or steers the exponential lerp otherwise.
+    pub fn follow(&mut self, target: DVec3) {
+        match self.transition.as_mut() {
+            Some(transition) => transition.to.target = target,
+            None => self.lerp_target = Some(target),
+        }
     }
 
     /// Advance any active camera-transition animations.
     ///
-    /// Call once per frame with the real elapsed time in seconds.
+    /// Call once per frame with the real elapsed time in seconds. A timed
+    /// transition plays out along its easing curve; otherwise the target
+    /// converges exponentially on `lerp_target`.
     pub fn update_transition(&mut self, dt: f32) {
-        let alpha = (dt * CAMERA_LERP_SPEED).min(1.0);
+        if let Some(transition) = self.transition.as_mut() {
+            transition.elapsed += dt;
+            let finished = transition.is_finished();
+            // Land on the destination exactly rather than within rounding.
+            let pose = if finished {
+                transition.to
+            } else {
+                transition.pose()
+            };
+            self.set_pose(pose);
+            if finished {
+                self.transition = None;
+            }
+            return;
+        }
+
+        let alpha = if self.reduced_motion {
+            1.0
+        } else {
+            1.0 - (-dt * CAMERA_LERP_SPEED).exp()
+        };
 
         if let Some(tgt) = self.lerp_target {
-            self.target = self.target.lerp(tgt, alpha);
+            self.target = self.target.lerp(tgt, alpha as f64);
             if self.target.distance(tgt) < 0.01 {
                 self.target = tgt;
                 self.lerp_target = None;
             }
         }
-
-        if let Some(dist) = self.lerp_distance {
-            self.distance += (dist - self.distance) * alpha;
-            if (self.distance - dist).abs() < 0.01 {
-                self.distance = dist;
-                self.lerp_distance = None;
-            }
-        }
     }
 }
+
+/// Move a world position into camera-relative space.
+///
+/// The subtraction happens in `f64`, so the result stays precise near the
+/// camera however far the scene has drifted from the world origin.
+pub fn relative_to_eye(world: DVec3, eye: DVec3) -> Vec3 {
+    (world - eye).as_vec3()
+}
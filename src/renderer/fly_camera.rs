@@ -0,0 +1,175 @@
+//! Free-fly camera mode.
+//!
+//! The fly camera does not render by itself: every frame it writes its pose
+//! into the orbital [`Camera`] (angles, plus a target `distance` ahead of the
+//! eye), so the renderer, picking and labels keep working unchanged, and
+//! leaving fly mode continues from exactly the same view.
+//!
+//! Its position is stored relative to an *anchor* (the Sun), so the camera
+//! drifts along with the solar system instead of being left behind by the
+//! galactic motion.
+
+use glam::{DVec3, Vec2, Vec3};
+
+use super::camera::Camera;
+use crate::constants::{
+    FLY_ACCELERATION, FLY_LOOK_SENSITIVITY, FLY_MAX_SPEED, FLY_MIN_SPEED, FLY_SPEED_PER_DISTANCE,
+    PHI_CLAMP,
+};
+use crate::simulation::body::CelestialBody;
+
+/// A direction of keyboard thrust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlyDirection {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl FlyDirection {
+    pub const ALL: [FlyDirection; 6] = [
+        FlyDirection::Forward,
+        FlyDirection::Back,
+        FlyDirection::Left,
+        FlyDirection::Right,
+        FlyDirection::Up,
+        FlyDirection::Down,
+    ];
+
+    /// Thrust axis in camera space: x = right, y = up, z = forward.
+    fn axis(self) -> Vec3 {
+        match self {
+            FlyDirection::Forward => Vec3::Z,
+            FlyDirection::Back => Vec3::NEG_Z,
+            FlyDirection::Left => Vec3::NEG_X,
+            FlyDirection::Right => Vec3::X,
+            FlyDirection::Up => Vec3::Y,
+            FlyDirection::Down => Vec3::NEG_Y,
+        }
+    }
+}
+
+/// Analog input for one frame (keyboard, gamepad, or both combined).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlyInput {
+    /// Desired thrust in camera space (x = right, y = up, z = forward), each in [-1, 1].
+    pub thrust: Vec3,
+    /// Look rate in radians per second (x = yaw, y = pitch).
+    pub look_rate: Vec2,
+}
+
+/// Free-fly camera state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlyCamera {
+    /// Eye position relative to the anchor.
+    pub offset: DVec3,
+    /// Heading, using the orbital camera's `theta` convention.
+    pub yaw: f32,
+    /// Elevation, using the orbital camera's `phi` convention.
+    pub pitch: f32,
+    /// Current velocity (display units per second), eased towards the target.
+    velocity: DVec3,
+    /// Keyboard thrust keys currently held.
+    held: [bool; 6],
+}
+
+impl FlyCamera {
+    /// Start flying from wherever the orbital camera currently is.
+    pub fn from_orbit(camera: &Camera, anchor: DVec3) -> Self {
+        Self {
+            offset: camera.eye_position() - anchor,
+            yaw: camera.theta,
+            pitch: camera.phi,
+            velocity: DVec3::ZERO,
+            held: [false; 6],
+        }
+    }
+
+    /// Unit view direction.
+    pub fn forward(&self) -> Vec3 {
+        -Vec3::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.sin(),
+        )
+    }
+
+    /// Unit vector to the right of the view direction, kept horizontal.
+    pub fn right(&self) -> Vec3 {
+        self.forward().cross(Vec3::Y).normalize()
+    }
+
+    /// Mouse-look from drag deltas (pixels): drag right looks right.
+    pub fn look(&mut self, dx: f32, dy: f32) {
+        self.yaw += dx * FLY_LOOK_SENSITIVITY;
+        self.pitch = (self.pitch + dy * FLY_LOOK_SENSITIVITY).clamp(-PHI_CLAMP, PHI_CLAMP);
+    }
+
+    /// Press or release a thrust key.
+    pub fn set_held(&mut self, direction: FlyDirection, pressed: bool) {
+        let i = FlyDirection::ALL
+            .iter()
+            .position(|d| *d == direction)
+            .unwrap_or(0);
+        self.held[i] = pressed;
+    }
+
+    /// Release every thrust key (e.g. when the window loses focus).
+    pub fn release_all(&mut self) {
+        self.held = [false; 6];
+    }
+
+    /// Thrust from the keys currently held.
+    pub fn keyboard_thrust(&self) -> Vec3 {
+        FlyDirection::ALL
+            .iter()
+            .zip(self.held)
+            .filter(|(_, held)| *held)
+            .map(|(d, _)| d.axis())
+            .sum()
+    }
+
+    /// Integrate one frame: turn by `input.look_rate`, then ease the velocity
+    /// towards `input.thrust × speed` and move.
+    pub fn update(&mut self, dt: f32, input: FlyInput, speed: f32) {
+        self.yaw += input.look_rate.x * dt;
+        self.pitch = (self.pitch + input.look_rate.y * dt).clamp(-PHI_CLAMP, PHI_CLAMP);
+
+        let thrust = input.thrust.clamp_length_max(1.0);
+        let world = self.right() * thrust.x + Vec3::Y * thrust.y + self.forward() * thrust.z;
+        let target_velocity = (world * speed).as_dvec3();
+
+        let blend = (dt * FLY_ACCELERATION).min(1.0) as f64;
+        self.velocity += (target_velocity - self.velocity) * blend;
+        self.offset += self.velocity * dt as f64;
+    }
+
+    /// Write this pose into the orbital camera, cancelling its transitions.
+    ///
+    /// The orbit target is placed `camera.distance` ahead, so the orbital
+    /// eye lands exactly on the fly camera's position.
+    pub fn apply_to(&self, camera: &mut Camera, anchor: DVec3) {
+        camera.theta = self.yaw;
+        camera.phi = self.pitch;
+        camera.lerp_target = None;
+        camera.transition = None;
+        let eye = anchor + self.offset;
+        camera.target = eye + (self.forward() * camera.distance).as_dvec3();
+    }
+}
+
+/// Cruise speed at `eye`: proportional to the distance to the nearest body
+/// surface, so flying is precise near planets and fast between them.
+pub fn cruise_speed(eye: DVec3, bodies: &[CelestialBody]) -> f32 {
+    let clearance = bodies
+        .iter()
+        .map(|b| (eye.distance(b.position) - b.display_radius as f64).max(0.0))
+        .fold(f64::INFINITY, f64::min);
+    if !clearance.is_finite() {
+        return FLY_MAX_SPEED;
+    }
+    (clearance as f32 * FLY_SPEED_PER_DISTANCE).clamp(FLY_MIN_SPEED, FLY_MAX_SPEED)
+}
@@ -0,0 +1,118 @@
+//! Label font: glyph lookup and text layout over the SDF atlas baked by `build.rs`.
+//!
+//! Layout works in device pixels with the origin at the top-left of the
+//! canvas, matching the label shader. `size_px` is the glyph cap height.
+
+use glam::Vec2;
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext as GL;
+
+mod atlas {
+    include!(concat!(env!("OUT_DIR"), "/font_atlas.rs"));
+}
+
+pub use atlas::{ATLAS, ATLAS_HEIGHT, ATLAS_WIDTH};
+
+/// Horizontal advance per character, in font pixels (glyph + 1 px spacing).
+const ADVANCE: f32 = atlas::GLYPH_WIDTH as f32 + 1.0;
+
+/// One textured quad of laid-out text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphQuad {
+    /// Top-left corner in device pixels.
+    pub min: Vec2,
+    /// Bottom-right corner in device pixels.
+    pub max: Vec2,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+}
+
+/// Atlas cell of `c`; characters outside the font fall back to `'?'`.
+fn glyph_index(c: char) -> usize {
+    let code = c as u32;
+    if code >= atlas::FIRST_CHAR && ((code - atlas::FIRST_CHAR) as usize) < atlas::ASCII_COUNT {
+        (code - atlas::FIRST_CHAR) as usize
+    } else if let Some(i) = atlas::EXTRA_CHARS.iter().position(|&extra| extra == c) {
+        atlas::ASCII_COUNT + i
+    } else {
+        ('?' as u32 - atlas::FIRST_CHAR) as usize
+    }
+}
+
+/// Texture coordinates `(min, max)` of the whole cell (glyph + padding) for `c`.
+pub fn glyph_uv(c: char) -> (Vec2, Vec2) {
+    let index = glyph_index(c);
+    let x = (index % atlas::COLUMNS * atlas::CELL_WIDTH) as f32;
+    let y = (index / atlas::COLUMNS * atlas::CELL_HEIGHT) as f32;
+    let size = Vec2::new(ATLAS_WIDTH as f32, ATLAS_HEIGHT as f32);
+    let min = Vec2::new(x, y) / size;
+    let max = Vec2::new(x + atlas::CELL_WIDTH as f32, y + atlas::CELL_HEIGHT as f32) / size;
+    (min, max)
+}
+
+/// Width in device pixels of `text` at cap height `size_px`.
+pub fn text_width(text: &str, size_px: f32) -> f32 {
+    let count = text.chars().count();
+    if count == 0 {
+        return 0.0;
+    }
+    let unit = size_px / atlas::GLYPH_HEIGHT as f32;
+    (count as f32 * ADVANCE - 1.0) * unit
+}
+
+/// Lay out `text` with its top-left glyph corner at `origin`.
+///
+/// Quads include the atlas padding so the distance field can fall off
+/// smoothly (for anti-aliasing and the halo); they therefore extend a little
+/// beyond [`text_width`].
+pub fn layout(text: &str, origin: Vec2, size_px: f32) -> impl Iterator<Item = GlyphQuad> + '_ {
+    let unit = size_px / atlas::GLYPH_HEIGHT as f32;
+    let padding = atlas::CELL_PADDING as f32 / atlas::TEXELS_PER_PIXEL as f32 * unit;
+    let cell = Vec2::new(
+        atlas::CELL_WIDTH as f32,
+        atlas::CELL_HEIGHT as f32,
+    ) / atlas::TEXELS_PER_PIXEL as f32
+        * unit;
+
+    text.chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(move |(i, c)| {
+            let min = origin + Vec2::new(i as f32 * ADVANCE * unit - padding, -padding);
+            let (uv_min, uv_max) = glyph_uv(c);
+            GlyphQuad {
+                min,
+                max: min + cell,
+                uv_min,
+                uv_max,
+            }
+        })
+}
+
+/// Upload the atlas as a single-channel, mipmapped texture.
+pub fn create_atlas_texture(gl: &GL) -> Result<web_sys::WebGlTexture, JsValue> {
+    let texture = gl
+        .create_texture()
+        .ok_or_else(|| JsValue::from_str("Failed to create font atlas texture"))?;
+    gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+    gl.pixel_storei(GL::UNPACK_ALIGNMENT, 1);
+    gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+        GL::TEXTURE_2D,
+        0,
+        GL::R8 as i32,
+        ATLAS_WIDTH as i32,
+        ATLAS_HEIGHT as i32,
+        0,
+        GL::RED,
+        GL::UNSIGNED_BYTE,
+        Some(ATLAS),
+    )?;
+    gl.pixel_storei(GL::UNPACK_ALIGNMENT, 4);
+    gl.generate_mipmap(GL::TEXTURE_2D);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR_MIPMAP_LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+    gl.bind_texture(GL::TEXTURE_2D, None);
+    Ok(texture)
+}
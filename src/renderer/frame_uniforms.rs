@@ -0,0 +1,81 @@
+//! Frame-constant uniform buffer (`Frame` block in `shaders/frame.glsl`).
+//!
+//! View, projection, light and eye position are written once per frame into
+//! a uniform buffer object bound at [`FRAME_UNIFORM_BINDING`], instead of
+//! being re-uploaded by every program that needs them. Every scene shader
+//! reads them from there; only the screen-space text shaders do without.
+
+use glam::{Mat4, Vec3};
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext as GL;
+
+use super::render_pass::FrameContext;
+
+/// Uniform-buffer binding point of the `Frame` block.
+pub const FRAME_UNIFORM_BINDING: u32 = 0;
+
+/// Name of the uniform block in GLSL.
+pub const FRAME_BLOCK_NAME: &str = "Frame";
+
+/// Size of the packed block in floats (std140: 2 × mat4 + 3 × vec4).
+pub const FRAME_BLOCK_FLOATS: usize = 16 + 16 + 4 + 4 + 4;
+
+/// Pack the `Frame` block following std140 layout rules.
+pub fn pack(
+    view: &Mat4,
+    projection: &Mat4,
+    light_pos: Vec3,
+    eye_position: Vec3,
+    time: f32,
+) -> [f32; FRAME_BLOCK_FLOATS] {
+    let mut data = [0.0; FRAME_BLOCK_FLOATS];
+    data[0..16].copy_from_slice(&view.to_cols_array());
+    data[16..32].copy_from_slice(&projection.to_cols_array());
+    data[32..35].copy_from_slice(&light_pos.to_array());
+    data[36..39].copy_from_slice(&eye_position.to_array());
+    data[40] = time;
+    data
+}
+
+/// GPU buffer backing the `Frame` block.
+pub struct FrameUniforms {
+    buffer: web_sys::WebGlBuffer,
+}
+
+impl FrameUniforms {
+    /// Allocate the buffer and attach it to [`FRAME_UNIFORM_BINDING`].
+    pub fn new(gl: &GL) -> Result<Self, JsValue> {
+        let buffer = gl
+            .create_buffer()
+            .ok_or_else(|| JsValue::from_str("Failed to create frame UBO"))?;
+        gl.bind_buffer(GL::UNIFORM_BUFFER, Some(&buffer));
+        gl.buffer_data_with_i32(
+            GL::UNIFORM_BUFFER,
+            (FRAME_BLOCK_FLOATS * 4) as i32,
+            GL::DYNAMIC_DRAW,
+        );
+        gl.bind_buffer(GL::UNIFORM_BUFFER, None);
+        gl.bind_buffer_base(GL::UNIFORM_BUFFER, FRAME_UNIFORM_BINDING, Some(&buffer));
+        Ok(Self { buffer })
+    }
+
+    /// Upload this frame's constants.
+    pub fn update(&self, ctx: &FrameContext, data: &[f32; FRAME_BLOCK_FLOATS]) {
+        ctx.bind_buffer(GL::UNIFORM_BUFFER, Some(&self.buffer));
+        unsafe {
+            let array = js_sys::Float32Array::view(data);
+            ctx.gl.buffer_sub_data_with_i32_and_array_buffer_view(
+                GL::UNIFORM_BUFFER,
+                0,
+                &array,
+            );
+        }
+        ctx.bind_buffer(GL::UNIFORM_BUFFER, None);
+    }
+
+    /// Detach and delete the buffer.
+    pub fn delete(&self, gl: &GL) {
+        gl.bind_buffer_base(GL::UNIFORM_BUFFER, FRAME_UNIFORM_BINDING, None);
+        gl.delete_buffer(Some(&self.buffer));
+    }
+}
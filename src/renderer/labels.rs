@@ -0,0 +1,124 @@
+//! Screen-space placement of body labels.
+//!
+//! Pure helpers used by the label pass: projection to device pixels,
+//! distance fade, greedy overlap culling, and the hover tooltip's text. Kept
+//! free of GL so they can be tested natively.
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::constants::{LABEL_FADE_END, LABEL_FADE_START};
+use crate::i18n::I18n;
+
+/// What the label pass draws next to each body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelMode {
+    Off,
+    #[default]
+    Names,
+    /// Name followed by the distance from the camera in AU.
+    NamesAndDistance,
+}
+
+impl LabelMode {
+    /// Next mode in the Off → Names → Names + distance cycle.
+    pub fn next(self) -> Self {
+        match self {
+            LabelMode::Off => LabelMode::Names,
+            LabelMode::Names => LabelMode::NamesAndDistance,
+            LabelMode::NamesAndDistance => LabelMode::Off,
+        }
+    }
+
+    /// Short identifier used in shared links.
+    pub fn id(self) -> &'static str {
+        match self {
+            LabelMode::Off => "off",
+            LabelMode::Names => "names",
+            LabelMode::NamesAndDistance => "distance",
+        }
+    }
+
+    /// Inverse of [`LabelMode::id`].
+    pub fn parse(id: &str) -> Option<Self> {
+        [LabelMode::Off, LabelMode::Names, LabelMode::NamesAndDistance]
+            .into_iter()
+            .find(|mode| mode.id() == id)
+    }
+}
+
+/// Axis-aligned rectangle in device pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+}
+
+/// A label competing for screen space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelPlacement {
+    pub rect: Rect,
+    /// Higher wins when two labels overlap.
+    pub priority: f32,
+}
+
+/// Project a camera-relative point to device pixels (origin top-left).
+///
+/// Returns `None` for points behind the camera.
+pub fn project_to_screen(view_projection: &Mat4, relative: Vec3, viewport: Vec2) -> Option<Vec2> {
+    let clip = *view_projection * relative.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    Some(Vec2::new(
+        (ndc.x * 0.5 + 0.5) * viewport.x,
+        (0.5 - ndc.y * 0.5) * viewport.y,
+    ))
+}
+
+/// Label opacity for a body `distance` display units from the camera.
+///
+/// Fully opaque up to [`LABEL_FADE_START`], fading out smoothly by
+/// [`LABEL_FADE_END`].
+pub fn fade_alpha(distance: f32) -> f32 {
+    let t = ((distance - LABEL_FADE_START) / (LABEL_FADE_END - LABEL_FADE_START)).clamp(0.0, 1.0);
+    1.0 - t * t * (3.0 - 2.0 * t)
+}
+
+/// Greedy overlap culling: returns the indices of the labels to draw,
+/// highest priority first. A label is dropped if it overlaps any label
+/// already accepted.
+pub fn cull_overlaps(labels: &[LabelPlacement]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..labels.len()).collect();
+    order.sort_by(|&a, &b| labels[b].priority.total_cmp(&labels[a].priority));
+
+    let mut accepted: Vec<usize> = Vec::with_capacity(labels.len());
+    for i in order {
+        if accepted.iter().all(|&j| !labels[i].rect.overlaps(&labels[j].rect)) {
+            accepted.push(i);
+        }
+    }
+    accepted
+}
+
+/// Tooltip for the hovered body `id`: its name and distance in AU — from the
+/// Sun for a planet, from the camera for a star.
+pub fn tooltip_text(id: &str, is_star: bool, distance_au: f64, i18n: &I18n) -> String {
+    let key = if is_star { "tooltip.star" } else { "tooltip.planet" };
+    i18n.format(
+        key,
+        &[
+            ("name", i18n.body_name(id)),
+            ("distance", &i18n.number(distance_au, 2)),
+        ],
+    )
+}
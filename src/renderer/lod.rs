@@ -0,0 +1,36 @@
+//! Level-of-detail selection for sphere meshes.
+//!
+//! Each body picks a mesh per frame from its projected screen radius, so a
+//! planet filling the screen gets a dense sphere while distant dots cost a
+//! handful of triangles.
+
+use glam::Mat4;
+
+/// Approximate on-screen radius, in device pixels, of a sphere of `radius`
+/// seen from `distance` (eye to centre).
+///
+/// Returns `f32::INFINITY` when the eye is inside the sphere.
+pub fn projected_radius_px(
+    radius: f32,
+    distance: f32,
+    projection: &Mat4,
+    viewport_height: f32,
+) -> f32 {
+    if distance <= radius {
+        return f32::INFINITY;
+    }
+    // `y_axis.y` of a perspective matrix is `1 / tan(fov_y / 2)`.
+    radius / distance * projection.y_axis.y * viewport_height * 0.5
+}
+
+/// Pick a LOD index (0 = finest) for a projected radius.
+///
+/// `thresholds_px` holds the minimum radius for each LOD except the coarsest,
+/// in descending order; anything smaller than the last threshold gets
+/// `thresholds_px.len()`.
+pub fn select_lod(radius_px: f32, thresholds_px: &[f32]) -> usize {
+    thresholds_px
+        .iter()
+        .position(|&t| radius_px >= t)
+        .unwrap_or(thresholds_px.len())
+}
@@ -0,0 +1,92 @@
+//! Measuring tool: distances and angles between bodies.
+//!
+//! Shift-clicking bodies collects them in a [`Measurement`]. Two bodies
+//! measure the distance between them, three also the angle at the middle
+//! one. Values are recomputed from the bodies' positions every frame, so
+//! they follow the simulation clock. Pure geometry, kept free of GL so it
+//! can be tested natively; the measure pass draws the result.
+
+use glam::DVec3;
+
+use crate::constants::{AU_KM, AU_TO_DISPLAY, SPEED_OF_LIGHT_KM_S};
+use crate::i18n::I18n;
+
+/// Most bodies one measurement links.
+pub const MAX_MEASURED: usize = 3;
+
+/// Bodies being measured, in the order they were picked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Measurement {
+    /// Indices into the simulation's bodies.
+    bodies: Vec<usize>,
+}
+
+impl Measurement {
+    pub fn bodies(&self) -> &[usize] {
+        &self.bodies
+    }
+
+    /// Add body `idx`, or drop it if it is already measured. Picking a body
+    /// beyond [`MAX_MEASURED`] starts a new measurement from it.
+    pub fn toggle(&mut self, idx: usize) {
+        if let Some(i) = self.bodies.iter().position(|&b| b == idx) {
+            self.bodies.remove(i);
+            return;
+        }
+        if self.bodies.len() == MAX_MEASURED {
+            self.bodies.clear();
+        }
+        self.bodies.push(idx);
+    }
+
+    pub fn clear(&mut self) {
+        self.bodies.clear();
+    }
+}
+
+/// A distance in the units the tool shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distance {
+    pub km: f64,
+    pub au: f64,
+    /// Light travel time in minutes.
+    pub light_minutes: f64,
+}
+
+impl Distance {
+    /// Distance between two positions in display units.
+    pub fn between(a: DVec3, b: DVec3) -> Self {
+        let au = a.distance(b) / AU_TO_DISPLAY as f64;
+        let km = au * AU_KM;
+        Self {
+            km,
+            au,
+            light_minutes: km / SPEED_OF_LIGHT_KM_S / 60.0,
+        }
+    }
+
+    /// `1.52 AU · 227.9 million km · 12.7 light-min`, localised.
+    pub fn label(&self, i18n: &I18n) -> String {
+        i18n.format(
+            "measure.distance",
+            &[
+                ("au", &i18n.number(self.au, 2)),
+                ("million_km", &i18n.number(self.km / 1.0e6, 1)),
+                ("minutes", &i18n.number(self.light_minutes, 1)),
+            ],
+        )
+    }
+}
+
+/// Angle in degrees at `vertex` between the directions to `a` and `b`;
+/// `None` when either coincides with the vertex.
+pub fn angle_deg(a: DVec3, vertex: DVec3, b: DVec3) -> Option<f64> {
+    let u = (a - vertex).try_normalize()?;
+    let v = (b - vertex).try_normalize()?;
+    Some(u.dot(v).clamp(-1.0, 1.0).acos().to_degrees())
+}
+
+/// `63.4°`, localised.
+pub fn angle_label(degrees: f64, i18n: &I18n) -> String {
+    i18n.format("measure.angle", &[("degrees", &i18n.number(degrees, 1))])
+}
@@ -1,33 +1,227 @@
-//! Mesh generation (sphere, ring) and GPU upload utilities.
+//! Mesh generation (sphere, cube-sphere, ring) and GPU upload utilities.
 //!
-//! A [`Mesh`] holds CPU-side vertex + index data. Upload it with
-//! [`create_mesh_vao`] to get a ready-to-draw VAO on the GPU.
+//! A [`Mesh`] holds CPU-side vertex + index data, assembled and validated by
+//! a [`MeshBuilder`]. Upload it with [`create_mesh_vao`] to get a ready-to-draw
+//! VAO on the GPU, or [`create_gpu_mesh`] to also keep its index count and
+//! index type alongside. [`delete_vao`] frees a VAO and its buffers again.
+
+use std::collections::HashMap;
+use std::fmt;
 
 use glam::Vec3;
-use wasm_bindgen::JsValue;
+use serde::Deserialize;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::WebGl2RenderingContext as GL;
 
 use crate::constants::*;
 
 // ─── CPU-side mesh ──────────────────────────────────────────────────────
 
-/// Interleaved vertex data (pos.xyz + norm.xyz + uv.xy) and u16 indices.
+/// Floats per interleaved vertex: pos.xyz + norm.xyz + uv.xy.
+pub const VERTEX_FLOATS: usize = 8;
+
+/// Largest vertex count that can use 16-bit indices.
+///
+/// WebGL2 always enables primitive restart, so index `0xFFFF` is reserved
+/// and the last usable 16-bit index is `0xFFFE`.
+pub const MAX_U16_VERTICES: usize = u16::MAX as usize;
+
+/// Triangle-list indices, stored as `u16` whenever every index fits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Indices {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Indices {
+    /// Pick the narrowest index type able to address `vertex_count` vertices.
+    pub fn narrowest(indices: Vec<u32>, vertex_count: usize) -> Self {
+        if vertex_count <= MAX_U16_VERTICES {
+            Indices::U16(indices.into_iter().map(|i| i as u16).collect())
+        } else {
+            Indices::U32(indices)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U16(v) => v.len(),
+            Indices::U32(v) => v.len(),
+        }
+    }
+
+    /// GL enum for `draw_elements` (`UNSIGNED_SHORT` or `UNSIGNED_INT`).
+    pub fn gl_type(&self) -> u32 {
+        match self {
+            Indices::U16(_) => GL::UNSIGNED_SHORT,
+            Indices::U32(_) => GL::UNSIGNED_INT,
+        }
+    }
+}
+
+/// Interleaved vertex data (pos.xyz + norm.xyz + uv.xy) and triangle indices.
 pub struct Mesh {
     pub vertices: Vec<f32>,
-    pub indices: Vec<u16>,
+    pub indices: Indices,
+}
+
+impl Mesh {
+    /// Validate interleaved `vertices` and triangle `indices` into a mesh:
+    /// the stride, triangle count, vertex values and index bounds.
+    pub fn new(vertices: Vec<f32>, indices: Vec<u32>) -> Result<Self, MeshError> {
+        if !vertices.len().is_multiple_of(VERTEX_FLOATS) {
+            return Err(MeshError::StrideMismatch {
+                floats: vertices.len(),
+                stride: VERTEX_FLOATS,
+            });
+        }
+        if vertices.is_empty() || indices.is_empty() {
+            return Err(MeshError::Empty);
+        }
+        if !indices.len().is_multiple_of(3) {
+            return Err(MeshError::IncompleteTriangle {
+                index_count: indices.len(),
+            });
+        }
+        if let Some(float_offset) = vertices.iter().position(|v| !v.is_finite()) {
+            return Err(MeshError::NonFinite { float_offset });
+        }
+        let vertex_count = vertices.len() / VERTEX_FLOATS;
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(MeshError::IndexOutOfBounds { index, vertex_count });
+        }
+
+        Ok(Mesh {
+            indices: Indices::narrowest(indices, vertex_count),
+            vertices,
+        })
+    }
+}
+
+/// Why [`Mesh::new`] refused to produce a mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshError {
+    /// No vertices or no triangles.
+    Empty,
+    /// Vertex data length is not a multiple of [`VERTEX_FLOATS`].
+    StrideMismatch { floats: usize, stride: usize },
+    /// Index count is not a multiple of 3.
+    IncompleteTriangle { index_count: usize },
+    /// A triangle references a vertex that does not exist.
+    IndexOutOfBounds { index: u32, vertex_count: usize },
+    /// A vertex attribute is NaN or infinite.
+    NonFinite { float_offset: usize },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::Empty => write!(f, "mesh has no triangles"),
+            MeshError::StrideMismatch { floats, stride } => {
+                write!(f, "{floats} vertex floats is not a multiple of the {stride}-float stride")
+            }
+            MeshError::IncompleteTriangle { index_count } => {
+                write!(f, "{index_count} indices do not form whole triangles")
+            }
+            MeshError::IndexOutOfBounds { index, vertex_count } => {
+                write!(f, "index {index} out of bounds for {vertex_count} vertices")
+            }
+            MeshError::NonFinite { float_offset } => {
+                write!(f, "non-finite vertex data at float {float_offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+/// Accumulates vertices and triangles, then validates them into a [`Mesh`].
+///
+/// Indices are collected as `u32`; [`MeshBuilder::build`] narrows them to
+/// `u16` when the vertex count allows, so small meshes stay compact while
+/// large ones (dense spheres, belts) are not capped at 65 535 vertices.
+#[derive(Default)]
+pub struct MeshBuilder {
+    vertices: Vec<f32>,
+    indices: Vec<u32>,
+}
+
+impl MeshBuilder {
+    /// Pre-allocate room for `vertices` vertices and `indices` indices.
+    pub fn with_capacity(vertices: usize, indices: usize) -> Self {
+        Self {
+            vertices: Vec::with_capacity(vertices * VERTEX_FLOATS),
+            indices: Vec::with_capacity(indices),
+        }
+    }
+
+    /// Append one vertex and return its index.
+    pub fn vertex(&mut self, position: [f32; 3], normal: [f32; 3], uv: [f32; 2]) -> u32 {
+        let index = self.vertex_count() as u32;
+        self.vertices.extend_from_slice(&position);
+        self.vertices.extend_from_slice(&normal);
+        self.vertices.extend_from_slice(&uv);
+        index
+    }
+
+    /// Append one triangle (counter-clockwise when seen from the front).
+    pub fn triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+
+    /// Number of complete vertices added so far.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / VERTEX_FLOATS
+    }
+
+    /// Validate the collected data; see [`Mesh::new`].
+    pub fn build(self) -> Result<Mesh, MeshError> {
+        Mesh::new(self.vertices, self.indices)
+    }
 }
 
 // ─── Sphere ──────────────────────────────────────────────────────────────
 
-/// Generate a UV-sphere with the default segment/ring counts from [`constants`].
-pub fn generate_sphere() -> Mesh {
-    generate_sphere_custom(SPHERE_SEGMENTS, SPHERE_RINGS)
+/// Tessellation scheme used for planet and Sun spheres (the `sphereKind`
+/// viewer option).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SphereKind {
+    /// Latitude/longitude grid — simple, but triangles pinch at the poles.
+    Uv,
+    /// Subdivided cube projected onto the sphere — near-uniform triangles.
+    #[default]
+    CubeSphere,
+}
+
+/// Generate one sphere per level of detail, finest first.
+///
+/// Resolutions come from [`SPHERE_LOD_RESOLUTIONS`] or
+/// [`CUBE_SPHERE_LOD_SUBDIVISIONS`] depending on `kind`.
+pub fn generate_sphere_lods(kind: SphereKind) -> Vec<Mesh> {
+    match kind {
+        SphereKind::Uv => SPHERE_LOD_RESOLUTIONS
+            .iter()
+            .map(|&(segments, rings)| generate_sphere_custom(segments, rings))
+            .collect(),
+        SphereKind::CubeSphere => CUBE_SPHERE_LOD_SUBDIVISIONS
+            .iter()
+            .map(|&n| generate_cube_sphere(n))
+            .collect(),
+    }
 }
 
 /// Generate a UV-sphere with custom resolution.
+///
+/// Resolutions above ~255×255 produce more than 65 535 vertices and
+/// automatically switch to 32-bit indices.
 pub fn generate_sphere_custom(segments: u32, rings: u32) -> Mesh {
-    let mut vertices = Vec::new();
-    let mut indices = Vec::new();
+    let segments = segments.max(3);
+    let rings = rings.max(2);
+    let mut builder = MeshBuilder::with_capacity(
+        ((segments + 1) * (rings + 1)) as usize,
+        (segments * rings * 6) as usize,
+    );
 
     for y in 0..=rings {
         let v = y as f32 / rings as f32;
@@ -37,21 +231,9 @@ pub fn generate_sphere_custom(segments: u32, rings: u32) -> Mesh {
             let u = x as f32 / segments as f32;
             let theta = u * std::f32::consts::TAU;
 
-            let px = phi.sin() * theta.cos();
-            let py = phi.cos();
-            let pz = phi.sin() * theta.sin();
-
-            // Position
-            vertices.push(px);
-            vertices.push(py);
-            vertices.push(pz);
-            // Normal (same as position on a unit sphere)
-            vertices.push(px);
-            vertices.push(py);
-            vertices.push(pz);
-            // UV (equirectangular)
-            vertices.push(u);
-            vertices.push(v);
+            let p = [phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin()];
+            // Normal is the same as position on a unit sphere; UV is equirectangular.
+            builder.vertex(p, p, [u, v]);
         }
     }
 
@@ -60,17 +242,116 @@ pub fn generate_sphere_custom(segments: u32, rings: u32) -> Mesh {
             let a = y * (segments + 1) + x;
             let b = a + segments + 1;
 
-            indices.push(a as u16);
-            indices.push(b as u16);
-            indices.push((a + 1) as u16);
+            // Counter-clockwise when seen from outside (front faces survive culling).
+            builder.triangle(a, a + 1, b);
+            builder.triangle(a + 1, b + 1, b);
+        }
+    }
+
+    builder.build().expect("UV sphere indices are in bounds")
+}
 
-            indices.push((a + 1) as u16);
-            indices.push(b as u16);
-            indices.push((b + 1) as u16);
+// ─── Cube-sphere ────────────────────────────────────────────────────────
+
+/// Cube faces as `(normal, u axis, v axis)`, with `u × v = normal`.
+const CUBE_FACES: [(Vec3, Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+    (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+    (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+    (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+    (Vec3::Z, Vec3::X, Vec3::Y),
+    (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+];
+
+/// Generate a cube-sphere: each cube face is split into `subdivisions²`
+/// quads and projected onto the unit sphere.
+///
+/// UVs use the same equirectangular mapping as [`generate_sphere_custom`], so
+/// existing planet textures apply unchanged. Vertices are duplicated along
+/// the longitude seam (with `u > 1`, relying on `REPEAT` wrapping) and at the
+/// poles, so no triangle interpolates across the whole texture.
+pub fn generate_cube_sphere(subdivisions: u32) -> Mesh {
+    let n = subdivisions.max(1);
+    let mut builder = MeshBuilder::with_capacity(
+        (6 * (n + 1) * (n + 1)) as usize,
+        (6 * n * n * 6) as usize,
+    );
+    let mut lookup: HashMap<[u32; 5], u32> = HashMap::new();
+
+    for (normal, u_axis, v_axis) in CUBE_FACES {
+        let point = |i: u32, j: u32| {
+            let s = i as f32 / n as f32 * 2.0 - 1.0;
+            let t = j as f32 / n as f32 * 2.0 - 1.0;
+            spherify(normal + u_axis * s + v_axis * t)
+        };
+
+        for j in 0..n {
+            for i in 0..n {
+                let p00 = point(i, j);
+                let p10 = point(i + 1, j);
+                let p11 = point(i + 1, j + 1);
+                let p01 = point(i, j + 1);
+
+                for tri in [[p00, p10, p11], [p00, p11, p01]] {
+                    let mut corners = [0; 3];
+                    for (k, (p, (u, v))) in tri.into_iter().zip(triangle_uvs(&tri)).enumerate() {
+                        let key = [p.x.to_bits(), p.y.to_bits(), p.z.to_bits(), u.to_bits(), v.to_bits()];
+                        corners[k] = *lookup
+                            .entry(key)
+                            .or_insert_with(|| builder.vertex(p.to_array(), p.to_array(), [u, v]));
+                    }
+                    builder.triangle(corners[0], corners[1], corners[2]);
+                }
+            }
         }
     }
 
-    Mesh { vertices, indices }
+    builder.build().expect("cube-sphere indices are in bounds")
+}
+
+/// Map a point on the cube `[-1, 1]³` onto the unit sphere.
+///
+/// Uses the area-preserving-ish mapping from Philip Nowell rather than a
+/// plain normalisation, which keeps triangle sizes much more uniform.
+fn spherify(p: Vec3) -> Vec3 {
+    let (x2, y2, z2) = (p.x * p.x, p.y * p.y, p.z * p.z);
+    Vec3::new(
+        p.x * (1.0 - y2 / 2.0 - z2 / 2.0 + y2 * z2 / 3.0).sqrt(),
+        p.y * (1.0 - z2 / 2.0 - x2 / 2.0 + z2 * x2 / 3.0).sqrt(),
+        p.z * (1.0 - x2 / 2.0 - y2 / 2.0 + x2 * y2 / 3.0).sqrt(),
+    )
+    .normalize()
+}
+
+/// Equirectangular UVs for one triangle, fixed up so the triangle never
+/// straddles the longitude seam or collapses at a pole.
+fn triangle_uvs(tri: &[Vec3; 3]) -> [(f32, f32); 3] {
+    let mut uv = tri.map(|p| {
+        let u = p.z.atan2(p.x).rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU;
+        let v = p.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI;
+        (u, v)
+    });
+
+    // Seam: unwrap longitudes that jumped from ~1 back to ~0.
+    let (min_u, max_u) = uv
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), &(u, _)| (lo.min(u), hi.max(u)));
+    if max_u - min_u > 0.5 {
+        for (u, _) in &mut uv {
+            if *u < 0.5 {
+                *u += 1.0;
+            }
+        }
+    }
+
+    // Pole: longitude is undefined, borrow the mean of the other two corners.
+    for k in 0..3 {
+        if tri[k].y.abs() > 1.0 - 1e-6 {
+            uv[k].0 = (uv[(k + 1) % 3].0 + uv[(k + 2) % 3].0) / 2.0;
+        }
+    }
+
+    uv
 }
 
 // ─── Ring (annulus) ─────────────────────────────────────────────────────
@@ -82,8 +363,10 @@ pub fn generate_ring() -> Mesh {
 
 /// Generate a flat annulus in the XZ plane with custom parameters.
 pub fn generate_ring_custom(inner: f32, outer: f32, segments: u32) -> Mesh {
-    let mut vertices = Vec::new();
-    let mut indices = Vec::new();
+    let segments = segments.max(3);
+    let mut builder =
+        MeshBuilder::with_capacity(((segments + 1) * 2) as usize, (segments * 6) as usize);
+    let up = [0.0, 1.0, 0.0];
 
     for i in 0..=segments {
         let angle = (i as f32 / segments as f32) * std::f32::consts::TAU;
@@ -91,43 +374,64 @@ pub fn generate_ring_custom(inner: f32, outer: f32, segments: u32) -> Mesh {
         let sin_a = angle.sin();
         let u = i as f32 / segments as f32;
 
-        // Inner vertex (pos + normal up + uv)
-        vertices.push(inner * cos_a);
-        vertices.push(0.0);
-        vertices.push(inner * sin_a);
-        vertices.push(0.0);
-        vertices.push(1.0);
-        vertices.push(0.0);
-        vertices.push(u);
-        vertices.push(0.0);
-
-        // Outer vertex (pos + normal up + uv)
-        vertices.push(outer * cos_a);
-        vertices.push(0.0);
-        vertices.push(outer * sin_a);
-        vertices.push(0.0);
-        vertices.push(1.0);
-        vertices.push(0.0);
-        vertices.push(u);
-        vertices.push(1.0);
+        builder.vertex([inner * cos_a, 0.0, inner * sin_a], up, [u, 0.0]);
+        builder.vertex([outer * cos_a, 0.0, outer * sin_a], up, [u, 1.0]);
     }
 
     for i in 0..segments {
         let base = i * 2;
-        indices.push(base as u16);
-        indices.push((base + 1) as u16);
-        indices.push((base + 2) as u16);
-
-        indices.push((base + 1) as u16);
-        indices.push((base + 3) as u16);
-        indices.push((base + 2) as u16);
+        builder.triangle(base, base + 1, base + 2);
+        builder.triangle(base + 1, base + 3, base + 2);
     }
 
-    Mesh { vertices, indices }
+    builder.build().expect("ring indices are in bounds")
 }
 
 // ─── GPU upload ──────────────────────────────────────────────────────────
 
+/// An uploaded indexed mesh, ready to draw.
+///
+/// Cloning shares the same GPU objects (the handles are reference-counted JS values).
+#[derive(Clone)]
+pub struct GpuMesh {
+    pub vao: web_sys::WebGlVertexArrayObject,
+    pub index_count: i32,
+    /// `UNSIGNED_SHORT` or `UNSIGNED_INT`, as chosen by [`Indices::narrowest`].
+    pub index_type: u32,
+}
+
+impl GpuMesh {
+    /// Issue an indexed triangle draw for this mesh (VAO must be bound).
+    pub fn draw(&self, gl: &GL) {
+        gl.draw_elements_with_i32(GL::TRIANGLES, self.index_count, self.index_type, 0);
+    }
+
+    /// Delete the VAO and its buffers (shared with every clone).
+    pub fn delete(&self, gl: &GL) {
+        delete_vao(gl, &self.vao);
+    }
+
+    /// Issue an instanced triangle draw for this mesh (VAO must be bound).
+    pub fn draw_instanced(&self, gl: &GL, instances: i32) {
+        gl.draw_elements_instanced_with_i32(
+            GL::TRIANGLES,
+            self.index_count,
+            self.index_type,
+            0,
+            instances,
+        );
+    }
+}
+
+/// Upload a [`Mesh`] and keep its index count and type for drawing.
+pub fn create_gpu_mesh(gl: &GL, mesh: &Mesh) -> Result<GpuMesh, JsValue> {
+    Ok(GpuMesh {
+        vao: create_mesh_vao(gl, mesh)?,
+        index_count: mesh.indices.len() as i32,
+        index_type: mesh.indices.gl_type(),
+    })
+}
+
 /// Upload a [`Mesh`] (interleaved pos+norm+uv) to a WebGL VAO.
 pub fn create_mesh_vao(gl: &GL, mesh: &Mesh) -> Result<web_sys::WebGlVertexArrayObject, JsValue> {
     let vao = gl
@@ -146,7 +450,7 @@ pub fn create_mesh_vao(gl: &GL, mesh: &Mesh) -> Result<web_sys::WebGlVertexArray
         gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &vert_array, GL::STATIC_DRAW);
     }
 
-    let stride = 8 * 4; // 8 floats × 4 bytes (pos.xyz + norm.xyz + uv.xy)
+    let stride = (VERTEX_FLOATS * 4) as i32; // pos.xyz + norm.xyz + uv.xy
 
     // location 0 — position
     gl.vertex_attrib_pointer_with_i32(0, 3, GL::FLOAT, false, stride, 0);
@@ -167,14 +471,53 @@ pub fn create_mesh_vao(gl: &GL, mesh: &Mesh) -> Result<web_sys::WebGlVertexArray
     gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&ibo));
 
     unsafe {
-        let idx_array = js_sys::Uint16Array::view(&mesh.indices);
-        gl.buffer_data_with_array_buffer_view(GL::ELEMENT_ARRAY_BUFFER, &idx_array, GL::STATIC_DRAW);
+        match &mesh.indices {
+            Indices::U16(indices) => {
+                let idx_array = js_sys::Uint16Array::view(indices);
+                gl.buffer_data_with_array_buffer_view(GL::ELEMENT_ARRAY_BUFFER, &idx_array, GL::STATIC_DRAW);
+            }
+            Indices::U32(indices) => {
+                let idx_array = js_sys::Uint32Array::view(indices);
+                gl.buffer_data_with_array_buffer_view(GL::ELEMENT_ARRAY_BUFFER, &idx_array, GL::STATIC_DRAW);
+            }
+        }
     }
 
     gl.bind_vertex_array(None);
     Ok(vao)
 }
 
+/// Delete `vao` together with the vertex and index buffers bound to it.
+///
+/// The upload helpers keep no handles to the buffers they create, so they
+/// are read back from the VAO's attribute and element-array bindings.
+pub fn delete_vao(gl: &GL, vao: &web_sys::WebGlVertexArrayObject) {
+    gl.bind_vertex_array(Some(vao));
+    let attributes = gl
+        .get_parameter(GL::MAX_VERTEX_ATTRIBS)
+        .ok()
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0) as u32;
+    let mut buffers: Vec<web_sys::WebGlBuffer> = (0..attributes)
+        .filter_map(|i| gl.get_vertex_attrib(i, GL::VERTEX_ATTRIB_ARRAY_BUFFER_BINDING).ok())
+        .filter_map(|b| b.dyn_into().ok())
+        .collect();
+    if let Some(indices) = gl
+        .get_parameter(GL::ELEMENT_ARRAY_BUFFER_BINDING)
+        .ok()
+        .and_then(|b| b.dyn_into().ok())
+    {
+        buffers.push(indices);
+    }
+    gl.bind_vertex_array(None);
+
+    // Deleting a buffer twice (e.g. one shared by several attributes) is a no-op.
+    for buffer in &buffers {
+        gl.delete_buffer(Some(buffer));
+    }
+    gl.delete_vertex_array(Some(vao));
+}
+
 /// Upload a line-strip (Vec3 positions) to a WebGL VAO.
 pub fn create_line_vao(gl: &GL, points: &[Vec3]) -> Result<web_sys::WebGlVertexArrayObject, JsValue> {
     let vao = gl
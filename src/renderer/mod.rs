@@ -2,33 +2,51 @@
 //!
 //! Sub-modules handle the individual concerns:
 //! - [`camera`]      — orbital camera controller
-//! - [`shader`]      — GLSL compilation & uniform helpers
+//! - [`fly_camera`]  — free-fly mode driving the orbital camera
+//! - [`frame_uniforms`] — per-frame uniform buffer shared by the scene shaders
+//! - [`shader`]      — GLSL compilation, `#include` splicing & uniform helpers
 //! - [`mesh`]        — CPU mesh generation & GPU upload
+//! - [`lod`]         — screen-size based level-of-detail selection
+//! - [`measure`]     — distances and angles for the measuring tool
+//! - [`picking`]     — GPU colour-ID picking of bodies, orbits and trails
 //! - [`starfield`]   — procedural background stars
-//! - [`texture`]     — async image → GPU texture loading
+//! - [`texture`]     — async image → texture-array layer loading
 //! - [`render_pass`] — `RenderPass` trait & concrete implementations
 
 pub mod camera;
+pub mod fly_camera;
+pub mod font;
+pub mod frame_uniforms;
+pub mod labels;
+pub mod lod;
+pub mod measure;
 pub mod mesh;
+pub mod picking;
 pub mod render_pass;
 pub mod shader;
 pub mod starfield;
 pub mod texture;
 
 use camera::Camera;
-use mesh::{create_line_vao, create_mesh_vao, create_trail_vao};
+use frame_uniforms::{FRAME_BLOCK_NAME, FRAME_UNIFORM_BINDING, FrameUniforms};
+use labels::LabelMode;
+use measure::Measurement;
+use mesh::{create_gpu_mesh, create_line_vao, create_trail_vao};
+use picking::{PickTarget, PickWindow, Picker};
 use render_pass::{
-    FrameContext, OrbitPass, PlanetPass, RenderPass, RingPass, StarfieldPass, TrailBuffer,
-    TrailPass,
+    FrameContext, FrameStats, HighlightPass, LabelPass, Layers, MeasurePass, OrbitPass, PlanetPass,
+    RenderPass, RingPass, StarfieldPass, SunPass, TrailBuffer, TrailPass,
 };
 use shader::ShaderProgram;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-use texture::TextureMap;
+use texture::{TextureArray, TextureMap};
 use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext as GL;
 
+use crate::accessibility::Preferences;
+use crate::constants::PICK_RADIUS_PX;
+use crate::i18n::I18n;
 use crate::simulation::body::CelestialBody;
 use crate::simulation::orbit;
 
@@ -44,6 +62,23 @@ const RING_VERT: &str = include_str!("../../shaders/ring.vert");
 const RING_FRAG: &str = include_str!("../../shaders/ring.frag");
 const TRAIL_VERT: &str = include_str!("../../shaders/trail.vert");
 const TRAIL_FRAG: &str = include_str!("../../shaders/trail.frag");
+const SUN_VERT: &str = include_str!("../../shaders/sun.vert");
+const SUN_FRAG: &str = include_str!("../../shaders/sun.frag");
+const PROMINENCE_VERT: &str = include_str!("../../shaders/prominence.vert");
+const PROMINENCE_FRAG: &str = include_str!("../../shaders/prominence.frag");
+const NOISE_GLSL: &str = include_str!("../../shaders/noise.glsl");
+const LABEL_VERT: &str = include_str!("../../shaders/label.vert");
+const LABEL_FRAG: &str = include_str!("../../shaders/label.frag");
+const HIGHLIGHT_VERT: &str = include_str!("../../shaders/highlight.vert");
+const HIGHLIGHT_FRAG: &str = include_str!("../../shaders/highlight.frag");
+const FRAME_GLSL: &str = include_str!("../../shaders/frame.glsl");
+const PICK_FRAG: &str = include_str!("../../shaders/pick.frag");
+
+/// Shared GLSL snippets available to `#include "…"` directives.
+const SHADER_INCLUDES: &[(&str, &str)] = &[("noise.glsl", NOISE_GLSL), ("frame.glsl", FRAME_GLSL)];
+
+/// Colour the canvas is cleared to.
+const BACKGROUND: [f32; 4] = [0.04, 0.04, 0.1, 1.0];
 
 // ─── Renderer ────────────────────────────────────────────────────────────
 
@@ -57,8 +92,43 @@ pub struct Renderer {
     /// Textures (populated asynchronously, shared via Rc).
     textures: TextureMap,
 
+    /// Frame-constant uniform buffer (`Frame` block).
+    frame_uniforms: FrameUniforms,
+
+    /// Flat-colour program passes draw into the picking ID buffer with.
+    id_shader: ShaderProgram,
+
+    /// Picking ID buffer; `None` where it could not be created, leaving
+    /// clicks to the ray cast.
+    picker: Option<Picker>,
+
+    /// GPU work counters of the last rendered frame.
+    stats: Cell<FrameStats>,
+
     /// Accumulated time for shader animations.
     render_time: f32,
+
+    /// Drawing-buffer size in device pixels.
+    viewport_width: u32,
+    viewport_height: u32,
+
+    /// Device pixels per CSS pixel, for sizing screen-space overlays.
+    pixel_ratio: f32,
+
+    /// What the label pass draws next to each body.
+    pub label_mode: LabelMode,
+
+    /// Which optional layers (orbits, trails) are drawn.
+    pub layers: Layers,
+
+    /// Bodies linked by the measuring tool.
+    pub measurement: Measurement,
+
+    /// Body under the pointer, highlighted with a tooltip.
+    pub hovered: Option<usize>,
+
+    /// Reduced motion and high contrast; see [`Renderer::set_preferences`].
+    preferences: Preferences,
 }
 
 impl Renderer {
@@ -67,61 +137,76 @@ impl Renderer {
         gl: GL,
         canvas_width: u32,
         canvas_height: u32,
+        pixel_ratio: f32,
         bodies: &[CelestialBody],
+        sphere_kind: mesh::SphereKind,
     ) -> Result<Self, JsValue> {
         // ── Compile shader programs ──
 
-        let planet_shader = ShaderProgram::new(
-            &gl,
-            PLANET_VERT,
-            PLANET_FRAG,
+        // Scene programs read the view, projection, eye, light and time
+        // from the shared `Frame` block; only per-draw values are uniforms.
+        let scene_program = |vert: &str, frag: &str, uniforms: &[&'static str]| {
+            let program = ShaderProgram::new(
+                &gl,
+                &shader::resolve_includes(vert, SHADER_INCLUDES),
+                &shader::resolve_includes(frag, SHADER_INCLUDES),
+                uniforms,
+            )?;
+            program.bind_uniform_block(&gl, FRAME_BLOCK_NAME, FRAME_UNIFORM_BINDING);
+            Ok::<_, JsValue>(program)
+        };
+
+        let planet_shader = scene_program(PLANET_VERT, PLANET_FRAG, &["u_textures"])?;
+        let sun_shader = scene_program(
+            SUN_VERT,
+            SUN_FRAG,
             &[
                 "u_model",
-                "u_view",
-                "u_projection",
-                "u_normal_matrix",
                 "u_color",
-                "u_light_pos",
-                "u_view_pos",
-                "u_is_star",
+                "u_rotation_days",
+                "u_diff_rotation",
                 "u_has_texture",
-                "u_texture",
+                "u_textures",
+                "u_layer",
             ],
         )?;
-        let orbit_shader = ShaderProgram::new(
-            &gl,
-            ORBIT_VERT,
-            ORBIT_FRAG,
-            &["u_view", "u_projection", "u_color"],
-        )?;
-        let star_shader = ShaderProgram::new(
-            &gl,
-            STAR_VERT,
-            STAR_FRAG,
-            &["u_view", "u_projection", "u_time"],
+        let prominence_shader = scene_program(
+            PROMINENCE_VERT,
+            PROMINENCE_FRAG,
+            &["u_center", "u_radius", "u_extent", "u_color"],
         )?;
-        let ring_shader = ShaderProgram::new(
-            &gl,
-            RING_VERT,
-            RING_FRAG,
-            &["u_model", "u_view", "u_projection", "u_color"],
-        )?;
-        let trail_shader = ShaderProgram::new(
-            &gl,
-            TRAIL_VERT,
-            TRAIL_FRAG,
-            &["u_view", "u_projection", "u_color"],
+        let line_uniforms = ["u_model", "u_color", "u_alpha"];
+        let orbit_shader = scene_program(ORBIT_VERT, ORBIT_FRAG, &line_uniforms)?;
+        let measure_shader = scene_program(ORBIT_VERT, ORBIT_FRAG, &line_uniforms)?;
+        let star_shader = scene_program(STAR_VERT, STAR_FRAG, &["u_twinkle"])?;
+        let ring_shader = scene_program(RING_VERT, RING_FRAG, &["u_model", "u_color"])?;
+        let trail_shader = scene_program(TRAIL_VERT, TRAIL_FRAG, &["u_color"])?;
+        let text_uniforms = ["u_viewport", "u_atlas", "u_color", "u_halo"];
+        let label_shader = ShaderProgram::new(&gl, LABEL_VERT, LABEL_FRAG, &text_uniforms)?;
+        let measure_text_shader = ShaderProgram::new(&gl, LABEL_VERT, LABEL_FRAG, &text_uniforms)?;
+        let highlight_shader = scene_program(
+            HIGHLIGHT_VERT,
+            HIGHLIGHT_FRAG,
+            &["u_center", "u_radius", "u_extent", "u_color"],
         )?;
+        let tooltip_shader = ShaderProgram::new(&gl, LABEL_VERT, LABEL_FRAG, &text_uniforms)?;
+        let id_shader = scene_program(ORBIT_VERT, PICK_FRAG, &["u_model", "u_id"])?;
 
         // ── Generate & upload meshes ──
 
-        let sphere = mesh::generate_sphere();
-        let planet_vao = create_mesh_vao(&gl, &sphere)?;
-        let planet_index_count = sphere.indices.len() as i32;
+        // The planet pass adds instance attributes to its VAOs, so it gets its own copies.
+        let sphere_meshes = mesh::generate_sphere_lods(sphere_kind);
+        let upload_lods = || -> Result<Vec<_>, JsValue> {
+            sphere_meshes.iter().map(|m| create_gpu_mesh(&gl, m)).collect()
+        };
+        let planet_lods = upload_lods()?;
+        let sun_lods = upload_lods()?;
+
+        let prominence_vao = gl
+            .create_vertex_array()
+            .ok_or_else(|| JsValue::from_str("Failed to create prominence VAO"))?;
 
-        let ring_mesh = mesh::generate_ring();
-        let ring_vao = create_mesh_vao(&gl, &ring_mesh)?;
-        let ring_index_count = ring_mesh.indices.len() as i32;
+        let ring_mesh = create_gpu_mesh(&gl, &mesh::generate_ring())?;
 
         let (star_vao, star_count) = starfield::create_starfield(&gl)?;
 
@@ -155,9 +240,14 @@ impl Renderer {
         gl.cull_face(GL::BACK);
         gl.enable(GL::BLEND);
         gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
-        gl.clear_color(0.04, 0.04, 0.1, 1.0);
+        let [r, g, b, a] = BACKGROUND;
+        gl.clear_color(r, g, b, a);
 
-        let textures: TextureMap = Rc::new(RefCell::new(HashMap::new()));
+        let textures: TextureMap = Rc::new(RefCell::new(TextureArray::new(&gl, bodies)?));
+        let frame_uniforms = FrameUniforms::new(&gl)?;
+        let picker = Picker::new(&gl)
+            .inspect_err(|e| log::warn!("GPU picking unavailable: {e:?}"))
+            .ok();
 
         // ── Assemble render passes (order matters!) ──
 
@@ -175,17 +265,28 @@ impl Renderer {
                 shader: trail_shader,
                 trails: trail_buffers,
             }),
-            Box::new(PlanetPass {
-                shader: planet_shader,
-                vao: planet_vao,
-                index_count: planet_index_count,
+            Box::new(PlanetPass::new(
+                &gl,
+                planet_shader,
+                planet_lods,
+                Rc::clone(&textures),
+            )?),
+            Box::new(SunPass {
+                shader: sun_shader,
+                lods: sun_lods,
                 textures: Rc::clone(&textures),
+                prominence_shader,
+                prominence_vao,
+                prominences: crate::constants::SUN_PROMINENCES,
             }),
             Box::new(RingPass {
                 shader: ring_shader,
-                vao: ring_vao,
-                index_count: ring_index_count,
+                mesh: ring_mesh,
             }),
+            Box::new(MeasurePass::new(&gl, measure_shader, measure_text_shader)?),
+            Box::new(LabelPass::new(&gl, label_shader)?),
+            // Last, so the tooltip sits on top of the labels.
+            Box::new(HighlightPass::new(&gl, highlight_shader, tooltip_shader)?),
         ];
 
         Ok(Self {
@@ -193,18 +294,34 @@ impl Renderer {
             camera,
             passes,
             textures,
+            frame_uniforms,
+            id_shader,
+            picker,
+            stats: Cell::new(FrameStats::default()),
             render_time: 0.0,
+            viewport_width: canvas_width.max(1),
+            viewport_height: canvas_height.max(1),
+            pixel_ratio,
+            label_mode: LabelMode::default(),
+            layers: Layers::default(),
+            measurement: Measurement::default(),
+            hovered: None,
+            preferences: Preferences::default(),
         })
     }
 
     // ── Public API ──
 
     /// Render one complete frame by iterating over all registered passes.
-    pub fn render(&mut self, bodies: &[CelestialBody], dt: f32) {
+    ///
+    /// `sim_days` is the current simulation time, used by passes whose
+    /// animation follows the simulated clock (e.g. solar rotation).
+    pub fn render(&mut self, bodies: &[CelestialBody], dt: f32, sim_days: f64, i18n: &I18n) {
         self.render_time += dt;
         let gl = &self.gl;
 
         gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+        self.stats.set(FrameStats::default());
 
         let ctx = FrameContext {
             gl,
@@ -212,20 +329,125 @@ impl Renderer {
             projection: self.camera.projection_matrix(),
             eye_position: self.camera.eye_position(),
             time: self.render_time,
+            sim_days,
+            viewport_width: self.viewport_width as f32,
+            viewport_height: self.viewport_height as f32,
+            pixel_ratio: self.pixel_ratio,
+            label_mode: self.label_mode,
+            layers: self.layers,
+            preferences: self.preferences,
+            measurement: &self.measurement,
+            hovered: self.hovered,
+            i18n,
+            stats: &self.stats,
         };
 
+        upload_frame_uniforms(&self.frame_uniforms, &ctx, bodies);
         for pass in &mut self.passes {
             pass.draw(&ctx, bodies);
         }
     }
 
-    /// Handle canvas resize.
-    pub fn resize(&mut self, width: u32, height: u32) {
+    /// What is drawn under the pointer at `pointer` (CSS pixels from the
+    /// canvas's top-left, on a canvas `canvas` CSS pixels in size), within
+    /// [`PICK_RADIUS_PX`] of it, as of the last rendered frame. `None` if
+    /// nothing is, or GPU picking is unavailable.
+    pub fn pick(
+        &mut self,
+        bodies: &[CelestialBody],
+        i18n: &I18n,
+        pointer: glam::Vec2,
+        canvas: glam::Vec2,
+    ) -> Option<PickTarget> {
+        let picker = self.picker.as_mut()?;
+        let gl = &self.gl;
+        let size = (self.viewport_width as i32, self.viewport_height as i32);
+        let radius = (PICK_RADIUS_PX * self.pixel_ratio).round() as i32;
+        let window = PickWindow::around(pointer, canvas, size, radius)?;
+        if !picker.begin(gl, size) {
+            return None;
+        }
+
+        let ctx = FrameContext {
+            gl,
+            view: self.camera.view_matrix(),
+            projection: self.camera.projection_matrix(),
+            eye_position: self.camera.eye_position(),
+            time: self.render_time,
+            // Only the Sun's surface animation depends on it.
+            sim_days: 0.0,
+            viewport_width: self.viewport_width as f32,
+            viewport_height: self.viewport_height as f32,
+            pixel_ratio: self.pixel_ratio,
+            label_mode: self.label_mode,
+            layers: self.layers,
+            preferences: self.preferences,
+            measurement: &self.measurement,
+            hovered: self.hovered,
+            i18n,
+            stats: &self.stats,
+        };
+
+        // The camera may have moved since the last frame.
+        upload_frame_uniforms(&self.frame_uniforms, &ctx, bodies);
+
+        // IDs must reach the buffer unblended; only the window is read back.
+        ctx.set_enabled(GL::BLEND, false);
+        ctx.set_enabled(GL::SCISSOR_TEST, true);
+        gl.scissor(window.x, window.y, window.width, window.height);
+        let ids = &self.id_shader;
+        ids.activate(&ctx);
+        for pass in &self.passes {
+            pass.draw_ids(&ctx, bodies, ids);
+        }
+        ctx.set_enabled(GL::SCISSOR_TEST, false);
+        ctx.set_enabled(GL::BLEND, true);
+        let [r, g, b, a] = BACKGROUND;
+        gl.clear_color(r, g, b, a);
+
+        picker.finish(gl, &window)
+    }
+
+    /// Adapt to the user's accessibility settings: reduced motion stills the
+    /// starfield and makes the camera jump, high contrast switches the orbit
+    /// and label palette.
+    pub fn set_preferences(&mut self, preferences: Preferences) {
+        self.preferences = preferences;
+        self.camera.reduced_motion = preferences.reduced_motion;
+    }
+
+    /// Draw calls and state changes issued by the last rendered frame.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.stats.get()
+    }
+
+    /// Handle canvas resize (`width`/`height` in device pixels).
+    pub fn resize(&mut self, width: u32, height: u32, pixel_ratio: f32) {
         let safe_height = height.max(1);
+        self.viewport_width = width.max(1);
+        self.viewport_height = safe_height;
+        self.pixel_ratio = pixel_ratio;
         self.gl.viewport(0, 0, width as i32, safe_height as i32);
         self.camera.set_aspect(width as f32 / safe_height as f32);
     }
 
+    /// Delete every GL object the renderer created. Frames rendered
+    /// afterwards only clear the canvas.
+    pub fn destroy(&mut self) {
+        let gl = &self.gl;
+        gl.use_program(None);
+        gl.bind_vertex_array(None);
+        for mut pass in self.passes.drain(..) {
+            pass.destroy(gl);
+        }
+        self.textures.borrow_mut().delete(gl);
+        self.frame_uniforms.delete(gl);
+        self.id_shader.delete(gl);
+        if let Some(picker) = self.picker.take() {
+            picker.delete(gl);
+        }
+    }
+
     /// Clone of the GL context for external use (e.g. texture loading).
     pub fn gl_handle(&self) -> GL {
         self.gl.clone()
@@ -236,3 +458,22 @@ impl Renderer {
         Rc::clone(&self.textures)
     }
 }
+
+/// Fill the `Frame` block for `ctx`. The light sits at the Sun's centre;
+/// the eye is the camera-relative origin.
+fn upload_frame_uniforms(uniforms: &FrameUniforms, ctx: &FrameContext, bodies: &[CelestialBody]) {
+    let light_pos = bodies
+        .iter()
+        .find(|b| b.is_star)
+        .map_or(glam::DVec3::ZERO, |b| b.position);
+    uniforms.update(
+        ctx,
+        &frame_uniforms::pack(
+            &ctx.view,
+            &ctx.projection,
+            ctx.relative(light_pos),
+            glam::Vec3::ZERO,
+            ctx.time,
+        ),
+    );
+}
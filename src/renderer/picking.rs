@@ -0,0 +1,233 @@
+//! GPU colour-ID picking.
+//!
+//! On a click every pickable object — bodies, Saturn's ring, orbit paths and
+//! trails — is drawn into an off-screen framebuffer with a flat colour that
+//! encodes what it is ([`PickTarget::encode`]). Only a small window around
+//! the pointer is rasterised (scissored) and read back, and the object
+//! nearest the pointer within [`PICK_RADIUS_PX`] wins, so single-pixel orbit
+//! lines and distant dots can be clicked as easily as a planet filling the
+//! screen. Bodies beat lines when both are in reach.
+//!
+//! The encoding and the window search are pure and tested natively; the
+//! [`Picker`] owns the framebuffer.
+//!
+//! [`PICK_RADIUS_PX`]: crate::constants::PICK_RADIUS_PX
+
+use glam::Vec2;
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext as GL;
+
+/// What a pick hit. Each variant holds the index of the body it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickTarget {
+    /// A body's sphere (or its rings).
+    Body(usize),
+    /// A planet's orbit path.
+    Orbit(usize),
+    /// A planet's trail.
+    Trail(usize),
+}
+
+impl PickTarget {
+    /// Index of the body this target belongs to.
+    pub fn body(self) -> usize {
+        match self {
+            PickTarget::Body(i) | PickTarget::Orbit(i) | PickTarget::Trail(i) => i,
+        }
+    }
+
+    /// Kind tag stored in the red channel; 0 means nothing was drawn.
+    fn kind(self) -> u8 {
+        match self {
+            PickTarget::Body(_) => 1,
+            PickTarget::Orbit(_) => 2,
+            PickTarget::Trail(_) => 3,
+        }
+    }
+
+    /// RGBA8 colour identifying this target: kind in red, body index in
+    /// green (high byte) and blue (low byte).
+    pub fn encode(self) -> [u8; 4] {
+        let index = u16::try_from(self.body()).unwrap_or(u16::MAX);
+        let [high, low] = index.to_be_bytes();
+        [self.kind(), high, low, u8::MAX]
+    }
+
+    /// Inverse of [`PickTarget::encode`]; `None` for the cleared background.
+    pub fn decode(rgba: [u8; 4]) -> Option<Self> {
+        let index = u16::from_be_bytes([rgba[1], rgba[2]]) as usize;
+        match rgba[0] {
+            1 => Some(PickTarget::Body(index)),
+            2 => Some(PickTarget::Orbit(index)),
+            3 => Some(PickTarget::Trail(index)),
+            _ => None,
+        }
+    }
+
+    /// [`PickTarget::encode`] as normalised floats for the ID shader.
+    pub fn color(self) -> [f32; 4] {
+        self.encode().map(|c| c as f32 / 255.0)
+    }
+}
+
+/// The framebuffer rectangle read back around the pointer, in GL pixel
+/// coordinates (origin bottom-left).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickWindow {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    /// The pointer's pixel, relative to the window's origin.
+    pub center: (i32, i32),
+    /// Search radius in device pixels.
+    pub radius: i32,
+}
+
+impl PickWindow {
+    /// Window of `radius` device pixels around the pointer at `pointer`
+    /// (CSS pixels from the canvas's top-left) on a canvas of `canvas` CSS
+    /// pixels drawn into a `framebuffer`-sized buffer. `None` when the
+    /// pointer is outside the canvas.
+    pub fn around(
+        pointer: Vec2,
+        canvas: Vec2,
+        framebuffer: (i32, i32),
+        radius: i32,
+    ) -> Option<Self> {
+        let (fb_width, fb_height) = framebuffer;
+        if canvas.x <= 0.0 || canvas.y <= 0.0 || fb_width <= 0 || fb_height <= 0 {
+            return None;
+        }
+        let px = (pointer.x * fb_width as f32 / canvas.x).floor() as i32;
+        let py = fb_height - 1 - (pointer.y * fb_height as f32 / canvas.y).floor() as i32;
+        if !(0..fb_width).contains(&px) || !(0..fb_height).contains(&py) {
+            return None;
+        }
+        let radius = radius.max(0);
+        let (x0, y0) = ((px - radius).max(0), (py - radius).max(0));
+        let (x1, y1) = (
+            (px + radius).min(fb_width - 1),
+            (py + radius).min(fb_height - 1),
+        );
+        Some(Self {
+            x: x0,
+            y: y0,
+            width: x1 - x0 + 1,
+            height: y1 - y0 + 1,
+            center: (px - x0, py - y0),
+            radius,
+        })
+    }
+
+    /// Bytes of an RGBA8 read-back of this window.
+    pub fn byte_len(&self) -> usize {
+        (self.width * self.height * 4) as usize
+    }
+
+    /// Target nearest the pointer among the window's `pixels` (RGBA8, rows
+    /// bottom to top), within the search radius. Bodies are preferred over
+    /// orbits and trails anywhere in reach.
+    pub fn nearest(&self, pixels: &[u8]) -> Option<PickTarget> {
+        let mut best: Option<(u8, i32, PickTarget)> = None;
+        for (i, rgba) in pixels.chunks_exact(4).enumerate() {
+            let Some(target) = PickTarget::decode([rgba[0], rgba[1], rgba[2], rgba[3]]) else {
+                continue;
+            };
+            let (x, y) = (i as i32 % self.width, i as i32 / self.width);
+            let (dx, dy) = (x - self.center.0, y - self.center.1);
+            let distance_sq = dx * dx + dy * dy;
+            if distance_sq > self.radius * self.radius {
+                continue;
+            }
+            let key = (target.kind(), distance_sq);
+            if best.is_none_or(|(kind, d, _)| key < (kind, d)) {
+                best = Some((key.0, key.1, target));
+            }
+        }
+        best.map(|(_, _, target)| target)
+    }
+}
+
+/// Off-screen ID framebuffer, sized to the drawing buffer on demand.
+pub struct Picker {
+    framebuffer: web_sys::WebGlFramebuffer,
+    color: web_sys::WebGlRenderbuffer,
+    depth: web_sys::WebGlRenderbuffer,
+    /// Size the renderbuffers were last allocated at.
+    size: (i32, i32),
+    /// Read-back staging, reused across picks.
+    pixels: Vec<u8>,
+}
+
+impl Picker {
+    pub fn new(gl: &GL) -> Result<Self, JsValue> {
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or_else(|| JsValue::from_str("Failed to create pick framebuffer"))?;
+        let create_renderbuffer = || {
+            gl.create_renderbuffer()
+                .ok_or_else(|| JsValue::from_str("Failed to create pick renderbuffer"))
+        };
+        Ok(Self {
+            framebuffer,
+            color: create_renderbuffer()?,
+            depth: create_renderbuffer()?,
+            size: (0, 0),
+            pixels: Vec::new(),
+        })
+    }
+
+    /// Bind the framebuffer at `size`, (re)allocating its storage when the
+    /// drawing buffer changed, and clear it. `false` if it is unusable.
+    pub fn begin(&mut self, gl: &GL, size: (i32, i32)) -> bool {
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.framebuffer));
+        if self.size != size {
+            for (buffer, format, attachment) in [
+                (&self.color, GL::RGBA8, GL::COLOR_ATTACHMENT0),
+                (&self.depth, GL::DEPTH_COMPONENT16, GL::DEPTH_ATTACHMENT),
+            ] {
+                gl.bind_renderbuffer(GL::RENDERBUFFER, Some(buffer));
+                gl.renderbuffer_storage(GL::RENDERBUFFER, format, size.0, size.1);
+                gl.framebuffer_renderbuffer(
+                    GL::FRAMEBUFFER,
+                    attachment,
+                    GL::RENDERBUFFER,
+                    Some(buffer),
+                );
+            }
+            gl.bind_renderbuffer(GL::RENDERBUFFER, None);
+            self.size = size;
+        }
+        if gl.check_framebuffer_status(GL::FRAMEBUFFER) != GL::FRAMEBUFFER_COMPLETE {
+            gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+            return false;
+        }
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+        true
+    }
+
+    /// Read `window` back, unbind the framebuffer and return what it hit.
+    pub fn finish(&mut self, gl: &GL, window: &PickWindow) -> Option<PickTarget> {
+        self.pixels.resize(window.byte_len(), 0);
+        let read = gl.read_pixels_with_opt_u8_array(
+            window.x,
+            window.y,
+            window.width,
+            window.height,
+            GL::RGBA,
+            GL::UNSIGNED_BYTE,
+            Some(&mut self.pixels),
+        );
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+        read.ok()?;
+        window.nearest(&self.pixels)
+    }
+
+    pub fn delete(&self, gl: &GL) {
+        gl.delete_framebuffer(Some(&self.framebuffer));
+        gl.delete_renderbuffer(Some(&self.color));
+        gl.delete_renderbuffer(Some(&self.depth));
+    }
+}
@@ -1,25 +1,159 @@
 //! Render pass trait and concrete implementations.
 //!
-//! Each visual layer (planets, orbits, starfield, rings) is a self-contained
+//! Each visual layer (planets, Sun, orbits, starfield, rings) is a self-contained
 //! [`RenderPass`].  To add a new visual layer, create a struct that implements
 //! the trait and register it in `Renderer::new`.
 
-use glam::{Mat4, Vec3};
+use std::cell::Cell;
+
+use glam::{DVec3, Mat4, Vec2, Vec3};
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext as GL;
 
+use super::camera::relative_to_eye;
+use super::font;
+use super::labels::{self, LabelMode, LabelPlacement, Rect};
+use super::lod;
+use super::measure::{self, Distance, MAX_MEASURED, Measurement};
+use super::mesh::{self, GpuMesh};
+use super::picking::PickTarget;
 use super::shader::ShaderProgram;
 use super::texture::TextureMap;
+use crate::constants::{
+    AU_TO_DISPLAY, HIGH_CONTRAST_LABEL_HALO_ALPHA, HIGH_CONTRAST_ORBIT_ALPHA,
+    HIGH_CONTRAST_WHITE_MIX, HOVER_COLOR, HOVER_GLOW_EXTENT, HOVER_RING_MIN_PX, HOVER_RING_SCALE,
+    LABEL_HALO_ALPHA, LABEL_MARGIN_PX, LABEL_OFFSET_PX, LABEL_SIZE_PX,
+    MEASURE_ALPHA, MEASURE_COLOR, ORBIT_ALPHA, SPHERE_LOD_THRESHOLDS_PX,
+    SUN_DIFFERENTIAL_ROTATION, SUN_PROMINENCE_EXTENT,
+};
+use crate::accessibility::Preferences;
+use crate::i18n::I18n;
 use crate::simulation::body::CelestialBody;
 
 // ─── Shared per-frame context ────────────────────────────────────────────
 
+/// Counters of GPU work submitted during one frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameStats {
+    /// `draw_*` calls issued.
+    pub draw_calls: u32,
+    /// Program, VAO, buffer and texture binds plus capability/blend toggles.
+    pub state_changes: u32,
+}
+
+/// Optional scene layers the user can hide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Layers {
+    /// Orbit ellipses.
+    pub orbits: bool,
+    /// Fading trails behind each planet.
+    pub trails: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            orbits: true,
+            trails: true,
+        }
+    }
+}
+
 /// Read-only snapshot of everything a render pass needs for one frame.
+///
+/// Passes draw in camera-relative space: `view` only rotates, and world
+/// positions go through [`FrameContext::relative`] before reaching the GPU.
 pub struct FrameContext<'a> {
     pub gl: &'a GL,
     pub view: Mat4,
     pub projection: Mat4,
-    pub eye_position: Vec3,
+    /// Camera position in world space.
+    pub eye_position: DVec3,
     pub time: f32,
+    /// Simulation time in Earth days (drives the Sun's rotation).
+    pub sim_days: f64,
+    /// Drawing-buffer width in device pixels.
+    pub viewport_width: f32,
+    /// Drawing-buffer height in device pixels (for screen-space LOD decisions).
+    pub viewport_height: f32,
+    /// Device pixels per CSS pixel (`window.devicePixelRatio`).
+    pub pixel_ratio: f32,
+    /// What the label pass should draw.
+    pub label_mode: LabelMode,
+    /// Which optional layers are visible.
+    pub layers: Layers,
+    /// Reduced motion and high contrast.
+    pub preferences: Preferences,
+    /// Bodies linked by the measuring tool.
+    pub measurement: &'a Measurement,
+    /// Body under the pointer, if any.
+    pub hovered: Option<usize>,
+    /// Body names and number formatting for labels.
+    pub i18n: &'a I18n,
+    /// Work counters, accumulated by every pass during the frame.
+    pub stats: &'a Cell<FrameStats>,
+}
+
+impl FrameContext<'_> {
+    /// World position → camera-relative `f32` position.
+    pub fn relative(&self, world: DVec3) -> Vec3 {
+        relative_to_eye(world, self.eye_position)
+    }
+
+    /// Record `n` draw calls.
+    pub fn count_draws(&self, n: u32) {
+        let mut stats = self.stats.get();
+        stats.draw_calls += n;
+        self.stats.set(stats);
+    }
+
+    /// Record one GL state change. Passes change state through the
+    /// helpers below and [`ShaderProgram::activate`], which call this.
+    pub(super) fn count_state_change(&self) {
+        let mut stats = self.stats.get();
+        stats.state_changes += 1;
+        self.stats.set(stats);
+    }
+
+    // ── Counted state changes ──
+
+    pub fn bind_vertex_array(&self, vao: Option<&web_sys::WebGlVertexArrayObject>) {
+        self.gl.bind_vertex_array(vao);
+        self.count_state_change();
+    }
+
+    pub fn bind_buffer(&self, target: u32, buffer: Option<&web_sys::WebGlBuffer>) {
+        self.gl.bind_buffer(target, buffer);
+        self.count_state_change();
+    }
+
+    /// Bind `texture` to `target` on texture unit 0.
+    pub fn bind_texture(&self, target: u32, texture: Option<&web_sys::WebGlTexture>) {
+        self.gl.active_texture(GL::TEXTURE0);
+        self.gl.bind_texture(target, texture);
+        self.count_state_change();
+    }
+
+    /// Enable or disable a capability (`DEPTH_TEST`, `CULL_FACE`, …).
+    pub fn set_enabled(&self, capability: u32, enabled: bool) {
+        if enabled {
+            self.gl.enable(capability);
+        } else {
+            self.gl.disable(capability);
+        }
+        self.count_state_change();
+    }
+
+    pub fn depth_mask(&self, write: bool) {
+        self.gl.depth_mask(write);
+        self.count_state_change();
+    }
+
+    pub fn blend_func(&self, src: u32, dst: u32) {
+        self.gl.blend_func(src, dst);
+        self.count_state_change();
+    }
 }
 
 /// A self-contained render pass.
@@ -28,60 +162,290 @@ pub struct FrameContext<'a> {
 /// labels, trails) without touching existing rendering code.
 pub trait RenderPass {
     fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]);
+
+    /// Draw whatever this pass shows that can be clicked into the picking
+    /// ID buffer (see [`super::picking`]), setting `u_model` and `u_id` on
+    /// the `ids` program for each object. The renderer has already activated
+    /// `ids` with the frame's view and projection. Passes with nothing to
+    /// pick keep this default.
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        let _ = (ctx, bodies, ids);
+    }
+
+    /// Delete the GL objects this pass created. Shared objects (the
+    /// texture array, the frame uniforms) belong to the renderer.
+    fn destroy(&mut self, gl: &GL);
+}
+
+/// Pick the sphere level of detail (0 = finest) for `body` from its
+/// projected size this frame, clamped to the `lod_count` meshes available.
+fn sphere_lod(ctx: &FrameContext, body: &CelestialBody, lod_count: usize) -> usize {
+    let distance = ctx.eye_position.distance(body.position) as f32;
+    let radius_px = lod::projected_radius_px(
+        body.display_radius,
+        distance,
+        &ctx.projection,
+        ctx.viewport_height,
+    );
+    lod::select_lod(radius_px, &SPHERE_LOD_THRESHOLDS_PX).min(lod_count - 1)
+}
+
+/// Draw the sphere of every body matching `filter` into the ID buffer,
+/// using the same LOD meshes as the visible pass so silhouettes agree.
+fn draw_sphere_ids(
+    ctx: &FrameContext,
+    bodies: &[CelestialBody],
+    ids: &ShaderProgram,
+    lods: &[GpuMesh],
+    filter: impl Fn(&CelestialBody) -> bool,
+) {
+    let gl = ctx.gl;
+    for (i, body) in bodies.iter().enumerate().filter(|(_, b)| filter(b)) {
+        let model = Mat4::from_translation(ctx.relative(body.position))
+            * Mat4::from_scale(Vec3::splat(body.display_radius));
+        ids.set_mat4(gl, "u_model", &model);
+        ids.set_vec4(gl, "u_id", &PickTarget::Body(i).color());
+        let mesh = &lods[sphere_lod(ctx, body, lods.len())];
+        ctx.bind_vertex_array(Some(&mesh.vao));
+        mesh.draw(gl);
+        ctx.count_draws(1);
+    }
+    ctx.bind_vertex_array(None);
 }
 
 // ─── Planet pass ─────────────────────────────────────────────────────────
 
+/// Floats per planet instance: model matrix, colour + flags, texture layer.
+pub const PLANET_INSTANCE_FLOATS: usize = 16 + 4 + 1;
+
+/// Instance flag: sample the body's texture-array layer instead of its colour.
+pub const INSTANCE_FLAG_TEXTURED: u32 = 1;
+
+/// First attribute location of the per-instance model matrix (uses 4 slots).
+const ATTRIB_INSTANCE_MODEL: u32 = 3;
+/// Attribute location of the per-instance colour + flags.
+const ATTRIB_INSTANCE_COLOR_FLAGS: u32 = 7;
+/// Attribute location of the per-instance texture layer.
+const ATTRIB_INSTANCE_LAYER: u32 = 8;
+
+/// Append one planet instance to `out` in the layout the planet shader expects.
+pub fn push_planet_instance(out: &mut Vec<f32>, model: &Mat4, color: [f32; 3], flags: u32, layer: i32) {
+    out.extend_from_slice(&model.to_cols_array());
+    out.extend_from_slice(&color);
+    out.push(flags as f32);
+    out.push(layer as f32);
+}
+
+/// Point the per-instance attributes of the bound VAO at the bound
+/// `ARRAY_BUFFER`, starting from instance `first`.
+fn set_instance_attributes(gl: &GL, first: usize) {
+    let stride = (PLANET_INSTANCE_FLOATS * 4) as i32;
+    let base = (first * PLANET_INSTANCE_FLOATS * 4) as i32;
+
+    for column in 0..4 {
+        let loc = ATTRIB_INSTANCE_MODEL + column;
+        gl.vertex_attrib_pointer_with_i32(loc, 4, GL::FLOAT, false, stride, base + column as i32 * 16);
+        gl.enable_vertex_attrib_array(loc);
+        gl.vertex_attrib_divisor(loc, 1);
+    }
+
+    gl.vertex_attrib_pointer_with_i32(ATTRIB_INSTANCE_COLOR_FLAGS, 4, GL::FLOAT, false, stride, base + 64);
+    gl.enable_vertex_attrib_array(ATTRIB_INSTANCE_COLOR_FLAGS);
+    gl.vertex_attrib_divisor(ATTRIB_INSTANCE_COLOR_FLAGS, 1);
+
+    gl.vertex_attrib_pointer_with_i32(ATTRIB_INSTANCE_LAYER, 1, GL::FLOAT, false, stride, base + 80);
+    gl.enable_vertex_attrib_array(ATTRIB_INSTANCE_LAYER);
+    gl.vertex_attrib_divisor(ATTRIB_INSTANCE_LAYER, 1);
+}
+
+/// Instanced planet renderer: one draw call per sphere LOD in use.
+///
+/// Frame constants come from the `Frame` uniform block; everything that
+/// varies per body is streamed as instance attributes, and textures are
+/// layers of a single `TEXTURE_2D_ARRAY`.
 pub struct PlanetPass {
-    pub shader: ShaderProgram,
-    pub vao: web_sys::WebGlVertexArrayObject,
-    pub index_count: i32,
-    pub textures: TextureMap,
+    shader: ShaderProgram,
+    /// Sphere meshes, finest first (see [`lod::select_lod`]). Dedicated to
+    /// this pass, since their VAOs also carry the instance attributes.
+    lods: Vec<GpuMesh>,
+    textures: TextureMap,
+    instance_buffer: web_sys::WebGlBuffer,
+    /// CPU staging for instance data, reused across frames.
+    instances: Vec<f32>,
+    /// `(lod, body index)` pairs sorted by LOD, reused across frames.
+    order: Vec<(usize, usize)>,
+}
+
+impl PlanetPass {
+    pub fn new(
+        gl: &GL,
+        shader: ShaderProgram,
+        lods: Vec<GpuMesh>,
+        textures: TextureMap,
+    ) -> Result<Self, JsValue> {
+        let instance_buffer = gl
+            .create_buffer()
+            .ok_or_else(|| JsValue::from_str("Failed to create planet instance buffer"))?;
+        Ok(Self {
+            shader,
+            lods,
+            textures,
+            instance_buffer,
+            instances: Vec::new(),
+            order: Vec::new(),
+        })
+    }
 }
 
 impl RenderPass for PlanetPass {
     fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
         let gl = ctx.gl;
-        let s = &self.shader;
-        s.activate(gl);
 
-        let textures = self.textures.borrow();
-        gl.bind_vertex_array(Some(&self.vao));
-
-        // Frame-constant uniforms — set once outside the loop
-        s.set_mat4(gl, "u_view", &ctx.view);
-        s.set_mat4(gl, "u_projection", &ctx.projection);
-        s.set_vec3(gl, "u_light_pos", &[0.0, 0.0, 0.0]);
-        s.set_vec3(
-            gl,
-            "u_view_pos",
-            &[ctx.eye_position.x, ctx.eye_position.y, ctx.eye_position.z],
-        );
+        // Bucket bodies by LOD so each mesh is drawn once. Stars are drawn by `SunPass`.
+        self.order.clear();
+        for (i, body) in bodies.iter().enumerate().filter(|(_, b)| !b.is_star) {
+            self.order.push((sphere_lod(ctx, body, self.lods.len()), i));
+        }
+        if self.order.is_empty() {
+            return;
+        }
+        self.order.sort_unstable();
 
-        for body in bodies {
-            let model = Mat4::from_translation(body.position)
+        let textures = self.textures.borrow();
+        self.instances.clear();
+        for &(_, i) in &self.order {
+            let body = &bodies[i];
+            let model = Mat4::from_translation(ctx.relative(body.position))
                 * Mat4::from_scale(Vec3::splat(body.display_radius));
-            let normal_matrix = model.inverse().transpose();
+            let layer = textures.layer(body.id);
+            let flags = if layer.is_some() { INSTANCE_FLAG_TEXTURED } else { 0 };
+            push_planet_instance(&mut self.instances, &model, body.color, flags, layer.unwrap_or(0));
+        }
+
+        self.shader.activate(ctx);
+        ctx.bind_buffer(GL::ARRAY_BUFFER, Some(&self.instance_buffer));
+        unsafe {
+            let array = js_sys::Float32Array::view(&self.instances);
+            gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &array, GL::DYNAMIC_DRAW);
+        }
+        ctx.bind_texture(GL::TEXTURE_2D_ARRAY, Some(&textures.texture));
+        self.shader.set_int(gl, "u_textures", 0);
+
+        let mut first = 0;
+        for run in self.order.chunk_by(|a, b| a.0 == b.0) {
+            let mesh = &self.lods[run[0].0];
+            ctx.bind_vertex_array(Some(&mesh.vao));
+            set_instance_attributes(gl, first);
+            mesh.draw_instanced(gl, run.len() as i32);
+            ctx.count_draws(1);
+            first += run.len();
+        }
+
+        ctx.bind_vertex_array(None);
+        ctx.bind_buffer(GL::ARRAY_BUFFER, None);
+        ctx.bind_texture(GL::TEXTURE_2D_ARRAY, None);
+    }
+
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        // The instance attributes on these VAOs are ignored by the ID program.
+        draw_sphere_ids(ctx, bodies, ids, &self.lods, |b| !b.is_star);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        for mesh in &self.lods {
+            mesh.delete(gl);
+        }
+        gl.delete_buffer(Some(&self.instance_buffer));
+    }
+}
 
+// ─── Sun pass ────────────────────────────────────────────────────────────
+
+/// Procedural star surface (granulation, sunspots, limb darkening) plus an
+/// optional additive billboard for prominences and corona glow.
+pub struct SunPass {
+    pub shader: ShaderProgram,
+    /// Sphere meshes, finest first.
+    pub lods: Vec<GpuMesh>,
+    pub textures: TextureMap,
+    pub prominence_shader: ShaderProgram,
+    /// Empty VAO — the prominence quad is expanded from `gl_VertexID`.
+    pub prominence_vao: web_sys::WebGlVertexArrayObject,
+    pub prominences: bool,
+}
+
+impl RenderPass for SunPass {
+    fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        let gl = ctx.gl;
+        let textures = self.textures.borrow();
+
+        // ── Photosphere ──
+        let s = &self.shader;
+        s.activate(ctx);
+        s.set_float(gl, "u_rotation_days", ctx.sim_days as f32);
+        s.set_vec3(gl, "u_diff_rotation", &SUN_DIFFERENTIAL_ROTATION);
+
+        for body in bodies.iter().filter(|b| b.is_star) {
+            let model = Mat4::from_translation(ctx.relative(body.position))
+                * Mat4::from_scale(Vec3::splat(body.display_radius));
             s.set_mat4(gl, "u_model", &model);
-            s.set_mat4(gl, "u_normal_matrix", &normal_matrix);
             s.set_vec3(gl, "u_color", &body.color);
-            s.set_bool(gl, "u_is_star", body.is_star);
-
-            // Texture binding
-            let has_texture = textures.contains_key(body.name);
-            s.set_bool(gl, "u_has_texture", has_texture);
-            if has_texture {
-                gl.active_texture(GL::TEXTURE0);
-                gl.bind_texture(GL::TEXTURE_2D, textures.get(body.name));
-                s.set_int(gl, "u_texture", 0);
+
+            let layer = textures.layer(body.id);
+            s.set_bool(gl, "u_has_texture", layer.is_some());
+            if let Some(layer) = layer {
+                ctx.bind_texture(GL::TEXTURE_2D_ARRAY, Some(&textures.texture));
+                s.set_int(gl, "u_textures", 0);
+                s.set_float(gl, "u_layer", layer as f32);
             }
 
-            gl.draw_elements_with_i32(GL::TRIANGLES, self.index_count, GL::UNSIGNED_SHORT, 0);
+            let mesh = &self.lods[sphere_lod(ctx, body, self.lods.len())];
+            ctx.bind_vertex_array(Some(&mesh.vao));
+            mesh.draw(gl);
+            ctx.count_draws(1);
         }
 
-        gl.bind_texture(GL::TEXTURE_2D, None);
-        gl.bind_vertex_array(None);
+        ctx.bind_texture(GL::TEXTURE_2D_ARRAY, None);
+
+        if !self.prominences {
+            ctx.bind_vertex_array(None);
+            return;
+        }
+
+        // ── Prominences & corona (additive, no depth writes) ──
+        let p = &self.prominence_shader;
+        p.activate(ctx);
+        ctx.bind_vertex_array(Some(&self.prominence_vao));
+        ctx.depth_mask(false);
+        ctx.blend_func(GL::SRC_ALPHA, GL::ONE);
+
+        p.set_float(gl, "u_extent", SUN_PROMINENCE_EXTENT);
+
+        for body in bodies.iter().filter(|b| b.is_star) {
+            p.set_vec3(gl, "u_center", &ctx.relative(body.position).to_array());
+            p.set_float(gl, "u_radius", body.display_radius);
+            p.set_vec3(gl, "u_color", &body.color);
+            gl.draw_arrays(GL::TRIANGLE_STRIP, 0, 4);
+            ctx.count_draws(1);
+        }
+
+        ctx.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
+        ctx.depth_mask(true);
+        ctx.bind_vertex_array(None);
+    }
+
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        draw_sphere_ids(ctx, bodies, ids, &self.lods, |b| b.is_star);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        self.prominence_shader.delete(gl);
+        for mesh in &self.lods {
+            mesh.delete(gl);
+        }
+        gl.delete_vertex_array(Some(&self.prominence_vao));
     }
 }
 
@@ -89,42 +453,75 @@ impl RenderPass for PlanetPass {
 
 pub struct RingPass {
     pub shader: ShaderProgram,
-    pub vao: web_sys::WebGlVertexArrayObject,
-    pub index_count: i32,
+    pub mesh: GpuMesh,
 }
 
 impl RenderPass for RingPass {
     fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
         let gl = ctx.gl;
         let s = &self.shader;
-        s.activate(gl);
+        s.activate(ctx);
 
-        gl.bind_vertex_array(Some(&self.vao));
-
-        // Frame-constant uniforms
-        s.set_mat4(gl, "u_view", &ctx.view);
-        s.set_mat4(gl, "u_projection", &ctx.projection);
+        ctx.bind_vertex_array(Some(&self.mesh.vao));
 
         // Disable culling for rings (double-sided)
-        gl.disable(GL::CULL_FACE);
+        ctx.set_enabled(GL::CULL_FACE, false);
 
         for body in bodies.iter().filter(|b| b.has_rings) {
-            let model = Mat4::from_translation(body.position)
+            let model = Mat4::from_translation(ctx.relative(body.position))
                 * Mat4::from_scale(Vec3::splat(body.display_radius));
 
             s.set_mat4(gl, "u_model", &model);
             s.set_vec3(gl, "u_color", &body.color);
 
-            gl.draw_elements_with_i32(GL::TRIANGLES, self.index_count, GL::UNSIGNED_SHORT, 0);
+            self.mesh.draw(gl);
+            ctx.count_draws(1);
         }
 
-        gl.enable(GL::CULL_FACE);
-        gl.bind_vertex_array(None);
+        ctx.set_enabled(GL::CULL_FACE, true);
+        ctx.bind_vertex_array(None);
+    }
+
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        let gl = ctx.gl;
+        ctx.bind_vertex_array(Some(&self.mesh.vao));
+        ctx.set_enabled(GL::CULL_FACE, false);
+        for (i, body) in bodies.iter().enumerate().filter(|(_, b)| b.has_rings) {
+            let model = Mat4::from_translation(ctx.relative(body.position))
+                * Mat4::from_scale(Vec3::splat(body.display_radius));
+            ids.set_mat4(gl, "u_model", &model);
+            // Clicking the rings picks their planet.
+            ids.set_vec4(gl, "u_id", &PickTarget::Body(i).color());
+            self.mesh.draw(gl);
+            ctx.count_draws(1);
+        }
+        ctx.set_enabled(GL::CULL_FACE, true);
+        ctx.bind_vertex_array(None);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        self.mesh.delete(gl);
     }
 }
 
 // ─── Orbit pass ──────────────────────────────────────────────────────────
 
+/// Colour and opacity of a body's orbit line. The high-contrast palette
+/// brings the body colour to full brightness, lifts it towards white and
+/// draws it nearly opaque.
+pub fn orbit_color(color: [f32; 3], high_contrast: bool) -> ([f32; 3], f32) {
+    if !high_contrast {
+        return (color, ORBIT_ALPHA);
+    }
+    let peak = color.into_iter().fold(f32::EPSILON, f32::max);
+    let lifted = color.map(|c| {
+        let full = (c / peak).min(1.0);
+        full + (1.0 - full) * HIGH_CONTRAST_WHITE_MIX
+    });
+    (lifted, HIGH_CONTRAST_ORBIT_ALPHA)
+}
+
 pub struct OrbitPass {
     pub shader: ShaderProgram,
     pub vaos: Vec<(web_sys::WebGlVertexArrayObject, i32)>,
@@ -132,12 +529,12 @@ pub struct OrbitPass {
 
 impl RenderPass for OrbitPass {
     fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        if !ctx.layers.orbits {
+            return;
+        }
         let gl = ctx.gl;
         let s = &self.shader;
-        s.activate(gl);
-
-        s.set_mat4(gl, "u_view", &ctx.view);
-        s.set_mat4(gl, "u_projection", &ctx.projection);
+        s.activate(ctx);
 
         // Orbits are centred on the Sun — translate them by its current position
         // so they follow the galactic drift.
@@ -145,19 +542,47 @@ impl RenderPass for OrbitPass {
             .iter()
             .find(|b| b.is_star)
             .map(|b| b.position)
-            .unwrap_or(Vec3::ZERO);
-        let model = Mat4::from_translation(sun_pos);
+            .unwrap_or(DVec3::ZERO);
+        let model = Mat4::from_translation(ctx.relative(sun_pos));
         s.set_mat4(gl, "u_model", &model);
 
         let planets: Vec<&CelestialBody> = bodies.iter().filter(|b| !b.is_star).collect();
         for (i, planet) in planets.iter().enumerate() {
             if let Some((vao, count)) = self.vaos.get(i) {
-                s.set_vec3(gl, "u_color", &planet.color);
-                gl.bind_vertex_array(Some(vao));
+                let (color, alpha) = orbit_color(planet.color, ctx.preferences.high_contrast);
+                s.set_vec3(gl, "u_color", &color);
+                s.set_float(gl, "u_alpha", alpha);
+                ctx.bind_vertex_array(Some(vao));
                 gl.draw_arrays(GL::LINE_STRIP, 0, *count);
-                gl.bind_vertex_array(None);
+                ctx.count_draws(1);
             }
         }
+        ctx.bind_vertex_array(None);
+    }
+
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        if !ctx.layers.orbits {
+            return;
+        }
+        let gl = ctx.gl;
+        let sun_pos = bodies.iter().find(|b| b.is_star).map_or(DVec3::ZERO, |b| b.position);
+        ids.set_mat4(gl, "u_model", &Mat4::from_translation(ctx.relative(sun_pos)));
+
+        let planets = bodies.iter().enumerate().filter(|(_, b)| !b.is_star).map(|(i, _)| i);
+        for (body, (vao, count)) in planets.zip(&self.vaos) {
+            ids.set_vec4(gl, "u_id", &PickTarget::Orbit(body).color());
+            ctx.bind_vertex_array(Some(vao));
+            gl.draw_arrays(GL::LINE_STRIP, 0, *count);
+            ctx.count_draws(1);
+        }
+        ctx.bind_vertex_array(None);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        for (vao, _) in &self.vaos {
+            mesh::delete_vao(gl, vao);
+        }
     }
 }
 
@@ -174,26 +599,26 @@ impl RenderPass for StarfieldPass {
         let _ = bodies; // starfield is independent of bodies
         let gl = ctx.gl;
         let s = &self.shader;
-        s.activate(gl);
+        s.activate(ctx);
 
         // Don't write to depth buffer — stars are a backdrop
-        gl.depth_mask(false);
+        ctx.depth_mask(false);
 
-        // Skybox-style: strip translation from the view matrix
-        let mut sky_view = ctx.view;
-        sky_view.w_axis.x = 0.0;
-        sky_view.w_axis.y = 0.0;
-        sky_view.w_axis.z = 0.0;
+        // Skybox-style: the camera-relative view matrix is already rotation-only
+        let twinkle = if ctx.preferences.reduced_motion { 0.0 } else { 1.0 };
+        s.set_float(gl, "u_twinkle", twinkle);
 
-        s.set_mat4(gl, "u_view", &sky_view);
-        s.set_mat4(gl, "u_projection", &ctx.projection);
-        s.set_float(gl, "u_time", ctx.time);
-
-        gl.bind_vertex_array(Some(&self.vao));
+        ctx.bind_vertex_array(Some(&self.vao));
         gl.draw_arrays(GL::POINTS, 0, self.count);
-        gl.bind_vertex_array(None);
+        ctx.bind_vertex_array(None);
 
-        gl.depth_mask(true);
+        ctx.depth_mask(true);
+        ctx.count_draws(1);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        mesh::delete_vao(gl, &self.vao);
     }
 }
 
@@ -204,7 +629,8 @@ use crate::constants::TRAIL_MAX_POINTS;
 
 /// Per-planet trail data: a ring buffer of past positions + a GPU buffer.
 pub struct TrailBuffer {
-    pub positions: VecDeque<Vec3>,
+    /// Past world positions, re-expressed relative to the eye on every upload.
+    pub positions: VecDeque<DVec3>,
     pub vao: web_sys::WebGlVertexArrayObject,
     pub vbo_pos: web_sys::WebGlBuffer,
     pub vbo_alpha: web_sys::WebGlBuffer,
@@ -218,7 +644,8 @@ pub struct TrailPass {
 
 impl TrailPass {
     /// Update trail buffers with current planet positions, then upload to GPU.
-    fn update_trails(&mut self, gl: &GL, bodies: &[CelestialBody]) {
+    fn update_trails(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        let gl = ctx.gl;
         let planets: Vec<&CelestialBody> = bodies.iter().filter(|b| !b.is_star).collect();
 
         for (i, planet) in planets.iter().enumerate() {
@@ -238,7 +665,7 @@ impl TrailPass {
                 let pos_data: Vec<f32> = trail
                     .positions
                     .iter()
-                    .flat_map(|p| [p.x, p.y, p.z])
+                    .flat_map(|&p| ctx.relative(p).to_array())
                     .collect();
 
                 // Build alpha data (0.0 at oldest → 1.0 at newest)
@@ -276,14 +703,15 @@ impl RenderPass for TrailPass {
     fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
         let gl = ctx.gl;
 
-        // Update trail data on CPU & GPU
-        self.update_trails(gl, bodies);
+        // Update trail data on CPU & GPU — even while hidden, so the trails
+        // are current when shown again.
+        self.update_trails(ctx, bodies);
+        if !ctx.layers.trails {
+            return;
+        }
 
         let s = &self.shader;
-        s.activate(gl);
-
-        s.set_mat4(gl, "u_view", &ctx.view);
-        s.set_mat4(gl, "u_projection", &ctx.projection);
+        s.activate(ctx);
 
         let planets: Vec<&CelestialBody> = bodies.iter().filter(|b| !b.is_star).collect();
 
@@ -294,10 +722,458 @@ impl RenderPass for TrailPass {
                     continue;
                 }
                 s.set_vec3(gl, "u_color", &planet.color);
-                gl.bind_vertex_array(Some(&trail.vao));
+                ctx.bind_vertex_array(Some(&trail.vao));
                 gl.draw_arrays(GL::LINE_STRIP, 0, len as i32);
-                gl.bind_vertex_array(None);
+                ctx.count_draws(1);
             }
         }
+        ctx.bind_vertex_array(None);
+    }
+
+    fn draw_ids(&self, ctx: &FrameContext, bodies: &[CelestialBody], ids: &ShaderProgram) {
+        if !ctx.layers.trails {
+            return;
+        }
+        let gl = ctx.gl;
+        // Trail vertices were uploaded camera-relative by the last frame.
+        ids.set_mat4(gl, "u_model", &Mat4::IDENTITY);
+
+        let planets = bodies.iter().enumerate().filter(|(_, b)| !b.is_star).map(|(i, _)| i);
+        for (body, trail) in planets.zip(&self.trails) {
+            let len = trail.positions.len();
+            if len < 2 {
+                continue;
+            }
+            ids.set_vec4(gl, "u_id", &PickTarget::Trail(body).color());
+            ctx.bind_vertex_array(Some(&trail.vao));
+            gl.draw_arrays(GL::LINE_STRIP, 0, len as i32);
+            ctx.count_draws(1);
+        }
+        ctx.bind_vertex_array(None);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        for trail in &self.trails {
+            gl.delete_buffer(Some(&trail.vbo_pos));
+            gl.delete_buffer(Some(&trail.vbo_alpha));
+            gl.delete_vertex_array(Some(&trail.vao));
+        }
+    }
+}
+
+// ─── Screen-space text ───────────────────────────────────────────────────
+
+/// Floats per label vertex: screen.xy, uv.xy, alpha.
+const LABEL_VERTEX_FLOATS: usize = 5;
+
+/// Text drawn in screen space from the SDF font atlas, batched into one
+/// draw call per frame.
+pub struct TextBatch {
+    shader: ShaderProgram,
+    vao: web_sys::WebGlVertexArrayObject,
+    vbo: web_sys::WebGlBuffer,
+    atlas: web_sys::WebGlTexture,
+    /// Per-frame vertex staging, reused across frames.
+    vertices: Vec<f32>,
+}
+
+impl TextBatch {
+    pub fn new(gl: &GL, shader: ShaderProgram) -> Result<Self, JsValue> {
+        let atlas = font::create_atlas_texture(gl)?;
+        let vao = gl
+            .create_vertex_array()
+            .ok_or_else(|| JsValue::from_str("Failed to create label VAO"))?;
+        let vbo = gl
+            .create_buffer()
+            .ok_or_else(|| JsValue::from_str("Failed to create label buffer"))?;
+
+        gl.bind_vertex_array(Some(&vao));
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&vbo));
+        let stride = (LABEL_VERTEX_FLOATS * 4) as i32;
+        gl.vertex_attrib_pointer_with_i32(0, 2, GL::FLOAT, false, stride, 0);
+        gl.enable_vertex_attrib_array(0);
+        gl.vertex_attrib_pointer_with_i32(1, 2, GL::FLOAT, false, stride, 8);
+        gl.enable_vertex_attrib_array(1);
+        gl.vertex_attrib_pointer_with_i32(2, 1, GL::FLOAT, false, stride, 16);
+        gl.enable_vertex_attrib_array(2);
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            shader,
+            vao,
+            vbo,
+            atlas,
+            vertices: Vec::new(),
+        })
+    }
+
+    /// Start a new frame's batch.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    /// Queue `text` with its top-left corner at `origin` (device pixels).
+    pub fn push(&mut self, text: &str, origin: Vec2, size: f32, alpha: f32) {
+        for q in font::layout(text, origin, size) {
+            let corners = [
+                (q.min.x, q.min.y, q.uv_min.x, q.uv_min.y),
+                (q.min.x, q.max.y, q.uv_min.x, q.uv_max.y),
+                (q.max.x, q.max.y, q.uv_max.x, q.uv_max.y),
+                (q.max.x, q.min.y, q.uv_max.x, q.uv_min.y),
+            ];
+            for k in [0, 1, 2, 0, 2, 3] {
+                let (x, y, u, v) = corners[k];
+                self.vertices.extend_from_slice(&[x, y, u, v, alpha]);
+            }
+        }
+    }
+
+    /// Draw the queued text in `color` above the scene.
+    pub fn draw(&self, ctx: &FrameContext, color: [f32; 3]) {
+        if self.vertices.is_empty() {
+            return;
+        }
+        let gl = ctx.gl;
+        let s = &self.shader;
+        s.activate(ctx);
+        s.set_vec2(gl, "u_viewport", &[ctx.viewport_width, ctx.viewport_height]);
+        s.set_vec3(gl, "u_color", &color);
+        let halo = if ctx.preferences.high_contrast {
+            HIGH_CONTRAST_LABEL_HALO_ALPHA
+        } else {
+            LABEL_HALO_ALPHA
+        };
+        s.set_float(gl, "u_halo", halo);
+        ctx.bind_texture(GL::TEXTURE_2D, Some(&self.atlas));
+        s.set_int(gl, "u_atlas", 0);
+
+        ctx.bind_vertex_array(Some(&self.vao));
+        ctx.bind_buffer(GL::ARRAY_BUFFER, Some(&self.vbo));
+        unsafe {
+            let array = js_sys::Float32Array::view(&self.vertices);
+            gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &array, GL::DYNAMIC_DRAW);
+        }
+
+        // Text floats above the scene.
+        ctx.set_enabled(GL::DEPTH_TEST, false);
+        gl.draw_arrays(GL::TRIANGLES, 0, (self.vertices.len() / LABEL_VERTEX_FLOATS) as i32);
+        ctx.set_enabled(GL::DEPTH_TEST, true);
+
+        ctx.bind_vertex_array(None);
+        ctx.bind_texture(GL::TEXTURE_2D, None);
+        ctx.count_draws(1);
+    }
+
+    pub fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        gl.delete_buffer(Some(&self.vbo));
+        gl.delete_vertex_array(Some(&self.vao));
+        gl.delete_texture(Some(&self.atlas));
+    }
+}
+
+// ─── Label pass ──────────────────────────────────────────────────────────
+
+/// Body names drawn in screen space from the SDF font atlas.
+///
+/// Labels sit to the right of each body's projected disc, fade with camera
+/// distance, and lose to larger (on screen) bodies when they overlap.
+pub struct LabelPass {
+    text: TextBatch,
+    /// Per-frame scratch: label text, top-left origin and opacity.
+    labels: Vec<(String, Vec2, f32)>,
+    placements: Vec<LabelPlacement>,
+}
+
+impl LabelPass {
+    pub fn new(gl: &GL, shader: ShaderProgram) -> Result<Self, JsValue> {
+        Ok(Self {
+            text: TextBatch::new(gl, shader)?,
+            labels: Vec::new(),
+            placements: Vec::new(),
+        })
+    }
+
+    /// Collect a candidate label for every body in front of the camera.
+    fn place_labels(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        self.labels.clear();
+        self.placements.clear();
+
+        let viewport = Vec2::new(ctx.viewport_width, ctx.viewport_height);
+        let view_projection = ctx.projection * ctx.view;
+        let size = LABEL_SIZE_PX * ctx.pixel_ratio;
+        let margin = Vec2::splat(LABEL_MARGIN_PX * ctx.pixel_ratio);
+
+        for body in bodies {
+            let relative = ctx.relative(body.position);
+            let distance = relative.length();
+            let alpha = labels::fade_alpha(distance);
+            if alpha <= 0.0 {
+                continue;
+            }
+            let Some(anchor) = labels::project_to_screen(&view_projection, relative, viewport) else {
+                continue;
+            };
+            let radius_px = lod::projected_radius_px(
+                body.display_radius,
+                distance,
+                &ctx.projection,
+                ctx.viewport_height,
+            )
+            .min(viewport.y);
+
+            let name = ctx.i18n.body_name(body.id);
+            let text = match ctx.label_mode {
+                LabelMode::NamesAndDistance => {
+                    let au = ctx.i18n.number((distance / AU_TO_DISPLAY) as f64, 2);
+                    ctx.i18n.format("label.distance", &[("name", name), ("distance", &au)])
+                }
+                _ => name.to_string(),
+            };
+            let origin = anchor + Vec2::new(radius_px + LABEL_OFFSET_PX * ctx.pixel_ratio, -0.5 * size);
+            let extent = Vec2::new(font::text_width(&text, size), size);
+
+            self.placements.push(LabelPlacement {
+                rect: Rect {
+                    min: origin - margin,
+                    max: origin + extent + margin,
+                },
+                priority: radius_px,
+            });
+            self.labels.push((text, origin, alpha));
+        }
+    }
+}
+
+impl RenderPass for LabelPass {
+    fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        if ctx.label_mode == LabelMode::Off {
+            return;
+        }
+        self.place_labels(ctx, bodies);
+
+        let size = LABEL_SIZE_PX * ctx.pixel_ratio;
+        self.text.clear();
+        for i in labels::cull_overlaps(&self.placements) {
+            let (text, origin, alpha) = &self.labels[i];
+            self.text.push(text, *origin, size, *alpha);
+        }
+        self.text.draw(ctx, [1.0, 1.0, 1.0]);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.text.destroy(gl);
+    }
+}
+
+// ─── Measure pass ────────────────────────────────────────────────────────
+
+/// Lines between the measured bodies, with the distance at the middle of
+/// each and the angle at the middle body of three.
+pub struct MeasurePass {
+    /// Line program (the orbit shaders).
+    shader: ShaderProgram,
+    vao: web_sys::WebGlVertexArrayObject,
+    vbo: web_sys::WebGlBuffer,
+    text: TextBatch,
+    /// Camera-relative line vertices, reused across frames.
+    points: Vec<f32>,
+}
+
+impl MeasurePass {
+    pub fn new(
+        gl: &GL,
+        line_shader: ShaderProgram,
+        text_shader: ShaderProgram,
+    ) -> Result<Self, JsValue> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or_else(|| JsValue::from_str("Failed to create measure VAO"))?;
+        let vbo = gl
+            .create_buffer()
+            .ok_or_else(|| JsValue::from_str("Failed to create measure buffer"))?;
+        gl.bind_vertex_array(Some(&vao));
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&vbo));
+        gl.vertex_attrib_pointer_with_i32(0, 3, GL::FLOAT, false, 0, 0);
+        gl.enable_vertex_attrib_array(0);
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            shader: line_shader,
+            vao,
+            vbo,
+            text: TextBatch::new(gl, text_shader)?,
+            points: Vec::with_capacity(MAX_MEASURED * 3),
+        })
+    }
+
+    /// Queue `text` centred horizontally on `anchor`, above it.
+    fn push_label(&mut self, ctx: &FrameContext, text: &str, anchor: Vec2) {
+        let size = LABEL_SIZE_PX * ctx.pixel_ratio;
+        let offset = LABEL_OFFSET_PX * ctx.pixel_ratio;
+        let origin = anchor - Vec2::new(0.5 * font::text_width(text, size), size + offset);
+        self.text.push(text, origin, size, 1.0);
+    }
+}
+
+impl RenderPass for MeasurePass {
+    fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        let mut buffer = [DVec3::ZERO; MAX_MEASURED];
+        let mut count = 0;
+        for body in ctx.measurement.bodies().iter().filter_map(|&i| bodies.get(i)) {
+            buffer[count] = body.position;
+            count += 1;
+        }
+        let positions = &buffer[..count];
+        if positions.len() < 2 {
+            return;
+        }
+        let gl = ctx.gl;
+
+        // ── Lines ──
+        self.points.clear();
+        for &p in positions {
+            self.points.extend_from_slice(&ctx.relative(p).to_array());
+        }
+        let s = &self.shader;
+        s.activate(ctx);
+        s.set_mat4(gl, "u_model", &Mat4::IDENTITY);
+        s.set_vec3(gl, "u_color", &MEASURE_COLOR);
+        s.set_float(gl, "u_alpha", MEASURE_ALPHA);
+        ctx.bind_vertex_array(Some(&self.vao));
+        ctx.bind_buffer(GL::ARRAY_BUFFER, Some(&self.vbo));
+        unsafe {
+            let array = js_sys::Float32Array::view(&self.points);
+            gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &array, GL::DYNAMIC_DRAW);
+        }
+        // The line runs between body centres, so it would vanish inside them.
+        ctx.set_enabled(GL::DEPTH_TEST, false);
+        gl.draw_arrays(GL::LINE_STRIP, 0, positions.len() as i32);
+        ctx.set_enabled(GL::DEPTH_TEST, true);
+        ctx.bind_vertex_array(None);
+        ctx.count_draws(1);
+
+        // ── Labels ──
+        let viewport = Vec2::new(ctx.viewport_width, ctx.viewport_height);
+        let view_projection = ctx.projection * ctx.view;
+        let screen = |world: DVec3| {
+            labels::project_to_screen(&view_projection, ctx.relative(world), viewport)
+        };
+        self.text.clear();
+        for pair in positions.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if let Some(anchor) = screen((a + b) * 0.5) {
+                let label = Distance::between(a, b).label(ctx.i18n);
+                self.push_label(ctx, &label, anchor);
+            }
+        }
+        if let [a, vertex, b] = positions[..]
+            && let Some(degrees) = measure::angle_deg(a, vertex, b)
+            && let Some(anchor) = screen(vertex)
+        {
+            let label = measure::angle_label(degrees, ctx.i18n);
+            self.push_label(ctx, &label, anchor);
+        }
+        self.text.draw(ctx, MEASURE_COLOR);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        gl.delete_buffer(Some(&self.vbo));
+        gl.delete_vertex_array(Some(&self.vao));
+        self.text.destroy(gl);
+    }
+}
+
+// ─── Highlight pass ──────────────────────────────────────────────────────
+
+/// Glow ring around the body under the pointer, with a tooltip giving its
+/// name and distance.
+pub struct HighlightPass {
+    shader: ShaderProgram,
+    /// Empty VAO — the ring quad is expanded from `gl_VertexID`.
+    vao: web_sys::WebGlVertexArrayObject,
+    text: TextBatch,
+}
+
+impl HighlightPass {
+    pub fn new(gl: &GL, shader: ShaderProgram, text_shader: ShaderProgram) -> Result<Self, JsValue> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or_else(|| JsValue::from_str("Failed to create highlight VAO"))?;
+        Ok(Self {
+            shader,
+            vao,
+            text: TextBatch::new(gl, text_shader)?,
+        })
+    }
+}
+
+impl RenderPass for HighlightPass {
+    fn draw(&mut self, ctx: &FrameContext, bodies: &[CelestialBody]) {
+        let Some(body) = ctx.hovered.and_then(|i| bodies.get(i)) else {
+            return;
+        };
+        let relative = ctx.relative(body.position);
+        let distance = relative.length();
+        let viewport = Vec2::new(ctx.viewport_width, ctx.viewport_height);
+        let Some(anchor) = labels::project_to_screen(&(ctx.projection * ctx.view), relative, viewport)
+        else {
+            return;
+        };
+        let radius_px = lod::projected_radius_px(
+            body.display_radius,
+            distance,
+            &ctx.projection,
+            ctx.viewport_height,
+        );
+        if !radius_px.is_finite() || radius_px <= 0.0 {
+            // The camera is inside the body.
+            return;
+        }
+        let ring_px = (radius_px * HOVER_RING_SCALE).max(HOVER_RING_MIN_PX * ctx.pixel_ratio);
+        let gl = ctx.gl;
+
+        // ── Ring (depth-tested so nearer bodies hide it, no depth writes) ──
+        let s = &self.shader;
+        s.activate(ctx);
+        ctx.bind_vertex_array(Some(&self.vao));
+        ctx.depth_mask(false);
+        s.set_vec3(gl, "u_center", &relative.to_array());
+        s.set_float(gl, "u_radius", body.display_radius * ring_px / radius_px);
+        s.set_float(gl, "u_extent", HOVER_GLOW_EXTENT);
+        s.set_vec3(gl, "u_color", &HOVER_COLOR);
+        gl.draw_arrays(GL::TRIANGLE_STRIP, 0, 4);
+        ctx.depth_mask(true);
+        ctx.bind_vertex_array(None);
+        ctx.count_draws(1);
+
+        // ── Tooltip, centred above the ring ──
+        let distance_au = if body.is_star {
+            distance as f64 / AU_TO_DISPLAY as f64
+        } else {
+            bodies
+                .iter()
+                .find(|b| b.is_star)
+                .map_or(body.position.length(), |sun| body.position.distance(sun.position))
+                / AU_TO_DISPLAY as f64
+        };
+        let text = labels::tooltip_text(body.id, body.is_star, distance_au, ctx.i18n);
+        let size = LABEL_SIZE_PX * ctx.pixel_ratio;
+        let origin = anchor
+            - Vec2::new(
+                0.5 * font::text_width(&text, size),
+                ring_px + size + LABEL_OFFSET_PX * ctx.pixel_ratio,
+            );
+        self.text.clear();
+        self.text.push(&text, origin, size, 1.0);
+        self.text.draw(ctx, HOVER_COLOR);
+    }
+
+    fn destroy(&mut self, gl: &GL) {
+        self.shader.delete(gl);
+        gl.delete_vertex_array(Some(&self.vao));
+        self.text.destroy(gl);
     }
 }
@@ -9,6 +9,8 @@ use glam::Mat4;
 use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext as GL;
 
+use super::render_pass::FrameContext;
+
 // ─── Shader compilation ──────────────────────────────────────────────────
 
 /// Compile a single GLSL shader (vertex or fragment).
@@ -31,6 +33,28 @@ fn compile_shader(gl: &GL, shader_type: u32, source: &str) -> Result<web_sys::We
     Ok(shader)
 }
 
+/// Resolve `#include "name"` lines against a fixed table of GLSL snippets.
+///
+/// GLSL ES has no include mechanism, so shared code (e.g. noise functions)
+/// is spliced in before compilation. Unknown names are left untouched so the
+/// GLSL compiler reports them.
+pub fn resolve_includes(source: &str, includes: &[(&str, &str)]) -> String {
+    source
+        .lines()
+        .map(|line| {
+            let name = line
+                .trim()
+                .strip_prefix("#include")
+                .map(|rest| rest.trim().trim_matches('"'));
+            match name.and_then(|n| includes.iter().find(|(inc, _)| *inc == n)) {
+                Some((_, snippet)) => *snippet,
+                None => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // ─── ShaderProgram ───────────────────────────────────────────────────────
 
 /// A compiled+linked WebGL program with cached uniform locations.
@@ -80,9 +104,25 @@ impl ShaderProgram {
         Ok(Self { program, locations })
     }
 
-    /// Bind this program for use.
-    pub fn activate(&self, gl: &GL) {
-        gl.use_program(Some(&self.program));
+    /// Bind this program for use, counting the state change.
+    pub fn activate(&self, ctx: &FrameContext) {
+        ctx.gl.use_program(Some(&self.program));
+        ctx.count_state_change();
+    }
+
+    /// Delete the program; it must not be activated afterwards.
+    pub fn delete(&self, gl: &GL) {
+        gl.delete_program(Some(&self.program));
+    }
+
+    /// Attach the named uniform block to a uniform-buffer binding point.
+    ///
+    /// Silently ignored when the block is not active in this program.
+    pub fn bind_uniform_block(&self, gl: &GL, block_name: &str, binding: u32) {
+        let index = gl.get_uniform_block_index(&self.program, block_name);
+        if index != GL::INVALID_INDEX {
+            gl.uniform_block_binding(&self.program, index, binding);
+        }
     }
 
     /// Get a cached uniform location (returns `None` for inactive/optimized-out uniforms).
@@ -98,12 +138,24 @@ impl ShaderProgram {
         }
     }
 
+    pub fn set_vec2(&self, gl: &GL, name: &str, v: &[f32; 2]) {
+        if let Some(loc) = self.loc(name) {
+            gl.uniform2f(Some(loc), v[0], v[1]);
+        }
+    }
+
     pub fn set_vec3(&self, gl: &GL, name: &str, v: &[f32; 3]) {
         if let Some(loc) = self.loc(name) {
             gl.uniform3f(Some(loc), v[0], v[1], v[2]);
         }
     }
 
+    pub fn set_vec4(&self, gl: &GL, name: &str, v: &[f32; 4]) {
+        if let Some(loc) = self.loc(name) {
+            gl.uniform4f(Some(loc), v[0], v[1], v[2], v[3]);
+        }
+    }
+
     pub fn set_float(&self, gl: &GL, name: &str, val: f32) {
         if let Some(loc) = self.loc(name) {
             gl.uniform1f(Some(loc), val);
@@ -1,24 +1,201 @@
-//! Asynchronous texture loading from image URLs.
+//! Asynchronous texture loading into a shared `TEXTURE_2D_ARRAY`.
 //!
-//! Each planet texture is loaded via an `HtmlImageElement`. The image
-//! reference is captured directly in the onload closure — no hidden DOM
-//! elements or `get_element_by_id` hacks needed.
+//! Every textured body owns one layer of a single array texture, so the
+//! instanced planet pass can sample any body without rebinding. Images are
+//! loaded via `HtmlImageElement`; on load they are uploaded to a temporary
+//! 2D texture and blitted (with scaling) into their layer, since source
+//! images come in different sizes.
+//!
+//! Each image's load and error callbacks are kept with the array until one
+//! of them fires (or the array is deleted), and then freed.
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use web_sys::WebGl2RenderingContext as GL;
 
+use crate::constants::{TEXTURE_ARRAY_HEIGHT, TEXTURE_ARRAY_WIDTH};
 use crate::simulation::body::CelestialBody;
 
-/// Shared handle to the texture map so multiple closures can insert into it.
-pub type TextureMap = Rc<RefCell<HashMap<String, web_sys::WebGlTexture>>>;
+/// One array texture holding a layer per textured body.
+pub struct TextureArray {
+    pub texture: web_sys::WebGlTexture,
+    /// Body name → layer index (assigned up front, in body order).
+    layers: HashMap<String, i32>,
+    /// Bodies whose layer has been filled.
+    loaded: HashSet<String>,
+    /// Images still loading, by body name.
+    pending: HashMap<String, PendingImage>,
+}
+
+/// An image being loaded, with the callbacks it will fire.
+struct PendingImage {
+    image: web_sys::HtmlImageElement,
+    _onload: Closure<dyn FnMut(web_sys::Event)>,
+    _onerror: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl PendingImage {
+    /// Unhook the callbacks from the image so they can be freed.
+    fn detach(&self) {
+        self.image.set_onload(None);
+        self.image.set_onerror(None);
+    }
+}
+
+impl TextureArray {
+    /// Allocate immutable storage for one layer per textured body.
+    pub fn new(gl: &GL, bodies: &[CelestialBody]) -> Result<Self, JsValue> {
+        let layers: HashMap<String, i32> = bodies
+            .iter()
+            .filter(|b| b.texture_file.is_some())
+            .enumerate()
+            .map(|(i, b)| (b.id.to_string(), i as i32))
+            .collect();
+
+        let texture = gl
+            .create_texture()
+            .ok_or_else(|| JsValue::from_str("Failed to create texture array"))?;
+        gl.bind_texture(GL::TEXTURE_2D_ARRAY, Some(&texture));
+        gl.tex_storage_3d(
+            GL::TEXTURE_2D_ARRAY,
+            mip_levels(TEXTURE_ARRAY_WIDTH, TEXTURE_ARRAY_HEIGHT),
+            GL::RGBA8,
+            TEXTURE_ARRAY_WIDTH,
+            TEXTURE_ARRAY_HEIGHT,
+            layers.len().max(1) as i32,
+        );
+        gl.tex_parameteri(GL::TEXTURE_2D_ARRAY, GL::TEXTURE_WRAP_S, GL::REPEAT as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D_ARRAY, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(
+            GL::TEXTURE_2D_ARRAY,
+            GL::TEXTURE_MIN_FILTER,
+            GL::LINEAR_MIPMAP_LINEAR as i32,
+        );
+        gl.tex_parameteri(GL::TEXTURE_2D_ARRAY, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+        gl.bind_texture(GL::TEXTURE_2D_ARRAY, None);
+
+        Ok(Self {
+            texture,
+            layers,
+            loaded: HashSet::new(),
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Layer index for `body_name`, once its image has been uploaded.
+    pub fn layer(&self, body_name: &str) -> Option<i32> {
+        if self.loaded.contains(body_name) {
+            self.layers.get(body_name).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Delete the texture. Layers are forgotten and images still loading
+    /// are unhooked, freeing their callbacks.
+    pub fn delete(&mut self, gl: &GL) {
+        gl.delete_texture(Some(&self.texture));
+        self.layers.clear();
+        self.loaded.clear();
+        for (_, pending) in self.pending.drain() {
+            pending.detach();
+        }
+    }
+
+    /// The image for `body_name` loaded or failed: free its callbacks.
+    /// The one running finishes normally; wasm-bindgen frees it afterwards.
+    fn settle(&mut self, body_name: &str) {
+        if let Some(pending) = self.pending.remove(body_name) {
+            pending.detach();
+        }
+    }
+
+    /// Scale `image` into the body's layer and rebuild the mip chain.
+    fn upload(&mut self, gl: &GL, body_name: &str, image: &web_sys::HtmlImageElement) {
+        let Some(&layer) = self.layers.get(body_name) else {
+            return;
+        };
+
+        // Temporary 2D texture at the image's native size.
+        let Some(source) = gl.create_texture() else {
+            return;
+        };
+        gl.bind_texture(GL::TEXTURE_2D, Some(&source));
+        if gl
+            .tex_image_2d_with_u32_and_u32_and_html_image_element(
+                GL::TEXTURE_2D,
+                0,
+                GL::RGBA as i32,
+                GL::RGBA,
+                GL::UNSIGNED_BYTE,
+                image,
+            )
+            .is_err()
+        {
+            gl.delete_texture(Some(&source));
+            return;
+        }
+
+        // Blit native size → layer size through a pair of framebuffers.
+        let read_fb = gl.create_framebuffer();
+        let draw_fb = gl.create_framebuffer();
+        gl.bind_framebuffer(GL::READ_FRAMEBUFFER, read_fb.as_ref());
+        gl.framebuffer_texture_2d(
+            GL::READ_FRAMEBUFFER,
+            GL::COLOR_ATTACHMENT0,
+            GL::TEXTURE_2D,
+            Some(&source),
+            0,
+        );
+        gl.bind_framebuffer(GL::DRAW_FRAMEBUFFER, draw_fb.as_ref());
+        gl.framebuffer_texture_layer(
+            GL::DRAW_FRAMEBUFFER,
+            GL::COLOR_ATTACHMENT0,
+            Some(&self.texture),
+            0,
+            layer,
+        );
+        gl.blit_framebuffer(
+            0,
+            0,
+            image.natural_width() as i32,
+            image.natural_height() as i32,
+            0,
+            0,
+            TEXTURE_ARRAY_WIDTH,
+            TEXTURE_ARRAY_HEIGHT,
+            GL::COLOR_BUFFER_BIT,
+            GL::LINEAR,
+        );
+        gl.bind_framebuffer(GL::READ_FRAMEBUFFER, None);
+        gl.bind_framebuffer(GL::DRAW_FRAMEBUFFER, None);
+        gl.delete_framebuffer(read_fb.as_ref());
+        gl.delete_framebuffer(draw_fb.as_ref());
+        gl.bind_texture(GL::TEXTURE_2D, None);
+        gl.delete_texture(Some(&source));
+
+        gl.bind_texture(GL::TEXTURE_2D_ARRAY, Some(&self.texture));
+        gl.generate_mipmap(GL::TEXTURE_2D_ARRAY);
+        gl.bind_texture(GL::TEXTURE_2D_ARRAY, None);
+
+        self.loaded.insert(body_name.to_string());
+    }
+}
+
+/// Number of mip levels for a full chain down to 1×1.
+pub fn mip_levels(width: i32, height: i32) -> i32 {
+    32 - (width.max(height).max(1) as u32).leading_zeros() as i32
+}
+
+/// Shared handle to the texture array so load callbacks can fill layers.
+pub type TextureMap = Rc<RefCell<TextureArray>>;
 
 /// Shared counter for tracking how many textures have finished loading.
 type LoadedCounter = Rc<RefCell<usize>>;
 
-/// Load a single texture from `url` and store it under `body_name`.
+/// Load a single texture from `url` into the layer reserved for `body_name`.
 pub fn load_texture_async(
     gl: &GL,
     textures: &TextureMap,
@@ -26,87 +203,95 @@ pub fn load_texture_async(
     url: &str,
     loaded_count: LoadedCounter,
     total: usize,
+    splash: bool,
 ) {
-    let image = Rc::new(web_sys::HtmlImageElement::new().unwrap());
+    let image = web_sys::HtmlImageElement::new().unwrap();
     image.set_cross_origin(Some("anonymous"));
 
     let gl_clone = gl.clone();
     let textures_clone = Rc::clone(textures);
     let name = body_name.to_string();
-    let image_ref = Rc::clone(&image);
+    let image_ref = image.clone();
     let loaded_ok = Rc::clone(&loaded_count);
 
     let onload = Closure::wrap(Box::new(move |_: web_sys::Event| {
-        let gl = &gl_clone;
-        let texture = gl.create_texture().unwrap();
-        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
-
-        gl.tex_image_2d_with_u32_and_u32_and_html_image_element(
-            GL::TEXTURE_2D,
-            0,
-            GL::RGBA as i32,
-            GL::RGBA,
-            GL::UNSIGNED_BYTE,
-            &image_ref,
-        )
-        .unwrap();
-
-        gl.generate_mipmap(GL::TEXTURE_2D);
-
-        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::REPEAT as i32);
-        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
-        gl.tex_parameteri(
-            GL::TEXTURE_2D,
-            GL::TEXTURE_MIN_FILTER,
-            GL::LINEAR_MIPMAP_LINEAR as i32,
-        );
-        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
-
-        gl.bind_texture(GL::TEXTURE_2D, None);
-
-        textures_clone.borrow_mut().insert(name.clone(), texture);
+        {
+            let mut textures = textures_clone.borrow_mut();
+            textures.upload(&gl_clone, &name, &image_ref);
+            textures.settle(&name);
+        }
         log::info!("🌍 Texture loaded: {}", name);
 
         // ── Splash progress ──
-        crate::splash::update_step(&format!("tex-{}", name), "done");
         let mut count = loaded_ok.borrow_mut();
         *count += 1;
-        if *count >= total {
-            crate::splash::hide_splash();
+        if splash {
+            crate::splash::update_step(&format!("tex-{}", name), "done");
+            if *count >= total {
+                crate::splash::hide_splash();
+            }
         }
     }) as Box<dyn FnMut(web_sys::Event)>);
 
     image.set_onload(Some(onload.as_ref().unchecked_ref()));
-    onload.forget();
 
     // Handle load errors so the splash still completes
+    let textures_err = Rc::clone(textures);
     let name_err = body_name.to_string();
     let loaded_err = Rc::clone(&loaded_count);
     let onerror = Closure::wrap(Box::new(move |_: web_sys::Event| {
+        textures_err.borrow_mut().settle(&name_err);
         log::warn!("⚠️ Failed to load texture: {}", name_err);
-        crate::splash::update_step(&format!("tex-{}", name_err), "done");
         let mut count = loaded_err.borrow_mut();
         *count += 1;
-        if *count >= total {
-            crate::splash::hide_splash();
+        if splash {
+            crate::splash::update_step(&format!("tex-{}", name_err), "done");
+            if *count >= total {
+                crate::splash::hide_splash();
+            }
         }
     }) as Box<dyn FnMut(web_sys::Event)>);
     image.set_onerror(Some(onerror.as_ref().unchecked_ref()));
-    onerror.forget();
 
     image.set_src(url);
+    textures.borrow_mut().pending.insert(
+        body_name.to_string(),
+        PendingImage {
+            image,
+            _onload: onload,
+            _onerror: onerror,
+        },
+    );
 }
 
-/// Kick off asynchronous texture loading for every body that has a texture file.
-pub fn start_loading_textures(gl: &GL, textures: &TextureMap, bodies: &[CelestialBody]) {
+/// Kick off asynchronous texture loading for every body that has a texture
+/// file, fetching `{path}{file}`. With `splash`, progress is reported to the
+/// loading splash, which is hidden once the last texture has settled.
+pub fn start_loading_textures(
+    gl: &GL,
+    textures: &TextureMap,
+    bodies: &[CelestialBody],
+    path: &str,
+    splash: bool,
+) {
     let total = bodies.iter().filter(|b| b.texture_file.is_some()).count();
     let loaded_count: LoadedCounter = Rc::new(RefCell::new(0));
 
     for body in bodies {
         if let Some(file) = body.texture_file {
-            crate::splash::update_step(&format!("tex-{}", body.name), "loading");
-            let url = format!("textures/{file}");
-            load_texture_async(gl, textures, body.name, &url, Rc::clone(&loaded_count), total);
+            if splash {
+                crate::splash::update_step(&format!("tex-{}", body.id), "loading");
+            }
+            let url = format!("{path}{file}");
+            load_texture_async(
+                gl,
+                textures,
+                body.id,
+                &url,
+                Rc::clone(&loaded_count),
+                total,
+                splash,
+            );
         }
     }
 }
@@ -1,13 +1,14 @@
-use glam::Vec3;
+use glam::DVec3;
 
-use crate::constants::AU_TO_DISPLAY;
+use crate::constants::{AU_KM, AU_TO_DISPLAY, GRAVITATIONAL_CONSTANT, SUN_GM_KM3_S2};
 
 /// Represents a celestial body in the solar system.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct CelestialBody {
-    /// Display name
-    pub name: &'static str,
+    /// Stable identifier (English name), used in links, sessions, the API
+    /// and message keys; the displayed name comes from the locale catalogue.
+    pub id: &'static str,
     /// RGB color (0.0–1.0)
     pub color: [f32; 3],
     /// Visual display radius (log-scaled for visibility)
@@ -20,6 +21,14 @@ pub struct CelestialBody {
     pub orbital_period_days: f64,
     /// Orbital inclination in radians (relative to ecliptic)
     pub inclination_rad: f64,
+    /// Orbital eccentricity (for info display; orbits are drawn circular)
+    pub eccentricity: f64,
+    /// Mass in kg
+    pub mass_kg: f64,
+    /// Length of a solar day in hours
+    pub day_length_hours: f64,
+    /// Number of known moons
+    pub moons: u32,
     /// Starting orbital angle in radians (longitude at epoch)
     pub start_angle_rad: f64,
     /// Whether this body has rings (Saturn)
@@ -28,16 +37,19 @@ pub struct CelestialBody {
     pub is_star: bool,
     /// Texture filename (e.g. "earth.jpg"), if any
     pub texture_file: Option<&'static str>,
-    /// Current computed 3D position (updated each frame)
-    pub position: Vec3,
+    /// Current computed 3D position in display units (updated each frame).
+    ///
+    /// Kept in `f64`: the galactic drift carries it far from the origin, where
+    /// `f32` can no longer resolve planet-scale detail.
+    pub position: DVec3,
 }
 
 impl CelestialBody {
     /// Compute the position of this body at a given simulation time (in Earth days).
     /// Uses simplified circular Kepler orbits.
-    pub fn position_at(&self, time_days: f64) -> Vec3 {
+    pub fn position_at(&self, time_days: f64) -> DVec3 {
         if self.is_star {
-            return Vec3::ZERO;
+            return DVec3::ZERO;
         }
 
         // Mean angular velocity: ω = 2π / T
@@ -46,24 +58,51 @@ impl CelestialBody {
         // Current angle: θ = θ₀ + ωt
         let angle = self.start_angle_rad + omega * time_days;
 
-        let display_distance = self.semi_major_axis_au as f32 * AU_TO_DISPLAY;
+        let display_distance = self.semi_major_axis_au * AU_TO_DISPLAY as f64;
 
         // Position in the orbital plane, then tilt by inclination
-        let cos_a = angle.cos() as f32;
-        let sin_a = angle.sin() as f32;
-        let cos_i = self.inclination_rad.cos() as f32;
-        let sin_i = self.inclination_rad.sin() as f32;
+        let (sin_a, cos_a) = angle.sin_cos();
+        let (sin_i, cos_i) = self.inclination_rad.sin_cos();
 
-        Vec3::new(
+        DVec3::new(
             display_distance * cos_a,
             display_distance * sin_a * sin_i,
             display_distance * sin_a * cos_i,
         )
     }
 
+    /// Surface gravity at the equator in m/s².
+    pub fn surface_gravity(&self) -> f64 {
+        let radius_m = self.real_radius_km * 1000.0;
+        GRAVITATIONAL_CONSTANT * self.mass_kg / (radius_m * radius_m)
+    }
+
+    /// Escape velocity from the surface in km/s.
+    pub fn escape_velocity_km_s(&self) -> f64 {
+        (2.0 * GRAVITATIONAL_CONSTANT * self.mass_kg / (self.real_radius_km * 1000.0)).sqrt()
+            / 1000.0
+    }
+
+    /// Orbital speed around the Sun in km/s. Orbits are simulated as
+    /// circles, so this is the circular speed at the semi-major axis.
+    pub fn orbital_speed_km_s(&self) -> f64 {
+        if self.is_star {
+            return 0.0;
+        }
+        (SUN_GM_KM3_S2 / (self.semi_major_axis_au * AU_KM)).sqrt()
+    }
+
+    /// Angle of `heliocentric` (display units, relative to the Sun) around
+    /// the orbit, measured in the orbital plane like `position_at`'s angle.
+    pub fn orbit_angle(&self, heliocentric: DVec3) -> f64 {
+        let (sin_i, cos_i) = self.inclination_rad.sin_cos();
+        let along = heliocentric.y * sin_i + heliocentric.z * cos_i;
+        along.atan2(heliocentric.x).rem_euclid(std::f64::consts::TAU)
+    }
+
     /// Update the body's position for the current simulation time,
     /// including the cumulative galactic drift offset.
-    pub fn update(&mut self, time_days: f64, galactic_offset: glam::Vec3) {
+    pub fn update(&mut self, time_days: f64, galactic_offset: DVec3) {
         self.position = self.position_at(time_days) + galactic_offset;
     }
 }
@@ -2,7 +2,7 @@ pub mod body;
 pub mod orbit;
 pub mod time;
 
-use glam::Vec3;
+use glam::DVec3;
 
 use body::CelestialBody;
 use time::SimulationTime;
@@ -15,7 +15,7 @@ use crate::constants::GALACTIC_SPEED_DISPLAY;
 /// Computed from equatorial coordinates (RA = 277°, Dec = +30°):
 ///   x = cos(Dec)·cos(RA),  y = cos(Dec)·sin(RA),  z = sin(Dec)
 /// The result is already a unit vector (magnitude ≈ 1.0).
-const SOLAR_APEX: Vec3 = Vec3::new(0.10554, -0.85959, 0.50000);
+const SOLAR_APEX: DVec3 = DVec3::new(0.10554, -0.85959, 0.50000);
 
 /// The top-level simulation state: holds all celestial bodies and the clock.
 pub struct Simulation {
@@ -25,7 +25,7 @@ pub struct Simulation {
     ///
     /// Multiply by `time.current_days` to obtain the cumulative galactic offset that
     /// is added to every body's position each frame.
-    pub galactic_velocity: Vec3,
+    pub galactic_velocity: DVec3,
 }
 
 impl Simulation {
@@ -43,7 +43,7 @@ impl Simulation {
     pub fn update(&mut self, dt_seconds: f64) {
         self.time.advance(dt_seconds);
         let t = self.time.current_days;
-        let galactic_offset = self.galactic_velocity * t as f32;
+        let galactic_offset = self.galactic_velocity * t;
         for body in &mut self.bodies {
             body.update(t, galactic_offset);
         }
@@ -0,0 +1,231 @@
+//! Camera bookmarks and scripted fly-through tours.
+//!
+//! A [`Bookmark`] records a view relative to a body (orbit angles and
+//! distance) plus, optionally, the simulation date and speed, so a shot can
+//! be repeated exactly. A [`Tour`] is a JSON file that sequences bookmarks
+//! with travel times, holds and easing curves:
+//!
+//! ```json
+//! {
+//!   "name": "Inner planets",
+//!   "bookmarks": [
+//!     { "name": "earth", "body": "Earth", "theta": 0.4, "phi": 0.3, "distance": 12.0 }
+//!   ],
+//!   "stops": [
+//!     { "bookmark": "earth", "travel": 4.0, "hold": 2.0, "easing": "ease_in_out" }
+//!   ]
+//! }
+//! ```
+//!
+//! [`TourPlayer`] only keeps time; the app applies each stop as it begins,
+//! and the camera's timed [`Transition`](crate::renderer::camera::Transition)
+//! does the interpolation.
+
+use std::fmt;
+
+use glam::DVec3;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::TOUR_DEFAULT_TRAVEL_SECONDS;
+use crate::renderer::camera::{Camera, CameraPose, Easing};
+use crate::simulation::time::SimulationTime;
+
+/// Tour bundled with the app (`www/tours/grand-tour.json`).
+pub const DEFAULT_TOUR: &str = include_str!("../www/tours/grand-tour.json");
+
+/// A named camera view around a body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Name of the body the camera orbits (case-insensitive).
+    pub body: String,
+    pub theta: f32,
+    pub phi: f32,
+    pub distance: f32,
+    /// Simulation date in days since J2000; the clock is left alone if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+    /// Simulation speed in days per second; left alone if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+
+impl Bookmark {
+    /// Record the current view of `body`, including the clock.
+    pub fn capture(name: &str, body: &str, camera: &Camera, time: &SimulationTime) -> Self {
+        Self {
+            name: name.to_string(),
+            body: body.to_string(),
+            theta: camera.theta,
+            phi: camera.phi,
+            distance: camera.distance,
+            time: Some(time.current_days),
+            speed: Some(time.days_per_second),
+        }
+    }
+
+    /// Camera pose for this bookmark with its body at `body_position`.
+    pub fn pose(&self, body_position: DVec3) -> CameraPose {
+        CameraPose {
+            target: body_position,
+            theta: self.theta,
+            phi: self.phi,
+            distance: self.distance,
+        }
+    }
+}
+
+fn default_travel() -> f32 {
+    TOUR_DEFAULT_TRAVEL_SECONDS
+}
+
+/// One step of a tour: fly to a bookmark, then stay there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TourStop {
+    /// Name of a bookmark in the same tour.
+    pub bookmark: String,
+    /// Seconds spent flying to the bookmark.
+    #[serde(default = "default_travel")]
+    pub travel: f32,
+    /// Seconds spent at the bookmark before the next stop.
+    #[serde(default)]
+    pub hold: f32,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+impl TourStop {
+    /// Total length of the stop in seconds.
+    pub fn duration(&self) -> f32 {
+        self.travel + self.hold
+    }
+}
+
+/// A sequence of bookmark stops.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tour {
+    pub name: String,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    pub stops: Vec<TourStop>,
+}
+
+/// Why a tour file was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TourError {
+    /// Not valid JSON, or not shaped like a tour.
+    Parse(String),
+    /// The tour has no stops.
+    Empty,
+    /// A stop names a bookmark the tour does not define.
+    UnknownBookmark { stop: usize, bookmark: String },
+    /// A stop has a negative or non-finite travel or hold time.
+    InvalidDuration { stop: usize },
+}
+
+impl fmt::Display for TourError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TourError::Parse(message) => write!(f, "tour: {message}"),
+            TourError::Empty => write!(f, "tour has no stops"),
+            TourError::UnknownBookmark { stop, bookmark } => {
+                write!(f, "tour stop {stop}: unknown bookmark `{bookmark}`")
+            }
+            TourError::InvalidDuration { stop } => {
+                write!(f, "tour stop {stop}: travel and hold must be non-negative")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TourError {}
+
+impl Tour {
+    /// Parse and validate a tour file.
+    pub fn from_json(text: &str) -> Result<Self, TourError> {
+        let tour: Tour = serde_json::from_str(text).map_err(|e| TourError::Parse(e.to_string()))?;
+        if tour.stops.is_empty() {
+            return Err(TourError::Empty);
+        }
+        for (stop, s) in tour.stops.iter().enumerate() {
+            if tour.bookmark(&s.bookmark).is_none() {
+                return Err(TourError::UnknownBookmark {
+                    stop,
+                    bookmark: s.bookmark.clone(),
+                });
+            }
+            let valid = |v: f32| v.is_finite() && v >= 0.0;
+            if !valid(s.travel) || !valid(s.hold) {
+                return Err(TourError::InvalidDuration { stop });
+            }
+        }
+        Ok(tour)
+    }
+
+    /// Bookmark called `name`.
+    pub fn bookmark(&self, name: &str) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|b| b.name == name)
+    }
+}
+
+/// What the app should do after advancing a [`TourPlayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourStep {
+    /// Stop `index` has just begun: start flying to its bookmark.
+    Begin(usize),
+    /// Still travelling or holding.
+    Continue,
+    /// The last stop is over.
+    Finished,
+}
+
+/// Playback clock for a validated [`Tour`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TourPlayer {
+    tour: Tour,
+    /// Index of the current stop, `None` before the first [`advance`](Self::advance).
+    stop: Option<usize>,
+    /// Seconds into the current stop.
+    elapsed: f32,
+}
+
+impl TourPlayer {
+    pub fn new(tour: Tour) -> Self {
+        Self {
+            tour,
+            stop: None,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Stop `index` and the bookmark it flies to.
+    pub fn stop(&self, index: usize) -> Option<(&TourStop, &Bookmark)> {
+        let stop = self.tour.stops.get(index)?;
+        Some((stop, self.tour.bookmark(&stop.bookmark)?))
+    }
+
+    /// Advance the clock by `dt` seconds.
+    ///
+    /// The first call begins stop 0. At most one stop begins per call; any
+    /// left-over time is carried into it.
+    pub fn advance(&mut self, dt: f32) -> TourStep {
+        let Some(index) = self.stop else {
+            self.stop = Some(0);
+            return TourStep::Begin(0);
+        };
+        let Some(current) = self.tour.stops.get(index) else {
+            return TourStep::Finished;
+        };
+        self.elapsed += dt;
+        if self.elapsed < current.duration() {
+            return TourStep::Continue;
+        }
+        self.elapsed -= current.duration();
+        self.stop = Some(index + 1);
+        if index + 1 < self.tour.stops.len() {
+            TourStep::Begin(index + 1)
+        } else {
+            TourStep::Finished
+        }
+    }
+}
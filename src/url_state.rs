@@ -0,0 +1,322 @@
+//! Deep-linkable view state in the URL hash.
+//!
+//! The hash is a `&`-separated list of `key=value` pairs, e.g.
+//!
+//! ```text
+//! #date=2030-05-01T00:00:00Z&speed=1&paused=1&body=Jupiter&lock=1&theta=0.000&phi=1.400&dist=24.00&layers=orbits,trails&labels=names
+//! ```
+//!
+//! Every key is optional, so hand-written links can be as short as
+//! `#date=2030-05-01&body=Jupiter`. Unknown keys are ignored. Encoding and
+//! decoding are pure Rust; only [`read_hash`] and [`write_hash`] touch the DOM.
+//!
+//! Dates are UTC and map onto the simulation clock's days since J2000
+//! (2000-01-01 12:00 UTC).
+
+use std::fmt;
+
+use crate::renderer::labels::LabelMode;
+use crate::renderer::render_pass::Layers;
+
+/// Seconds per day.
+const DAY_SECONDS: f64 = 86_400.0;
+
+/// Days from 1970-01-01 to the J2000 epoch (2000-01-01 12:00).
+const J2000_UNIX_DAYS: f64 = 10_957.5;
+
+// ── Dates ──
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date for days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Format days since J2000 as `YYYY-MM-DDTHH:MM:SSZ` (rounded to the second).
+pub fn days_to_iso(days: f64) -> String {
+    let seconds = ((days + J2000_UNIX_DAYS) * DAY_SECONDS).round() as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(DAY_SECONDS as i64));
+    let secs = seconds.rem_euclid(DAY_SECONDS as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Calendar date `(year, month, day)` (UTC) of `days` since J2000.
+pub fn civil_date(days: f64) -> (i64, u32, u32) {
+    let seconds = ((days + J2000_UNIX_DAYS) * DAY_SECONDS).round() as i64;
+    civil_from_days(seconds.div_euclid(DAY_SECONDS as i64))
+}
+
+/// Days since J2000 for a Unix timestamp in milliseconds (e.g. `Date.getTime()`).
+pub fn unix_millis_to_days(millis: f64) -> f64 {
+    millis / 1000.0 / DAY_SECONDS - J2000_UNIX_DAYS
+}
+
+/// Parse `YYYY-MM-DD`, optionally followed by `THH:MM` or `THH:MM:SS` and a
+/// trailing `Z`, into days since J2000.
+pub fn iso_to_days(text: &str) -> Option<f64> {
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = match text.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let mut seconds = 0u32;
+    if let Some(time) = time {
+        let fields: Vec<&str> = time.split(':').collect();
+        if !(2..=3).contains(&fields.len()) {
+            return None;
+        }
+        let limits = [24, 60, 60];
+        for (field, limit) in fields.iter().zip(limits) {
+            let value: u32 = field.parse().ok()?;
+            if value >= limit {
+                return None;
+            }
+            seconds = seconds * 60 + value;
+        }
+        if fields.len() == 2 {
+            seconds *= 60;
+        }
+    }
+
+    let unix_days = days_from_civil(year, month, day) as f64 + seconds as f64 / DAY_SECONDS;
+    Some(unix_days - J2000_UNIX_DAYS)
+}
+
+// ── View state ──
+
+/// A value in the hash that could not be understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlStateError {
+    pub key: String,
+    pub value: String,
+}
+
+impl fmt::Display for UrlStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "URL state: invalid {} `{}`", self.key, self.value)
+    }
+}
+
+impl std::error::Error for UrlStateError {}
+
+/// Shareable view state. `None` fields are absent from the link and leave
+/// the app's current value alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViewState {
+    /// Simulation date in days since J2000.
+    pub days: Option<f64>,
+    /// Simulation speed in days per second.
+    pub speed: Option<f64>,
+    pub paused: Option<bool>,
+    /// Name of the selected body.
+    pub body: Option<String>,
+    /// Whether the camera follows the selected body.
+    pub locked: Option<bool>,
+    pub theta: Option<f32>,
+    pub phi: Option<f32>,
+    pub distance: Option<f32>,
+    pub layers: Option<Layers>,
+    pub labels: Option<LabelMode>,
+}
+
+fn flag(value: bool) -> &'static str {
+    if value { "1" } else { "0" }
+}
+
+impl ViewState {
+    /// Encode as a URL hash, including the leading `#`.
+    pub fn to_hash(&self) -> String {
+        let mut pairs: Vec<String> = Vec::new();
+        if let Some(days) = self.days {
+            pairs.push(format!("date={}", days_to_iso(days)));
+        }
+        if let Some(speed) = self.speed {
+            pairs.push(format!("speed={speed}"));
+        }
+        if let Some(paused) = self.paused {
+            pairs.push(format!("paused={}", flag(paused)));
+        }
+        if let Some(body) = &self.body {
+            pairs.push(format!("body={}", encode_component(body)));
+        }
+        if let Some(locked) = self.locked {
+            pairs.push(format!("lock={}", flag(locked)));
+        }
+        if let Some(theta) = self.theta {
+            pairs.push(format!("theta={theta:.3}"));
+        }
+        if let Some(phi) = self.phi {
+            pairs.push(format!("phi={phi:.3}"));
+        }
+        if let Some(distance) = self.distance {
+            pairs.push(format!("dist={distance:.2}"));
+        }
+        if let Some(layers) = self.layers {
+            let mut visible = Vec::new();
+            if layers.orbits {
+                visible.push("orbits");
+            }
+            if layers.trails {
+                visible.push("trails");
+            }
+            pairs.push(format!("layers={}", visible.join(",")));
+        }
+        if let Some(labels) = self.labels {
+            pairs.push(format!("labels={}", labels.id()));
+        }
+        format!("#{}", pairs.join("&"))
+    }
+
+    /// Decode a URL hash (with or without the leading `#`).
+    pub fn from_hash(hash: &str) -> Result<Self, UrlStateError> {
+        let mut state = ViewState::default();
+        let hash = hash.strip_prefix('#').unwrap_or(hash);
+        for pair in hash.split('&').filter(|p| !p.is_empty()) {
+            let (key, raw) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = decode_component(raw);
+            let invalid = || UrlStateError {
+                key: key.to_string(),
+                value: value.clone(),
+            };
+            let boolean = || match value.as_str() {
+                "1" | "true" => Ok(true),
+                "0" | "false" => Ok(false),
+                _ => Err(invalid()),
+            };
+            let finite = |v: &str| v.parse::<f32>().ok().filter(|v| v.is_finite());
+            match key {
+                "date" => state.days = Some(iso_to_days(&value).ok_or_else(invalid)?),
+                "speed" => {
+                    let speed = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|s| s.is_finite() && *s > 0.0);
+                    state.speed = Some(speed.ok_or_else(invalid)?);
+                }
+                "paused" => state.paused = Some(boolean()?),
+                "body" if !value.is_empty() => state.body = Some(value.clone()),
+                "body" => return Err(invalid()),
+                "lock" => state.locked = Some(boolean()?),
+                "theta" => state.theta = Some(finite(&value).ok_or_else(invalid)?),
+                "phi" => state.phi = Some(finite(&value).ok_or_else(invalid)?),
+                "dist" => {
+                    let distance = finite(&value).filter(|d| *d > 0.0);
+                    state.distance = Some(distance.ok_or_else(invalid)?);
+                }
+                "layers" => {
+                    let mut layers = Layers {
+                        orbits: false,
+                        trails: false,
+                    };
+                    for layer in value.split(',').filter(|l| !l.is_empty()) {
+                        match layer {
+                            "orbits" => layers.orbits = true,
+                            "trails" => layers.trails = true,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    state.layers = Some(layers);
+                }
+                "labels" => state.labels = Some(LabelMode::parse(&value).ok_or_else(invalid)?),
+                _ => {}
+            }
+        }
+        Ok(state)
+    }
+}
+
+/// Percent-encode everything except unreserved characters.
+fn encode_component(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// Decode `%XX` escapes; malformed escapes are kept verbatim.
+fn decode_component(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ── DOM ──
+
+/// Current `location.hash`, or an empty string.
+pub fn read_hash() -> String {
+    web_sys::window()
+        .and_then(|w| w.location().hash().ok())
+        .unwrap_or_default()
+}
+
+/// Replace the URL hash without adding a history entry or firing `hashchange`.
+pub fn write_hash(hash: &str) {
+    if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+        let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(hash));
+    }
+}
@@ -0,0 +1,394 @@
+//! Viewer construction and teardown.
+//!
+//! Each viewer owns a canvas and its own [`AppState`], so several can run
+//! side by side on one page. What a viewer touches outside its canvas is
+//! opt-in through [`ViewerOptions`]: the page's HUD callbacks,
+//! document-level keyboard and gamepad input, the URL hash, the saved
+//! session and the loading splash. The bundled page turns all of them on; an
+//! embedded viewer gets none unless asked. Every viewer does label its
+//! canvas for screen readers and adds a live region beside it (see
+//! [`accessibility`]).
+//!
+//! Pages that mount and unmount viewers call [`Viewer::destroy`], which
+//! undoes everything [`create`] set up.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use web_sys::WebGl2RenderingContext as GL;
+
+use crate::accessibility::{self, LiveRegion, Preferences};
+use crate::app::AppState;
+use crate::data::solar_system::create_solar_system;
+use crate::hud::Hud;
+use crate::i18n::{self, I18n, Locale};
+use crate::lifecycle::{EventListeners, FrameLoop};
+use crate::renderer::Renderer;
+use crate::renderer::mesh::SphereKind;
+use crate::simulation::Simulation;
+use crate::simulation::body::CelestialBody;
+use crate::url_state::{self, UrlStateError, ViewState};
+use crate::{api, input, persistence, renderer, splash};
+
+/// Plain-data viewer options (the `hud` callbacks are passed separately).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ViewerOptions {
+    /// Names of the bodies to show (the Sun is always included); all when absent.
+    pub bodies: Option<Vec<String>>,
+    /// Initial view in URL-hash syntax, e.g. `date=1969-07-20&body=Earth`.
+    pub view: Option<String>,
+    /// Listen to document keys, gamepads and the page's `solara-action` and
+    /// `solara-tour` events.
+    pub page_controls: bool,
+    /// Mirror the view in `location.hash` and follow `hashchange`.
+    pub url_hash: bool,
+    /// localStorage key for the saved session; not saved when absent.
+    pub storage_key: Option<String>,
+    /// Report loading progress to the page's splash screen.
+    pub splash: bool,
+    /// Directory body textures are fetched from.
+    pub texture_path: String,
+    /// UI language as a BCP 47 tag (e.g. `fr`); the browser's preferred
+    /// languages decide when absent or unsupported.
+    pub locale: Option<String>,
+    /// Still the starfield and make camera moves jump; follows the browser's
+    /// `prefers-reduced-motion` when absent.
+    pub reduced_motion: Option<bool>,
+    /// High-contrast orbits and labels; follows `prefers-contrast` when absent.
+    pub high_contrast: Option<bool>,
+    /// Tessellation of the body spheres.
+    pub sphere_kind: SphereKind,
+}
+
+impl Default for ViewerOptions {
+    fn default() -> Self {
+        Self {
+            bodies: None,
+            view: None,
+            page_controls: false,
+            url_hash: false,
+            storage_key: None,
+            splash: false,
+            texture_path: "textures/".into(),
+            locale: None,
+            reduced_motion: None,
+            high_contrast: None,
+            sphere_kind: SphereKind::default(),
+        }
+    }
+}
+
+impl ViewerOptions {
+    /// The bodies this viewer simulates, in catalogue order.
+    pub fn catalogue(&self) -> Result<Vec<CelestialBody>, String> {
+        let all = create_solar_system();
+        let Some(names) = &self.bodies else {
+            return Ok(all);
+        };
+        if let Some(unknown) = names
+            .iter()
+            .find(|n| !all.iter().any(|b| b.id.eq_ignore_ascii_case(n)))
+        {
+            return Err(format!("No body named {unknown}"));
+        }
+        Ok(all
+            .into_iter()
+            .filter(|b| b.is_star || names.iter().any(|n| b.id.eq_ignore_ascii_case(n)))
+            .collect())
+    }
+
+    /// The locale to use, given the browser's preferred languages.
+    pub fn locale(&self, browser: &[String]) -> Locale {
+        Locale::negotiate(
+            self.locale
+                .iter()
+                .chain(browser)
+                .map(String::as_str),
+        )
+    }
+
+    /// Accessibility preferences: the options where set, the browser's
+    /// otherwise.
+    pub fn preferences(&self, browser: Preferences) -> Preferences {
+        Preferences {
+            reduced_motion: self.reduced_motion.unwrap_or(browser.reduced_motion),
+            high_contrast: self.high_contrast.unwrap_or(browser.high_contrast),
+        }
+    }
+
+    /// The `view` option, decoded.
+    pub fn initial_view(&self) -> Result<Option<ViewState>, UrlStateError> {
+        self.view.as_deref().map(ViewState::from_hash).transpose()
+    }
+}
+
+/// Size the canvas's drawing buffer to its CSS box at the current pixel ratio.
+fn fit_canvas(canvas: &web_sys::HtmlCanvasElement) -> (u32, u32, f32) {
+    let dpr = web_sys::window().map_or(1.0, |w| w.device_pixel_ratio());
+    let width = (canvas.client_width().max(1) as f64 * dpr) as u32;
+    let height = (canvas.client_height().max(1) as f64 * dpr) as u32;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    (width, height, dpr as f32)
+}
+
+/// Resize observer on the canvas, and the callback it calls. Dropping it
+/// disconnects the observer.
+struct ResizeHandler {
+    observer: web_sys::ResizeObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for ResizeHandler {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// A running viewer, and everything [`Viewer::destroy`] has to undo.
+pub struct Viewer {
+    pub state: Rc<RefCell<AppState>>,
+    frames: Rc<FrameLoop>,
+    listeners: RefCell<EventListeners>,
+    resize: RefCell<Option<ResizeHandler>>,
+    destroyed: Cell<bool>,
+}
+
+impl Viewer {
+    /// Stop the render loop, remove every listener and delete the GL
+    /// resources. The session is saved one last time first.
+    ///
+    /// Safe to call twice, and from the viewer's own event callbacks.
+    pub fn destroy(&self) {
+        if self.destroyed.replace(true) {
+            return;
+        }
+        self.frames.stop();
+        self.resize.borrow_mut().take();
+        self.listeners.borrow_mut().remove_all();
+        {
+            let mut s = self.state.borrow_mut();
+            s.save_session();
+            s.listeners = Default::default();
+            s.pending_events.clear();
+            s.renderer.destroy();
+            if let Some(live_region) = s.live_region.take() {
+                live_region.remove();
+            }
+        }
+        api::unregister(self);
+        log::info!("🛑 Viewer destroyed");
+    }
+}
+
+/// Build a viewer on `canvas` and start its render loop.
+pub fn create(
+    canvas: web_sys::HtmlCanvasElement,
+    options: ViewerOptions,
+    hud: Hud,
+) -> Result<Rc<Viewer>, JsValue> {
+    let step = |id: &str, status: &str| {
+        if options.splash {
+            splash::update_step(id, status);
+        }
+    };
+    step("wasm", "done");
+    step("webgl", "loading");
+
+    // ── Canvas & WebGL2 ──
+    let (width, height, dpr) = fit_canvas(&canvas);
+    let gl: GL = canvas
+        .get_context("webgl2")?
+        .ok_or("WebGL2 not supported")?
+        .dyn_into::<GL>()?;
+    gl.viewport(0, 0, width as i32, height as i32);
+
+    step("webgl", "done");
+    step("simulation", "loading");
+
+    // ── Simulation ──
+    let bodies = options.catalogue()?;
+    let initial_view = options.initial_view().map_err(|e| e.to_string())?;
+    let simulation = Simulation::new(bodies.clone());
+
+    step("simulation", "done");
+    step("renderer", "loading");
+
+    // ── Renderer ──
+    let renderer = Renderer::new(gl, width, height, dpr, &bodies, options.sphere_kind)?;
+    log::info!("✨ Renderer initialized ({width}×{height})");
+
+    step("renderer", "done");
+
+    // ── Textures (async) ──
+    let gl_ref = renderer.gl_handle();
+    let tex_ref = renderer.textures_handle();
+    renderer::texture::start_loading_textures(
+        &gl_ref,
+        &tex_ref,
+        &bodies,
+        &options.texture_path,
+        options.splash,
+    );
+    log::info!("📥 Texture loading started for {} bodies", bodies.len());
+
+    // ── Shared state ──
+    let page_controls = options.page_controls;
+    let url_hash = options.url_hash;
+    let locale = options.locale(&i18n::browser_languages());
+    log::info!("🌐 Locale: {}", locale.id());
+    let state = Rc::new(RefCell::new(AppState::new(
+        renderer, simulation, options, hud, I18n::new(locale),
+    )));
+    attach(canvas, Rc::clone(&state), page_controls, url_hash, initial_view).inspect_err(|_| {
+        // Whatever `attach` set up was dropped with it, which removes the
+        // listeners; the GL resources have to be deleted by hand.
+        let mut s = state.borrow_mut();
+        s.renderer.destroy();
+        if let Some(live_region) = s.live_region.take() {
+            live_region.remove();
+        }
+    })
+}
+
+/// Wire a new viewer's `state` to its `canvas` and the page, and start
+/// drawing.
+fn attach(
+    canvas: web_sys::HtmlCanvasElement,
+    state: Rc<RefCell<AppState>>,
+    page_controls: bool,
+    url_hash: bool,
+    initial_view: Option<ViewState>,
+) -> Result<Rc<Viewer>, JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+    let mut listeners = EventListeners::default();
+
+    // ── Accessibility ──
+    {
+        let mut s = state.borrow_mut();
+        let preferences = s.options.preferences(accessibility::browser_preferences());
+        s.renderer.set_preferences(preferences);
+        match LiveRegion::attach(&canvas, &s.i18n) {
+            Ok(live_region) => s.live_region = Some(live_region),
+            Err(err) => log::warn!("⚠️ Live region: {err:?}"),
+        }
+    }
+    // Preferences the options leave open follow the browser's as they change.
+    for query in [
+        accessibility::REDUCED_MOTION_QUERY,
+        accessibility::HIGH_CONTRAST_QUERY,
+    ] {
+        let Some(list) = window.match_media(query)? else {
+            continue;
+        };
+        let state_media = Rc::clone(&state);
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            let mut s = state_media.borrow_mut();
+            let preferences = s.options.preferences(accessibility::browser_preferences());
+            s.renderer.set_preferences(preferences);
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        listeners.add(&list, "change", closure)?;
+    }
+
+    // ── Input ──
+    input::setup_input(&canvas, &state, page_controls, &mut listeners)?;
+
+    // ── Saved session, then the requested view ──
+    let storage_key = state.borrow().options.storage_key.clone();
+    if let Some(session) = storage_key.as_deref().and_then(persistence::load) {
+        state.borrow_mut().restore_session(&session);
+        log::info!("💾 Session restored");
+    }
+    if storage_key.is_some() {
+        // Save on the way out as well as periodically.
+        let state_save = Rc::clone(&state);
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            state_save.borrow_mut().save_session();
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        listeners.add(&window, "pagehide", closure)?;
+    }
+    if let Some(view) = initial_view {
+        state.borrow_mut().apply_view_state(&view, false);
+    }
+
+    // ── Deep links ──
+    // A link's view takes precedence over the saved session.
+    if url_hash {
+        let hash = url_state::read_hash();
+        if !hash.is_empty() {
+            match ViewState::from_hash(&hash) {
+                Ok(view) => state.borrow_mut().apply_view_state(&view, false),
+                Err(err) => log::warn!("⚠️ {err}"),
+            }
+        }
+        // Links pasted into an open tab fly to the new view.
+        let state_hash = Rc::clone(&state);
+        let closure = Closure::wrap(Box::new(move |_: web_sys::HashChangeEvent| {
+            match ViewState::from_hash(&url_state::read_hash()) {
+                Ok(view) => state_hash.borrow_mut().apply_view_state(&view, true),
+                Err(err) => log::warn!("⚠️ {err}"),
+            }
+        }) as Box<dyn FnMut(web_sys::HashChangeEvent)>);
+        listeners.add(&window, "hashchange", closure)?;
+    }
+
+    // ── Canvas resize ──
+    let resize = {
+        let state_resize = Rc::clone(&state);
+        let canvas_resize = canvas.clone();
+        let closure = Closure::wrap(Box::new(move |_: js_sys::Array| {
+            let (w, h, dpr) = fit_canvas(&canvas_resize);
+            state_resize.borrow_mut().renderer.resize(w, h, dpr);
+        }) as Box<dyn FnMut(js_sys::Array)>);
+        let observer = web_sys::ResizeObserver::new(closure.as_ref().unchecked_ref())?;
+        observer.observe(&canvas);
+        ResizeHandler {
+            observer,
+            _callback: closure,
+        }
+    };
+
+    // ── Render loop ──
+    let state_loop = Rc::clone(&state);
+    let frames = FrameLoop::start(move |dt| {
+        state_loop.borrow_mut().tick(dt);
+        api::flush_events(&state_loop);
+    });
+
+    // ── Hidden tabs ──
+    // Nothing is drawn while the page is hidden; the clock resumes from
+    // where it stopped rather than catching up.
+    {
+        let frames_visibility = Rc::clone(&frames);
+        let state_visibility = Rc::clone(&state);
+        let document_visibility = document.clone();
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            if document_visibility.hidden() {
+                frames_visibility.pause();
+                state_visibility.borrow_mut().save_session();
+            } else {
+                frames_visibility.resume();
+            }
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        listeners.add(&document, "visibilitychange", closure)?;
+        if document.hidden() {
+            frames.pause();
+        }
+    }
+
+    let viewer = Rc::new(Viewer {
+        state,
+        frames,
+        listeners: RefCell::new(listeners),
+        resize: RefCell::new(Some(resize)),
+        destroyed: Cell::new(false),
+    });
+    api::register(&viewer);
+
+    log::info!("🌍 SOLARA render loop started");
+    Ok(viewer)
+}
@@ -1,7 +1,7 @@
 // SOLARA — WASM bootstrap
 // Loads the compiled WASM module and starts the application.
 
-import init from '../pkg/solara.js';
+import init, { SolaraHandle } from '../pkg/solara.js';
 
 // ── Splash-screen helpers (called from Rust via wasm-bindgen) ────────────
 
@@ -31,7 +31,7 @@ window.solaraUpdateStep = function (stepId, status) {
     const statusEl = document.getElementById('splash-status');
     if (statusEl) {
         if (pct >= 100) {
-            statusEl.textContent = 'Ready!';
+            statusEl.textContent = t('splash.ready');
         } else if (status === 'loading' && el) {
             const label = el.querySelector('.step-label');
             if (label) statusEl.textContent = label.textContent + '…';
@@ -50,63 +50,149 @@ window.solaraHideSplash = function () {
     }, 400);
 };
 
-// ── HUD helpers (called from Rust via wasm-bindgen) ──────────────────────
+// ── Localisation ─────────────────────────────────────────────────────────
 
-const MS_PER_DAY = 86400000;
-let _hudFpsEma = 60;
+// The viewer's catalogue (`SolaraHandle.messages()`), loaded once it exists.
+let messages = {};
+let locale = 'en';
 
-window.solaraUpdateHud = function (days, speed, paused, fps) {
-    // Exponential moving average for smooth FPS display
-    if (fps > 0) {
-        _hudFpsEma = _hudFpsEma * 0.9 + fps * 0.1;
+// Message `key` with its `{name}` placeholders filled from `args`.
+function t(key, args = {}) {
+    let text = messages[key] ?? key;
+    for (const [name, value] of Object.entries(args)) {
+        text = text.replaceAll('{' + name + '}', value);
     }
+    return text;
+}
 
-    // Convert J2000 days (Jan 1.5 2000 = 2000-01-01T12:00Z) to a calendar date
-    const j2000Ms = Date.UTC(2000, 0, 1, 12, 0, 0);
-    const simDate  = new Date(j2000Ms + days * MS_PER_DAY);
-
-    const dateEl  = document.getElementById('hud-date');
-    const speedEl = document.getElementById('hud-speed');
-    const fpsEl   = document.getElementById('hud-fps');
+function formatNumber(value, digits) {
+    return value.toLocaleString(locale, { minimumFractionDigits: digits, maximumFractionDigits: digits });
+}
 
-    if (dateEl) {
-        dateEl.textContent = simDate.toLocaleDateString('en-US', {
-            year: 'numeric', month: 'short', day: 'numeric'
-        });
+// Apply the viewer's catalogue to the page's static text, and redraw what
+// the page formats itself.
+function translatePage(solara) {
+    messages = solara.messages();
+    locale = solara.locale();
+    document.documentElement.lang = locale;
+    for (const el of document.querySelectorAll('[data-i18n]')) el.textContent = t(el.dataset.i18n);
+    for (const el of document.querySelectorAll('[data-i18n-title]')) {
+        el.title = t(el.dataset.i18nTitle);
+        el.setAttribute('aria-label', el.title);
     }
-    if (speedEl) {
-        if (paused) {
-            speedEl.textContent = 'Paused';
-        } else if (speed >= 365.25) {
-            speedEl.textContent = '\u00d7' + (speed / 365.25).toFixed(1) + ' yr/s';
-        } else {
-            speedEl.textContent = '\u00d7' + speed.toFixed(1) + ' d/s';
-        }
+    for (const el of document.querySelectorAll('[data-i18n-label]')) el.setAttribute('aria-label', t(el.dataset.i18nLabel));
+    for (const el of document.querySelectorAll('[data-i18n-tooltip]')) el.dataset.tooltip = t(el.dataset.i18nTooltip);
+    renderBindings();
+    panelBody = null;
+}
+
+// ── HUD helpers (passed to the viewer as its `hud` callbacks) ────────────
+
+function setText(id, text) {
+    const el = document.getElementById(id);
+    if (el) el.textContent = text;
+}
+
+// Render the HUD and planet panel from a snapshot (see `HudSnapshot` in
+// pkg/solara.d.ts).
+window.solaraUpdateHud = function (hud) {
+    if (hud.locale !== locale && window.solara) translatePage(window.solara);
+    setText('hud-date', hud.dateLabel);
+    setText('hud-speed', hud.speed.label);
+    setText('hud-fps', t('hud.fps_value', { fps: Math.round(hud.fps.average) }));
+    setText('hud-gpu', t('hud.gpu_value', { draws: hud.gpu.drawCalls, state: hud.gpu.stateChanges }));
+    setText('hud-frame', t(hud.flyMode ? 'frame.fly' : 'frame.heliocentric'));
+    setText('hud-pause', hud.speed.paused ? '▶' : '⏸');
+    renderPlanetPanel(hud.selected);
+    renderLiveValues(hud.selected, hud.live);
+};
+
+// Radius of the orbit diagram, in SVG units.
+const ORBIT_DIAGRAM_RADIUS = 40;
+
+// Name of the body whose catalogue facts the panel shows, so they are only
+// written when the selection changes.
+let panelBody = null;
+
+function renderPlanetPanel(body) {
+    const panel = document.getElementById('planet-info');
+    if (!panel) return;
+    panel.classList.toggle('hidden', !body);
+    if (!body) {
+        panelBody = null;
+        return;
     }
-    if (fpsEl) {
-        fpsEl.textContent = Math.round(_hudFpsEma) + ' FPS';
+    setText('planet-lock-hint', t(body.locked ? 'panel.locked_hint' : 'panel.lock_hint'));
+    if (panelBody === body.name) return;
+    panelBody = body.name;
+    setText('planet-name', body.displayName);
+    setText('planet-radius', t('unit.km', { value: formatNumber(body.radiusKm, 0) }));
+    setText('planet-mass', t('unit.kg', { value: formatMass(body.massKg) }));
+    setText('planet-gravity', t('unit.m_s2', { value: formatNumber(body.gravity, 2) }));
+    setText('planet-escape', t('unit.km_s', { value: formatNumber(body.escapeVelocityKmS, 1) }));
+    setText('planet-day', formatHours(body.dayLengthHours));
+    setText('planet-moons', formatNumber(body.moons, 0));
+    document.getElementById('planet-orbit')?.classList.toggle('hidden', body.isStar);
+    if (body.isStar) return;
+    setText('planet-distance', t('unit.au', { value: formatNumber(body.semiMajorAxisAu, 3) }));
+    setText('planet-eccentricity', formatNumber(body.eccentricity, 4));
+    setText('planet-period', t('unit.days', { value: formatNumber(body.orbitalPeriodDays, 1) }));
+    setText('planet-inclination', formatNumber(body.inclinationDeg, 2) + '°');
+    setText('planet-speed', t('unit.km_s', { value: formatNumber(body.orbitalSpeedKmS, 2) }));
+}
+
+// Values that change every frame, written in place.
+function renderLiveValues(body, live) {
+    if (!body || !live) return;
+    setText('planet-earth-distance', live.earthDistanceAu === null
+        ? '—' : t('unit.au', { value: formatNumber(live.earthDistanceAu, 3) }));
+    setText('planet-light-time', live.earthLightSeconds === null
+        ? t('panel.light_from_sun', { time: formatLightTime(body.sunLightSeconds) })
+        : t('panel.light_from_earth', { time: formatLightTime(live.earthLightSeconds) }));
+    const dot = document.getElementById('planet-orbit-dot');
+    if (dot) {
+        // The simulation moves bodies on circles, so the diagram draws one.
+        // Screen y points down; orbits run anticlockwise seen from above.
+        const r = ORBIT_DIAGRAM_RADIUS;
+        dot.setAttribute('cx', (r * Math.cos(live.orbitAngle)).toFixed(2));
+        dot.setAttribute('cy', (-r * Math.sin(live.orbitAngle)).toFixed(2));
     }
+}
 
-    // Sync pause button icon
-    const pauseBtn = document.getElementById('hud-pause');
-    if (pauseBtn) pauseBtn.textContent = paused ? '▶' : '⏸';
-};
+function formatHours(hours) {
+    return hours >= 48
+        ? t('unit.days', { value: formatNumber(hours / 24, 1) })
+        : t('unit.hours', { value: formatNumber(hours, 1) });
+}
+
+function formatLightTime(seconds) {
+    if (seconds < 60) return t('unit.seconds', { value: formatNumber(seconds, 1) });
+    if (seconds < 3600) return t('unit.minutes', { value: formatNumber(seconds / 60, 1) });
+    return t('unit.hours', { value: formatNumber(seconds / 3600, 2) });
+}
+
+// `1.99 × 10^30`, with the mantissa in the page's locale.
+function formatMass(kg) {
+    const exponent = Math.floor(Math.log10(kg));
+    return formatNumber(kg / 10 ** exponent, 2) + ' × 10^' + exponent;
+}
 
 window.solaraToggleHud = function () {
     const hud = document.getElementById('hud');
     if (hud) hud.classList.toggle('hidden');
 };
 
-// ── Speed control buttons → keyboard event bridge ────────────────────────
+// ── Speed control buttons → action bridge ────────────────────────────────
 
-function dispatchKey(key) {
-    document.dispatchEvent(new KeyboardEvent('keydown', { key, bubbles: true }));
+// Buttons trigger actions by id, so they keep working when keys are remapped.
+function dispatchAction(action) {
+    window.dispatchEvent(new CustomEvent('solara-action', { detail: action }));
 }
 
-document.getElementById('hud-pause')?.addEventListener('click', () => dispatchKey(' '));
-document.getElementById('hud-slower')?.addEventListener('click', () => dispatchKey('-'));
-document.getElementById('hud-faster')?.addEventListener('click', () => dispatchKey('+'));
-document.getElementById('hud-reset')?.addEventListener('click', () => dispatchKey('R'));
+document.getElementById('hud-pause')?.addEventListener('click', () => dispatchAction('toggle_pause'));
+document.getElementById('hud-slower')?.addEventListener('click', () => dispatchAction('speed_down'));
+document.getElementById('hud-faster')?.addEventListener('click', () => dispatchAction('speed_up'));
+document.getElementById('hud-reset')?.addEventListener('click', () => dispatchAction('reset_speed'));
 
 // ── Camera control buttons ───────────────────────────────────────────────
 
@@ -125,8 +211,8 @@ document.getElementById('hud-zoom-out')?.addEventListener('click', () => {
     }
 });
 
-document.getElementById('hud-home')?.addEventListener('click', () => dispatchKey('Home'));
-document.getElementById('hud-top-view')?.addEventListener('click', () => dispatchKey('t'));
+document.getElementById('hud-home')?.addEventListener('click', () => dispatchAction('recenter'));
+document.getElementById('hud-top-view')?.addEventListener('click', () => dispatchAction('top_down_view'));
 
 // ── Help panel toggle ────────────────────────────────────────────────────
 
@@ -134,30 +220,72 @@ function toggleHelp() {
     const panel = document.getElementById('help-panel');
     if (panel) panel.classList.toggle('hidden');
 }
+window.solaraToggleHelp = toggleHelp;
 
 document.getElementById('hud-help')?.addEventListener('click', toggleHelp);
 document.getElementById('help-close')?.addEventListener('click', toggleHelp);
 
 // Close help on Escape
 document.addEventListener('keydown', (e) => {
-    if (e.key === '?' || (e.key === '/' && e.shiftKey)) {
-        e.preventDefault();
-        toggleHelp();
-    } else if (e.key === 'Escape') {
+    if (e.key === 'Escape') {
         const panel = document.getElementById('help-panel');
         panel?.classList.add('hidden');
     }
 });
 
-// ── Debounced resize ─────────────────────────────────────────────────────
+// Keyboard sections of the help panel, rebuilt from the live binding table.
+// `rows` is an array of [section, keys, description, action], already in
+// the viewer's language.
+let bindingRows = [];
 
-let resizeTimer;
-window.addEventListener('resize', () => {
-    clearTimeout(resizeTimer);
-    resizeTimer = setTimeout(() => {
-        window.dispatchEvent(new CustomEvent('solara-resize'));
-    }, 150);
-});
+window.solaraSetBindings = function (rows) {
+    bindingRows = rows;
+    renderBindings();
+};
+
+function renderBindings() {
+    const container = document.getElementById('help-bindings');
+    if (!container) return;
+    container.replaceChildren();
+    const rows = bindingRows;
+
+    const sections = new Map();
+    for (const [section, keys, description, action] of rows) {
+        if (!sections.has(section)) {
+            const el = document.createElement('div');
+            el.className = 'help-section';
+            const title = document.createElement('h4');
+            title.textContent = section;
+            el.appendChild(title);
+            container.appendChild(el);
+            sections.set(section, el);
+        }
+        const row = document.createElement('div');
+        row.className = 'help-row';
+        row.title = t('help.run');
+        row.addEventListener('click', () => dispatchAction(action));
+        const kbd = document.createElement('kbd');
+        kbd.textContent = keys;
+        const label = document.createElement('span');
+        label.textContent = description;
+        row.append(kbd, label);
+        sections.get(section).appendChild(row);
+    }
+}
+
+// ── Tours ────────────────────────────────────────────────────────────────
+
+// Fetch a tour file and hand its JSON to the engine.
+window.solaraPlayTour = async function (url) {
+    try {
+        const response = await fetch(url);
+        if (!response.ok) throw new Error(response.status + ' ' + response.statusText);
+        const text = await response.text();
+        window.dispatchEvent(new CustomEvent('solara-tour', { detail: text }));
+    } catch (error) {
+        console.warn('SOLARA: could not load tour ' + url + ':', error);
+    }
+};
 
 // ── Boot ──────────────────────────────────────────────────────────────────
 
@@ -165,7 +293,28 @@ async function run() {
     try {
         window.solaraUpdateStep('wasm', 'loading');
         await init();
-        // init() calls start() → updates all engine steps synchronously.
+        const params = new URLSearchParams(window.location.search);
+        // The page's viewer gets every page-level integration; embedded
+        // viewers opt in to these individually (see src/viewer.rs).
+        window.solara = new SolaraHandle(document.getElementById('solara-canvas'), {
+            hud: {
+                update: window.solaraUpdateHud,
+                bindings: window.solaraSetBindings,
+                toggle: window.solaraToggleHud,
+                toggleHelp: window.solaraToggleHelp,
+            },
+            pageControls: true,
+            urlHash: true,
+            storageKey: 'solara.session',
+            splash: true,
+            // ?lang=fr overrides the browser's language.
+            locale: params.get('lang') ?? undefined,
+        });
+        translatePage(window.solara);
+        // ?tour=grand-tour plays tours/grand-tour.json once the engine is up.
+        const tour = params.get('tour');
+        if (tour) window.solaraPlayTour('tours/' + encodeURIComponent(tour) + '.json');
+        // The constructor updates all engine steps synchronously.
         // Texture loads continue asynchronously; texture.rs hides the splash
         // when the last one finishes.
     } catch (error) {